@@ -0,0 +1,173 @@
+//! Compare the rope backed `TextBuffer` against the previous `Vec<Vec<char>>` storage of the text
+//! editor on a 5 MB section.
+//!
+//! Run with `cargo bench --bench text_buffer`.
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::hint::black_box;
+
+#[path = "../src/app/widget/text_editor/text_buffer.rs"]
+mod text_buffer;
+
+use text_buffer::TextBuffer;
+
+const SECTION_SIZE: usize = 5 * 1024 * 1024;
+
+/// The storage used by the text editor before `TextBuffer`, kept here as the baseline.
+struct VecLines {
+    lines: Vec<Vec<char>>,
+}
+impl VecLines {
+    fn from_str(content: &str) -> Self {
+        Self {
+            lines: content
+                .split('\n')
+                .map(|line| line.chars().collect())
+                .collect(),
+        }
+    }
+    fn insert_char(&mut self, x: usize, y: usize, c: char) {
+        let line = &mut self.lines[y];
+        let x = x.min(line.len());
+        line.insert(x, c);
+    }
+    fn remove_char(&mut self, x: usize, y: usize) {
+        let line = &mut self.lines[y];
+        if x < line.len() {
+            line.remove(x);
+        }
+    }
+    fn insert_line(&mut self, at: usize) {
+        self.lines.insert(at, Vec::new());
+    }
+    fn remove_line(&mut self, at: usize) {
+        self.lines.remove(at);
+    }
+    fn join(&self) -> String {
+        let mut lines = self.lines.clone();
+        let line_number = lines.len();
+        for line in &mut lines[0..line_number - 1] {
+            line.push('\n');
+        }
+        lines.concat().iter().collect()
+    }
+}
+
+/// Something like a pasted log, short lines with an occasional very long one.
+fn section() -> String {
+    let mut content = String::with_capacity(SECTION_SIZE + 1024);
+    let mut i = 0;
+    while content.len() < SECTION_SIZE {
+        if i % 500 == 0 {
+            content.push_str(&"x".repeat(16 * 1024));
+        } else {
+            content.push_str(&format!(
+                "2025-01-01T00:00:{:02} INFO request {} finished in {}ms",
+                i % 60,
+                i,
+                i % 997
+            ));
+        }
+        content.push('\n');
+        i += 1;
+    }
+    content
+}
+
+fn bench_load(c: &mut Criterion, content: &str) {
+    let mut group = c.benchmark_group("load");
+    group.sample_size(10);
+    group.bench_function("vec_lines", |b| {
+        b.iter(|| VecLines::from_str(black_box(content)))
+    });
+    group.bench_function("text_buffer", |b| {
+        b.iter(|| TextBuffer::from(black_box(content)))
+    });
+    group.bench_function("vec_lines_to_string", |b| {
+        let lines = VecLines::from_str(content);
+        b.iter(|| lines.join())
+    });
+    group.bench_function("text_buffer_to_string", |b| {
+        let buffer = TextBuffer::from(content);
+        b.iter(|| buffer.to_string())
+    });
+    group.finish();
+}
+
+fn bench_insert(c: &mut Criterion, content: &str) {
+    let mut group = c.benchmark_group("insert");
+    let lines = VecLines::from_str(content);
+    let buffer = TextBuffer::from(content);
+    let middle = lines.lines.len() / 2;
+    // Typing in the middle of a long line, then opening new lines.
+    group.bench_function("vec_lines", |b| {
+        b.iter_batched_ref(
+            || VecLines {
+                lines: lines.lines.clone(),
+            },
+            |lines| {
+                for i in 0..100 {
+                    lines.insert_char(8 * 1024 + i, 0, 'a');
+                    lines.insert_line(middle);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("text_buffer", |b| {
+        b.iter_batched_ref(
+            || buffer.clone(),
+            |buffer| {
+                for i in 0..100 {
+                    buffer.insert_char(8 * 1024 + i, 0, 'a');
+                    buffer.insert_line(middle, "");
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_delete(c: &mut Criterion, content: &str) {
+    let mut group = c.benchmark_group("delete");
+    let lines = VecLines::from_str(content);
+    let buffer = TextBuffer::from(content);
+    let middle = lines.lines.len() / 2;
+    group.bench_function("vec_lines", |b| {
+        b.iter_batched_ref(
+            || VecLines {
+                lines: lines.lines.clone(),
+            },
+            |lines| {
+                for _ in 0..100 {
+                    lines.remove_char(8 * 1024, 0);
+                    lines.remove_line(middle);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("text_buffer", |b| {
+        b.iter_batched_ref(
+            || buffer.clone(),
+            |buffer| {
+                for _ in 0..100 {
+                    buffer.remove_char(8 * 1024, 0);
+                    buffer.remove_line(middle);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    let content = section();
+    bench_load(c, &content);
+    bench_insert(c, &content);
+    bench_delete(c, &content);
+}
+
+criterion_group!(text_buffer_benches, benches);
+criterion_main!(text_buffer_benches);
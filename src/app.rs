@@ -1,8 +1,22 @@
+use crate::app::event::{AppEvent, Timer, Timers};
+use crate::app::keymap::{keymap, Action};
+use crate::app::page::glyph_create_page::GlyphCreatePage;
+use crate::app::page::glyph_open_page::GlyphOpenPage;
+use crate::app::popup::command_palette::CommandPalette;
 use crate::app::popup::message_popup::MessagePopup;
-use crate::theme::{Iceberg, Theme};
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use crate::app::popup::help_popup::HelpPopup;
+use crate::app::popup::log_popup::LogPopup;
+use crate::app::popup::quit_popup::QuitPopup;
+use crate::app::popup::theme_popup::ThemePopup;
+use crate::config::Config;
+use crate::crash;
+use crate::recent::{self, RecentGlyph};
+use crate::theme::{ColorSupport, Theme};
+use crate::utils::type_title;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
 use page::entrance_page::EntrancePage;
 use page::glyph_page::GlyphPage;
+use page::glyph_tabs_page::GlyphTabsPage;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::prelude::{Color, Stylize};
 use ratatui::text::{Line, Span};
@@ -11,8 +25,11 @@ use ratatui::Frame;
 use rusqlite::Connection;
 use std::any::Any;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 pub mod dialog;
+pub mod event;
+pub mod keymap;
 pub mod page;
 pub mod popup;
 pub mod widget;
@@ -29,6 +46,20 @@ pub enum AppCommand {
     PopPage,
     PushPopup(Box<dyn Container>),
     PopPopup,
+    /// Execute the action as if its key was pressed.
+    DispatchAction(Action),
+    /// Deliver `AppEvent::Timer` with the name of the timer once it is due.
+    StartTimer(Timer),
+    StopTimer(String),
+    /// Save the unsaved entries of every glyph, the commands queued after it are dropped on failure.
+    SaveUnsavedEntries,
+    /// Give up the unsaved entries of every glyph, including their recovery journal, the commands
+    /// queued after it are dropped on failure.
+    DiscardUnsavedEntries,
+    /// Open the glyph in a new tab, or switch to its tab when it is opened already.
+    OpenTab(Box<GlyphPage>),
+    /// Remember where the glyph is left, and show it on the entrance page.
+    RecordRecentGlyph(RecentGlyph),
 }
 pub enum GlyphCommand {
     OpenGlyph(PathBuf),           // Path to Glyph DB
@@ -42,6 +73,46 @@ pub enum PageCommand {
     PushDialog(Box<dyn Container>),
     PopDialog,
 }
+impl Command {
+    /// Name the command, with its arguments when they are plain data.
+    pub fn describe(&self) -> String {
+        match self {
+            Command::AppCommand(app_command) => match app_command {
+                AppCommand::Quit => String::from("Quit"),
+                AppCommand::PushPage(_) => String::from("PushPage"),
+                AppCommand::PopPage => String::from("PopPage"),
+                AppCommand::PushPopup(_) => String::from("PushPopup"),
+                AppCommand::PopPopup => String::from("PopPopup"),
+                AppCommand::DispatchAction(action) => format!("DispatchAction({:?})", action),
+                AppCommand::StartTimer(timer) => format!("StartTimer({})", timer.name),
+                AppCommand::StopTimer(name) => format!("StopTimer({})", name),
+                AppCommand::SaveUnsavedEntries => String::from("SaveUnsavedEntries"),
+                AppCommand::DiscardUnsavedEntries => String::from("DiscardUnsavedEntries"),
+                AppCommand::OpenTab(glyph_page) => format!("OpenTab({})", glyph_page.name()),
+                AppCommand::RecordRecentGlyph(recent_glyph) => {
+                    format!("RecordRecentGlyph({})", recent_glyph.path.display())
+                }
+            },
+            Command::GlyphCommand(glyph_command) => match glyph_command {
+                GlyphCommand::OpenGlyph(path) => format!("OpenGlyph({})", path.display()),
+                GlyphCommand::CreateGlyph(path, name) => {
+                    format!("CreateGlyph({}, {})", path.display(), name)
+                }
+                GlyphCommand::CreateEntry(name) => format!("CreateEntry({})", name),
+                GlyphCommand::SetEntryUnsavedState(eid, is_unsaved) => {
+                    format!("SetEntryUnsavedState({}, {})", eid, is_unsaved)
+                }
+                GlyphCommand::RefreshEditSectionEditor => String::from("RefreshEditSectionEditor"),
+                GlyphCommand::RefreshLayoutEditPanel => String::from("RefreshLayoutEditPanel"),
+            },
+            Command::PageCommand(page_command) => match page_command {
+                PageCommand::PushDialog(_) => String::from("PushDialog"),
+                PageCommand::PopDialog => String::from("PopDialog"),
+            },
+            Command::Data(_) => String::from("Data"),
+        }
+    }
+}
 
 #[macro_export]
 macro_rules! block {
@@ -59,6 +130,9 @@ macro_rules! block {
         }
     };
 }
+/// Lines scrolled by a notch of the mouse wheel.
+pub const MOUSE_SCROLL_LINES: u16 = 3;
+
 pub enum DrawFlag {
     DEFAULT = 0b0000_0000,
     HIGHLIGHTING = 0b0000_0001,
@@ -88,7 +162,7 @@ pub(crate) fn get_draw_flag(
 }
 
 pub trait Drawable {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme);
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme);
 }
 pub trait Interactable: Convertible {
     fn handle(
@@ -97,10 +171,39 @@ pub trait Interactable: Convertible {
         parent_state: Option<&mut dyn Any>,
     ) -> color_eyre::Result<Vec<Command>>;
 
-    /// Get a descriptive key bindings action, default to none,
+    /// Actions handled by the component, default to none.
+    fn actions(&self) -> Vec<Action> {
+        Vec::new()
+    }
+    /// Get a descriptive key bindings action, default to the bindings of `actions`,
     /// It does nothing but telling users the key available.
     fn keymap(&self) -> Vec<(&str, &str)> {
-        Vec::new()
+        keymap::describe(&self.actions())
+    }
+    /// Key bindings listed in the help overlay, grouped under a title.
+    /// Default to `keymap` under the name of the component.
+    fn help(&self) -> Vec<(String, Vec<(&str, &str)>)> {
+        vec![(type_title(std::any::type_name::<Self>()), self.keymap())]
+    }
+    /// Handle a mouse event, its position is relative to the terminal. Default to ignore it.
+    fn handle_mouse(
+        &mut self,
+        _mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> color_eyre::Result<Vec<Command>> {
+        Ok(Vec::new())
+    }
+    /// Whether printable keys are taken as text, global actions bound to them are ignored meanwhile.
+    fn is_typing(&self) -> bool {
+        false
+    }
+    /// Handle an event which is neither a key nor a mouse event. Default to ignore it.
+    fn handle_event(
+        &mut self,
+        _event: &AppEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> color_eyre::Result<Vec<Command>> {
+        Ok(Vec::new())
     }
 }
 pub trait Focusable {
@@ -195,40 +298,65 @@ impl<T: Interactable + Drawable + Focusable> Container for T {
 
 // Global State of the Application
 pub struct AppState {
-    pub theme: Iceberg,
+    pub theme: Theme,
+    /// Themes to switch between, the built-in ones followed by the user ones.
+    pub themes: Vec<Theme>,
     pub should_quit: bool,
+    pub config: Config,
+    /// Colors of the terminal, every theme is degraded to them.
+    pub color_support: ColorSupport,
+}
+impl AppState {
+    /// Start with the theme of the config, every theme is degraded to the colors of the terminal.
+    pub fn new(config: Config, user_themes: Vec<Theme>) -> Self {
+        let color_support: ColorSupport = ColorSupport::detect();
+        let themes: Vec<Theme> = Theme::builtins()
+            .into_iter()
+            .chain(user_themes)
+            .map(|theme| theme.degraded(color_support))
+            .collect();
+        let theme: Theme = themes
+            .iter()
+            .find(|theme| theme.name == config.theme)
+            .cloned()
+            .unwrap_or_else(|| Theme::default().degraded(color_support));
+        Self {
+            theme,
+            themes,
+            should_quit: false,
+            config,
+            color_support,
+        }
+    }
 }
 pub struct Application {
     pub page_states: Vec<Box<dyn Container>>,
     pub popup_states: Vec<Box<dyn Container>>,
     pub q_commands: Vec<Command>,
+    pub timers: Timers,
     pub state: AppState,
 }
 
 impl Application {
-    pub fn new() -> Application {
+    pub fn new(state: AppState) -> Application {
         Application {
             page_states: vec![Box::new(EntrancePage::new())],
             popup_states: Vec::new(),
-            state: AppState {
-                theme: Iceberg,
-                should_quit: false,
-            },
+            state,
             q_commands: Vec::new(),
+            timers: Timers::default(),
         }
     }
-    pub fn from(connection: Connection) -> Application {
+    pub fn from(connection: Connection, state: AppState) -> Application {
         Application {
             page_states: vec![
                 EntrancePage::new().into(),
-                GlyphPage::new(connection).into(),
+                GlyphTabsPage::new(GlyphPage::new(connection, &state.config)).into(),
             ],
             popup_states: Vec::new(),
-            state: AppState {
-                theme: Iceberg,
-                should_quit: false,
-            },
+            state,
             q_commands: Vec::new(),
+            timers: Timers::default(),
         }
     }
     pub(crate) fn view_to_focus_ref(&self) -> Option<&dyn Container> {
@@ -262,6 +390,42 @@ impl Application {
         Some(self.popup_states.len() - 1)
     }
 
+    /// Actions available from every container along the focus, deepest first.
+    pub(crate) fn focused_actions(&self) -> Vec<Action> {
+        let containers: Vec<&dyn Container> = self
+            .view_to_focus_ref()
+            .map(focus_chain)
+            .unwrap_or_default();
+        let mut actions: Vec<Action> = Vec::new();
+        for container in containers.iter().rev() {
+            for action in container.actions() {
+                if !actions.contains(&action) {
+                    actions.push(action);
+                }
+            }
+        }
+        actions
+    }
+    /// Key bindings of the containers along the focus, from the outermost one, then the global ones.
+    pub(crate) fn help_groups(&self) -> Vec<(String, Vec<(String, String)>)> {
+        let mut groups: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        for container in self
+            .view_to_focus_ref()
+            .map(focus_chain)
+            .unwrap_or_default()
+        {
+            for (title, bindings) in container.help() {
+                if !bindings.is_empty() {
+                    groups.push((title, to_owned_bindings(bindings)));
+                }
+            }
+        }
+        groups.push((
+            String::from("Global"),
+            to_owned_bindings(keymap::describe(&GLOBAL_ACTIONS)),
+        ));
+        groups
+    }
     /// Recursively find the bottom container user is interacting.
     pub(crate) fn focused_container_ref(&self) -> Option<&dyn Container> {
         let mut temp: Option<&dyn Container> = None;
@@ -303,45 +467,119 @@ pub fn keymap_to_line<'a>(key_map: Vec<(&'a str, &'a str)>) -> Line<'a> {
     line.dim()
 }
 pub fn handle_key_events(key: &KeyEvent, app: &mut Application) {
-    handle_global_events(key, app);
-    if (*app).view_to_focus_mut().is_none() {
+    if handle_global_events(key, app) {
+        process_command(app);
         return;
     }
+    let mut commands: Vec<Command> = dispatch_key(key, app);
+    app.q_commands.append(&mut commands);
 
-    // Retrieve the Command from Page/Popup
+    process_command(app);
+}
+pub fn handle_mouse_events(mouse: &MouseEvent, app: &mut Application) {
     let mut commands: Vec<Command> = Vec::new();
     if let Some(popup_index) = (*app).focused_popup_index() {
         commands = app.popup_states[popup_index]
-            .handle(key, Some(&mut app.state))
-            .unwrap_or_else(|report| {
-                vec![Command::AppCommand(AppCommand::PushPopup(
-                    MessagePopup::new(report.to_string().as_str(), Color::Red).into(),
-                ))]
-            });
+            .handle_mouse(mouse, Some(&mut app.state))
+            .unwrap_or_else(error_commands);
     } else if let Some(page_index) = (*app).focused_page_index() {
         commands = app.page_states[page_index]
-            .handle(key, Some(&mut app.state))
-            .unwrap_or_else(|report| {
-                vec![Command::AppCommand(AppCommand::PushPopup(
-                    MessagePopup::new(report.to_string().as_str(), Color::Red).into(),
-                ))]
-            });
+            .handle_mouse(mouse, Some(&mut app.state))
+            .unwrap_or_else(error_commands);
     }
     app.q_commands.append(&mut commands);
 
     process_command(app);
 }
+/// Deliver the event to every page and popup, from the bottom one.
+pub fn handle_app_events(event: &AppEvent, app: &mut Application) {
+    let mut commands: Vec<Command> = Vec::new();
+    for container in app
+        .page_states
+        .iter_mut()
+        .chain(app.popup_states.iter_mut())
+    {
+        commands.append(
+            &mut container
+                .handle_event(event, Some(&mut app.state))
+                .unwrap_or_else(error_commands),
+        );
+    }
+    app.q_commands.append(&mut commands);
+
+    process_command(app);
+}
+/// Type the pasted text into the focused container. The rest of the text is dropped once the
+/// container stops typing, so that pasted text would never trigger any action.
+pub fn handle_paste_events(text: &str, app: &mut Application) {
+    for char in text.chars() {
+        if !app
+            .focused_container_ref()
+            .is_some_and(|container| container.is_typing())
+        {
+            return;
+        }
+        let key: KeyEvent = match char {
+            '\r' => continue,
+            '\n' => KeyEvent::from(KeyCode::Enter),
+            _ => KeyEvent::from(KeyCode::Char(char)),
+        };
+        let mut commands: Vec<Command> = dispatch_key(&key, app);
+        app.q_commands.append(&mut commands);
+        process_command(app);
+    }
+}
+/// Deliver the timers due by now.
+pub fn handle_timers(app: &mut Application) {
+    for name in app.timers.take_due(Instant::now()) {
+        handle_app_events(&AppEvent::Timer(name), app);
+    }
+}
+/// Retrieve the Command from Page/Popup
+fn dispatch_key(key: &KeyEvent, app: &mut Application) -> Vec<Command> {
+    let mut commands: Vec<Command> = Vec::new();
+    if let Some(popup_index) = (*app).focused_popup_index() {
+        commands = app.popup_states[popup_index]
+            .handle(key, Some(&mut app.state))
+            .unwrap_or_else(error_commands);
+    } else if let Some(page_index) = (*app).focused_page_index() {
+        commands = app.page_states[page_index]
+            .handle(key, Some(&mut app.state))
+            .unwrap_or_else(error_commands);
+    }
+    commands
+}
 fn process_command(app: &mut Application) {
     // Process the Command
     while let Some(command) = app.q_commands.pop() {
+        crash::record_command(command.describe());
+        log::debug!("Process {}", command.describe());
         match command {
             Command::AppCommand(app_command) => match app_command {
                 AppCommand::PushPage(view) => {
                     app.page_states.push(view);
+                    record_recent_glyph(app, app.page_states.len() - 1);
                 }
                 AppCommand::PopPage => {
+                    if let Some(index) = app.page_states.len().checked_sub(1) {
+                        record_recent_glyph(app, index);
+                    }
                     app.page_states.pop();
                 }
+                AppCommand::OpenTab(glyph_page) => {
+                    match app
+                        .page_states
+                        .last_mut()
+                        .and_then(|page| (**page).as_any_mut().downcast_mut::<GlyphTabsPage>())
+                    {
+                        Some(tabs_page) => tabs_page.open(*glyph_page),
+                        None => app.page_states.push(GlyphTabsPage::new(*glyph_page).into()),
+                    }
+                    record_recent_glyph(app, app.page_states.len() - 1);
+                }
+                AppCommand::RecordRecentGlyph(recent_glyph) => {
+                    record(app, recent_glyph);
+                }
                 AppCommand::PushPopup(popup) => {
                     app.popup_states.push(popup);
                 }
@@ -349,7 +587,61 @@ fn process_command(app: &mut Application) {
                     app.popup_states.pop();
                 }
                 AppCommand::Quit => {
-                    app.state.should_quit = true;
+                    let unsaved_entry_names: Vec<String> = unsaved_entry_names(app);
+                    if unsaved_entry_names.is_empty() {
+                        app.state.should_quit = true;
+                    } else if !app
+                        .popup_states
+                        .last()
+                        .is_some_and(|popup| (**popup).as_any().is::<QuitPopup>())
+                    {
+                        app.popup_states
+                            .push(QuitPopup::new(unsaved_entry_names).into());
+                    }
+                }
+                AppCommand::DispatchAction(action) => {
+                    let mut commands: Vec<Command> = match global_action_commands(action, app) {
+                        Some(commands) => commands,
+                        None => match keymap().bindings(action).first() {
+                            Some(binding) => dispatch_key(&binding.to_key_event(), app),
+                            None => Vec::new(),
+                        },
+                    };
+                    app.q_commands.append(&mut commands);
+                }
+                AppCommand::StartTimer(timer) => {
+                    app.timers.start(timer, Instant::now());
+                }
+                AppCommand::StopTimer(name) => {
+                    app.timers.stop(&name);
+                }
+                AppCommand::SaveUnsavedEntries => {
+                    let result = glyph_pages_mut(app).into_iter().try_for_each(|page| {
+                        page.state
+                            .entry_state
+                            .borrow_mut()
+                            .save_updated_entries_db()
+                            .map(|_num_saved| ())
+                    });
+                    if let Err(report) = result {
+                        app.q_commands.clear();
+                        app.popup_states.push(
+                            MessagePopup::new(report.to_string().as_str(), Color::Red).into(),
+                        );
+                    }
+                }
+                AppCommand::DiscardUnsavedEntries => {
+                    let result = glyph_pages_mut(app).into_iter().try_for_each(|page| {
+                        let mut entry_state = page.state.entry_state.borrow_mut();
+                        entry_state.updated_entries.clear();
+                        entry_state.discard_journal()
+                    });
+                    if let Err(report) = result {
+                        app.q_commands.clear();
+                        app.popup_states.push(
+                            MessagePopup::new(report.to_string().as_str(), Color::Red).into(),
+                        );
+                    }
                 }
             },
             _ => {
@@ -359,58 +651,179 @@ fn process_command(app: &mut Application) {
         }
     }
 }
-fn handle_global_events(key: &KeyEvent, app: &mut Application) {
+/// Actions available regardless of the focus.
+const GLOBAL_ACTIONS: [Action; 6] = [
+    Action::OpenPalette,
+    Action::Help,
+    Action::SwitchTheme,
+    Action::OpenGlyph,
+    Action::CreateGlyph,
+    Action::Quit,
+];
+/// Global actions left out of the help overlay and the palette.
+const HIDDEN_GLOBAL_ACTIONS: [Action; 1] = [Action::ShowLog];
+/// Handle the keys available everywhere, return whether the key has been consumed.
+fn handle_global_events(key: &KeyEvent, app: &mut Application) -> bool {
     if key.kind == KeyEventKind::Press {
-        if let KeyCode::F(num) = key.code {
-            match num {
-                1 => {
-                    app.state.should_quit = true;
-                }
-                2 => {}
-                3 => {}
-                _ => {}
+        let is_text: bool = matches!(key.code, KeyCode::Char(_))
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let is_typing: bool = app
+            .focused_container_ref()
+            .is_some_and(|container| container.is_typing());
+        if !(is_text && is_typing)
+            && let Some(action) = keymap()
+                .action(key, &GLOBAL_ACTIONS)
+                .or_else(|| keymap().action(key, &HIDDEN_GLOBAL_ACTIONS))
+            && let Some(mut commands) = global_action_commands(action, app)
+        {
+            app.q_commands.append(&mut commands);
+            return true;
+        }
+    }
+    false
+}
+fn global_action_commands(action: Action, app: &Application) -> Option<Vec<Command>> {
+    match action {
+        Action::Quit => Some(vec![Command::AppCommand(AppCommand::Quit)]),
+        Action::OpenPalette => {
+            // The shortcut closes the palette when it is already opened.
+            if let Some(popup) = app.popup_states.last()
+                && (**popup).as_any().is::<CommandPalette>()
+            {
+                return Some(vec![Command::AppCommand(AppCommand::PopPopup)]);
+            }
+            let mut actions: Vec<Action> = app
+                .focused_actions()
+                .into_iter()
+                .filter(|action| !keymap().bindings(*action).is_empty())
+                .collect();
+            actions.extend(GLOBAL_ACTIONS.iter().filter(|a| **a != Action::OpenPalette));
+            Some(vec![Command::AppCommand(AppCommand::PushPopup(
+                CommandPalette::new(actions).into(),
+            ))])
+        }
+        Action::Help => {
+            if let Some(popup) = app.popup_states.last()
+                && (**popup).as_any().is::<HelpPopup>()
+            {
+                return Some(vec![Command::AppCommand(AppCommand::PopPopup)]);
+            }
+            Some(vec![Command::AppCommand(AppCommand::PushPopup(
+                HelpPopup::new(app.help_groups()).into(),
+            ))])
+        }
+        Action::SwitchTheme => {
+            if let Some(popup) = app.popup_states.last()
+                && (**popup).as_any().is::<ThemePopup>()
+            {
+                return Some(vec![Command::AppCommand(AppCommand::PopPopup)]);
+            }
+            Some(vec![Command::AppCommand(AppCommand::PushPopup(
+                ThemePopup::new(app.state.themes.clone(), app.state.theme.clone()).into(),
+            ))])
+        }
+        Action::ShowLog => {
+            if let Some(popup) = app.popup_states.last()
+                && (**popup).as_any().is::<LogPopup>()
+            {
+                return Some(vec![Command::AppCommand(AppCommand::PopPopup)]);
             }
+            Some(vec![Command::AppCommand(AppCommand::PushPopup(
+                LogPopup::new().into(),
+            ))])
         }
+        Action::OpenGlyph => Some(vec![Command::AppCommand(AppCommand::PushPage(
+            GlyphOpenPage::new(&app.state.config).into(),
+        ))]),
+        Action::CreateGlyph => Some(vec![Command::AppCommand(AppCommand::PushPage(
+            GlyphCreatePage::new(&app.state.config).into(),
+        ))]),
+        _ => None,
     }
 }
 
 /*
    Helper Function
 */
-pub fn is_cycle_forward_hover_key(key_event: &KeyEvent) -> bool {
-    if let KeyCode::Char(c) = key_event.code {
-        return match c {
-            'j' => true,
-            _ => false,
-        };
+/// Show the error to the user, and log it.
+fn error_commands(report: color_eyre::Report) -> Vec<Command> {
+    log::error!("{}", report);
+    vec![Command::AppCommand(AppCommand::PushPopup(
+        MessagePopup::new(report.to_string().as_str(), Color::Red).into(),
+    ))]
+}
+fn glyph_pages_mut(app: &mut Application) -> Vec<&mut GlyphPage> {
+    app.page_states
+        .iter_mut()
+        .filter_map(|page| (**page).as_any_mut().downcast_mut::<GlyphTabsPage>())
+        .flat_map(|tabs_page| tabs_page.tabs.iter_mut())
+        .collect()
+}
+/// Remember where the opened glyphs are left, the active tab of the top page being the latest.
+pub fn record_recent_glyphs(app: &mut Application) {
+    for index in 0..app.page_states.len() {
+        record_recent_glyph(app, index);
     }
-    if let KeyCode::Down = key_event.code {
-        return true;
+}
+/// Remember where the glyphs of the page are left, its active tab being the latest.
+fn record_recent_glyph(app: &mut Application, page_index: usize) {
+    let Some(tabs_page) = (*app.page_states[page_index])
+        .as_any()
+        .downcast_ref::<GlyphTabsPage>()
+    else {
+        return;
+    };
+    let recent_glyphs: Vec<RecentGlyph> = tabs_page
+        .tabs_by_activity()
+        .filter_map(GlyphPage::recent_glyph)
+        .collect();
+    for recent_glyph in recent_glyphs {
+        record(app, recent_glyph);
     }
-    if let KeyCode::Right = key_event.code {
-        return true;
+}
+/// Remember where the glyph is left, and show it on the entrance page.
+fn record(app: &mut Application, recent_glyph: RecentGlyph) {
+    match recent::record(recent_glyph) {
+        Ok(recent_glyphs) => {
+            for page in app.page_states.iter_mut() {
+                if let Some(entrance_page) = (**page).as_any_mut().downcast_mut::<EntrancePage>() {
+                    entrance_page.state.recent_glyphs = recent_glyphs.clone();
+                }
+            }
+        }
+        Err(report) => log::warn!("Recent glyphs are not saved: {}", report),
     }
-    if let KeyCode::Tab = key_event.code {
-        return true;
+}
+/// Names of the unsaved entries across the opened glyphs.
+fn unsaved_entry_names(app: &Application) -> Vec<String> {
+    app.page_states
+        .iter()
+        .filter_map(|page| (**page).as_any().downcast_ref::<GlyphTabsPage>())
+        .flat_map(|tabs_page| tabs_page.tabs.iter())
+        .flat_map(|page| page.state.entry_state.borrow().get_unsaved_entry_names())
+        .collect()
+}
+/// The container followed by its focused child, recursively.
+pub(crate) fn focus_chain(container: &dyn Container) -> Vec<&dyn Container> {
+    let mut containers: Vec<&dyn Container> = vec![container];
+    while let Some(child) = containers.last().unwrap().focused_child_ref() {
+        containers.push(child);
     }
-    false
+    containers
+}
+fn to_owned_bindings(bindings: Vec<(&str, &str)>) -> Vec<(String, String)> {
+    bindings
+        .into_iter()
+        .map(|(key, description)| (key.to_string(), description.to_string()))
+        .collect()
+}
+
+pub fn is_cycle_forward_hover_key(key_event: &KeyEvent) -> bool {
+    keymap().matches(key_event, Action::NextItem)
 }
 
 pub fn is_cycle_backward_hover_key(key_event: &KeyEvent) -> bool {
-    if let KeyCode::Char(c) = key_event.code {
-        return match c {
-            'k' => true,
-            _ => false,
-        };
-    }
-    if let KeyCode::Up = key_event.code {
-        return true;
-    }
-    if let KeyCode::Left = key_event.code {
-        return true;
-    }
-    if let KeyCode::BackTab = key_event.code {
-        return true;
-    }
-    false
+    keymap().matches(key_event, Action::PreviousItem)
 }
@@ -1,4 +1,5 @@
 pub mod confirm_dialog;
 pub mod number_input_dialog;
 pub mod search_entry_dialog;
+pub mod template_dialog;
 pub mod text_input_dialog;
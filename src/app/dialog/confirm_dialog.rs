@@ -19,6 +19,8 @@ pub struct ConfirmDialogState {
     pub is_focused: bool,
     pub hovered_index: Option<usize>,
 }
+/// Called with the parent state.
+type CancelCallback = Box<dyn FnOnce(Option<&mut dyn Any>) -> Result<Vec<Command>>>;
 pub struct ConfirmDialog {
     pub components: Vec<Box<dyn Component>>,
     pub state: ConfirmDialogState,
@@ -26,14 +28,13 @@ pub struct ConfirmDialog {
 
     pub on_submit:
         Option<Box<dyn FnOnce(Option<&mut dyn Any>, Option<&mut dyn Any>) -> Result<Vec<Command>>>>,
+    pub on_cancel: Option<CancelCallback>,
 }
 impl ConfirmDialog {
     pub fn new(message: &str) -> Self {
         Self {
             components: vec![
-                LineButton::new("Back")
-                    .on_interact(Box::new(|_| Ok(vec![PageCommand(PopDialog)])))
-                    .into(),
+                LineButton::new("Back").into(),
                 LineButton::new("Confirm").into(),
             ],
             state: ConfirmDialogState {
@@ -42,6 +43,7 @@ impl ConfirmDialog {
             },
             message: String::from(message),
             on_submit: None,
+            on_cancel: None,
         }
     }
 
@@ -55,6 +57,20 @@ impl ConfirmDialog {
         self
     }
 
+    /// Called with the parent state when the dialog is dismissed without confirming.
+    pub fn on_cancel(mut self, on_cancel: CancelCallback) -> Self {
+        self.on_cancel = Some(on_cancel);
+        self
+    }
+    fn cancel(&mut self, parent_state: Option<&mut dyn Any>) -> Result<Vec<Command>> {
+        let mut commands: Vec<Command> = match self.on_cancel.take() {
+            Some(on_cancel) => on_cancel(parent_state)?,
+            None => Vec::new(),
+        };
+        commands.push(PageCommand(PopDialog));
+        Ok(commands)
+    }
+
     pub(crate) fn cycle_hover(&mut self, offset: i16) {
         let max: u16 = self.components.len() as u16;
         if let Some(hover_index) = self.state.hovered_index {
@@ -70,7 +86,7 @@ impl From<ConfirmDialog> for Box<dyn Container> {
     }
 }
 impl Drawable for ConfirmDialog {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let dialog_area: Rect = area.centered(Constraint::Length(42), Constraint::Length(5));
         let dialog_frame = Block::bordered()
             .border_type(match draw_flag {
@@ -114,7 +130,7 @@ impl Interactable for ConfirmDialog {
         match key.kind {
             KeyEventKind::Press => {
                 if let KeyCode::Esc = key.code {
-                    return Ok(vec![PageCommand(PopDialog)]);
+                    return self.cancel(parent_state);
                 }
                 if is_cycle_forward_hover_key(key) {
                     self.cycle_hover(1)
@@ -128,10 +144,11 @@ impl Interactable for ConfirmDialog {
                     return match index {
                         0 => {
                             // Back Button
-                            self.components[0].handle(key, None)
+                            self.cancel(parent_state)
                         }
                         1 => {
                             // Confirm Button
+                            log::info!("Confirm {:?}", self.message);
                             if let Some(on_submit) = self.on_submit.take() {
                                 let callback_result =
                                     on_submit(parent_state, Some(&mut self.state));
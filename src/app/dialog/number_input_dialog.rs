@@ -100,7 +100,7 @@ impl From<NumberInputDialog> for Box<dyn Container> {
 }
 
 impl Drawable for NumberInputDialog {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let dialog_area: Rect = area.centered(Constraint::Length(42), Constraint::Length(5));
         let back_button = (*self.components[0])
             .as_any()
@@ -182,6 +182,7 @@ impl Interactable for NumberInputDialog {
                                 if !self.is_valid_input() {
                                     return Ok(Vec::new());
                                 }
+                                log::info!("Submit number input {}", self.state.number_input);
                                 if let Some(on_submit) = self.on_submit.take() {
                                     let callback_result =
                                         on_submit(parent_state, Some(&mut self.state));
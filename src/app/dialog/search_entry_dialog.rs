@@ -87,7 +87,7 @@ impl From<SearchEntryDialog> for Box<dyn Container> {
 }
 
 impl Drawable for SearchEntryDialog {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let dialog_frame = block!(self.state.label.clone(), draw_flag, theme);
         let dialog_area: Rect = area.centered(Constraint::Length(64), Constraint::Percentage(50));
         let dialog_inner_area: Rect = dialog_frame.inner(dialog_area);
@@ -140,8 +140,11 @@ impl Interactable for SearchEntryDialog {
                 if let KeyCode::Esc = key.code {
                     return Ok(vec![PageCommand(PopDialog)]);
                 }
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    if let KeyCode::Char('n') = key.code {
+                // c-p opens the command palette, hence the arrow keys.
+                let is_control: bool = key.modifiers.contains(KeyModifiers::CONTROL);
+                if is_control || matches!(key.code, KeyCode::Up | KeyCode::Down) {
+                    if key.code == KeyCode::Down || (is_control && key.code == KeyCode::Char('n'))
+                    {
                         self.state.hovered_index = cycle_offset(
                             self.state.hovered_index as u16,
                             1,
@@ -152,7 +155,8 @@ impl Interactable for SearchEntryDialog {
                                 .count() as u16,
                         ) as usize;
                     }
-                    if let KeyCode::Char('p') = key.code {
+                    if key.code == KeyCode::Up || (is_control && key.code == KeyCode::Char('p'))
+                    {
                         self.state.hovered_index = cycle_offset(
                             self.state.hovered_index as u16,
                             -1,
@@ -166,6 +170,7 @@ impl Interactable for SearchEntryDialog {
                     return Ok(vec![]);
                 }
                 if let KeyCode::Enter = key.code {
+                    log::info!("Submit entry search at {}", self.state.hovered_index);
                     if let Some(on_submit) = self.on_submit.take() {
                         return on_submit(_parent_state, Some(&mut self.state));
                     } else {
@@ -181,12 +186,15 @@ impl Interactable for SearchEntryDialog {
     }
     fn keymap(&self) -> Vec<(&str, &str)> {
         [
-            ("c-n", "Next Item"),
-            ("c-p", "Previous Item"),
+            ("c-n/down", "Next Item"),
+            ("c-p/up", "Previous Item"),
             ("Enter", "Open Entry"),
         ]
         .into()
     }
+    fn is_typing(&self) -> bool {
+        true
+    }
 }
 impl Focusable for SearchEntryDialog {
     fn is_focused(&self) -> bool {
@@ -0,0 +1,206 @@
+use crate::app::Command::PageCommand;
+use crate::app::PageCommand::PopDialog;
+use crate::app::{
+    is_cycle_backward_hover_key, is_cycle_forward_hover_key, Command, Container, DrawFlag,
+    Drawable, Focusable, Interactable,
+};
+use crate::block;
+use crate::models::layout::{Layout, LayoutOrientation};
+use crate::models::template::LayoutTemplate;
+use crate::theme::Theme;
+use crate::utils::cycle_offset;
+use color_eyre::{Report, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, Widget};
+use ratatui::Frame;
+use std::any::Any;
+
+pub struct TemplateDialogState {
+    pub label: String,
+    pub is_focused: bool,
+    pub hovered_index: usize,
+    pub templates: Vec<LayoutTemplate>,
+}
+impl TemplateDialogState {
+    pub fn hovered_template(&self) -> Option<&LayoutTemplate> {
+        self.templates.get(self.hovered_index)
+    }
+}
+/// Called with the parent state and the state of the dialog.
+type SubmitCallback =
+    Box<dyn FnOnce(Option<&mut dyn Any>, Option<&mut dyn Any>) -> Result<Vec<Command>>>;
+/// Pick a layout template, the hovered one is previewed aside.
+pub struct TemplateDialog {
+    state: TemplateDialogState,
+    on_submit: Option<SubmitCallback>,
+}
+impl TemplateDialog {
+    pub fn new(label: &str, templates: Vec<LayoutTemplate>) -> Self {
+        Self {
+            state: TemplateDialogState {
+                label: label.to_string(),
+                is_focused: true,
+                hovered_index: 0,
+                templates,
+            },
+            on_submit: None,
+        }
+    }
+    pub fn on_submit(mut self, on_submit: SubmitCallback) -> Self {
+        self.on_submit = Some(on_submit);
+        self
+    }
+}
+impl From<TemplateDialog> for Box<dyn Container> {
+    fn from(dialog: TemplateDialog) -> Self {
+        Box::new(dialog)
+    }
+}
+
+impl Drawable for TemplateDialog {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
+        let dialog_frame = block!(self.state.label.clone(), draw_flag, theme);
+        let dialog_area: Rect = area.centered(Constraint::Length(80), Constraint::Percentage(50));
+        let dialog_inner_area: Rect = dialog_frame.inner(dialog_area);
+        let [list_area, preview_area] =
+            ratatui::layout::Layout::horizontal([Constraint::Length(28), Constraint::Fill(1)])
+                .areas(dialog_inner_area);
+        Clear.render(dialog_area, frame.buffer_mut());
+        dialog_frame.render(dialog_area, frame.buffer_mut());
+
+        // List Area
+        let list_border: Block = Block::bordered().title("Templates");
+        let list_inner_area: Rect = list_border.inner(list_area);
+        list_border.render(list_area, frame.buffer_mut());
+        if self.state.templates.is_empty() {
+            Line::from("No template saved")
+                .dim()
+                .render(list_inner_area, frame.buffer_mut());
+        }
+        let rows = list_inner_area.rows().collect::<Vec<Rect>>();
+        for (index, template) in self.state.templates.iter().enumerate() {
+            if index >= rows.len() {
+                break;
+            }
+            let prefix = match index == self.state.hovered_index {
+                true => "> ",
+                false => "  ",
+            };
+            let suffix = match template.id {
+                Some(_) => "",
+                None => " (built-in)",
+            };
+            let mut line: Line =
+                Line::from([prefix, template.name.as_str(), suffix].concat()).dim();
+            if index == self.state.hovered_index {
+                line = line.bold().not_dim();
+            }
+            line.render(rows[index], frame.buffer_mut());
+        }
+
+        // Preview Area
+        let preview_border: Block = Block::bordered().title("Preview");
+        let preview_inner_area: Rect = preview_border.inner(preview_area);
+        preview_border.render(preview_area, frame.buffer_mut());
+        if let Some(template) = self.state.hovered_template() {
+            render_preview(&template.layout, preview_inner_area, frame.buffer_mut());
+        }
+    }
+}
+/// Outline the panels of the layout, labelled after them.
+fn render_preview(layout: &Layout, area: Rect, buffer: &mut Buffer) {
+    if layout.sub_layouts.is_empty() {
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(layout.label.as_str())
+            .dim()
+            .render(area, buffer);
+        return;
+    }
+    let constraints: Vec<Constraint> = layout
+        .sub_layouts
+        .iter()
+        .map(|sub_layout| sub_layout.details.constraint())
+        .collect();
+    let sub_areas = match layout.details.orientation {
+        LayoutOrientation::Vertical => ratatui::layout::Layout::vertical(constraints).split(area),
+        LayoutOrientation::Horizontal => {
+            ratatui::layout::Layout::horizontal(constraints).split(area)
+        }
+        // Only the first child of a stack is shown.
+        LayoutOrientation::Stack => {
+            render_preview(&layout.sub_layouts[0], area, buffer);
+            return;
+        }
+    };
+    for (sub_layout, sub_area) in layout.sub_layouts.iter().zip(sub_areas.iter()) {
+        render_preview(sub_layout, *sub_area, buffer);
+    }
+}
+
+impl Interactable for TemplateDialog {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                if let KeyCode::Esc = key.code {
+                    return Ok(vec![PageCommand(PopDialog)]);
+                }
+                let count: u16 = self.state.templates.len() as u16;
+                if is_cycle_forward_hover_key(key) && count > 0 {
+                    self.state.hovered_index =
+                        cycle_offset(self.state.hovered_index as u16, 1, count) as usize;
+                }
+                if is_cycle_backward_hover_key(key) && count > 0 {
+                    self.state.hovered_index =
+                        cycle_offset(self.state.hovered_index as u16, -1, count) as usize;
+                }
+                if let KeyCode::Enter = key.code
+                    && let Some(template) = self.state.hovered_template()
+                {
+                    log::info!("Submit layout template {:?}", template.name);
+                    return if let Some(on_submit) = self.on_submit.take() {
+                        let mut commands = on_submit(parent_state, Some(&mut self.state))?;
+                        commands.push(PageCommand(PopDialog));
+                        Ok(commands)
+                    } else {
+                        Err(Report::msg("Submit has already been called!"))
+                    };
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn keymap(&self) -> Vec<(&str, &str)> {
+        [
+            ("j/k/up/down/tab/backtab", "Navigate"),
+            ("Enter", "Choose Template"),
+        ]
+        .into()
+    }
+}
+impl Focusable for TemplateDialog {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
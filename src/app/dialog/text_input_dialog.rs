@@ -95,7 +95,7 @@ impl From<TextInputDialog> for Box<dyn Container> {
 }
 
 impl Drawable for TextInputDialog {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let dialog_area: Rect = area.centered(Constraint::Length(64), Constraint::Length(5));
         let back_button = (*self.components[0])
             .as_any()
@@ -177,6 +177,7 @@ impl Interactable for TextInputDialog {
                                 if !self.is_valid_input() {
                                     return Ok(Vec::new());
                                 }
+                                log::info!("Submit text input");
                                 if let Some(on_submit) = self.on_submit.take() {
                                     let callback_result =
                                         on_submit(parent_state, Some(&mut self.state));
@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+/// Interval between two `AppEvent::Tick`s when nothing else happens.
+pub const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Events which are not keys nor mouse events, delivered to every page and popup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppEvent {
+    Tick,
+    /// The new width and height of the terminal.
+    Resize(u16, u16),
+    FocusGained,
+    FocusLost,
+    /// A timer started with `AppCommand::StartTimer` is due, carrying its name.
+    Timer(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Timer {
+    /// Identify the timer, starting a timer with the same name replaces it.
+    pub name: String,
+    pub interval: Duration,
+    pub repeat: bool,
+}
+impl Timer {
+    pub fn once(name: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            name: name.into(),
+            interval,
+            repeat: false,
+        }
+    }
+    pub fn repeat(name: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            name: name.into(),
+            interval,
+            repeat: true,
+        }
+    }
+}
+
+/// Timers registered by the containers, along with their next deadline.
+#[derive(Default)]
+pub struct Timers {
+    timers: Vec<(Timer, Instant)>,
+}
+impl Timers {
+    pub fn start(&mut self, timer: Timer, now: Instant) {
+        self.stop(&timer.name);
+        let deadline: Instant = now + timer.interval;
+        self.timers.push((timer, deadline));
+    }
+    pub fn stop(&mut self, name: &str) {
+        self.timers.retain(|(timer, _deadline)| timer.name != name);
+    }
+    pub fn is_running(&self, name: &str) -> bool {
+        self.timers
+            .iter()
+            .any(|(timer, _deadline)| timer.name == name)
+    }
+    /// The earliest deadline among the timers, if any.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers.iter().map(|(_timer, deadline)| *deadline).min()
+    }
+    /// Names of the timers due at `now`, repeating ones are rescheduled and the others removed.
+    pub fn take_due(&mut self, now: Instant) -> Vec<String> {
+        let mut due: Vec<String> = Vec::new();
+        self.timers.retain_mut(|(timer, deadline)| {
+            if *deadline > now {
+                return true;
+            }
+            due.push(timer.name.clone());
+            if timer.repeat {
+                *deadline = now + timer.interval;
+            }
+            timer.repeat
+        });
+        due
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timers_take_due() {
+        let now: Instant = Instant::now();
+        let mut timers: Timers = Timers::default();
+        timers.start(Timer::once("once", Duration::from_secs(1)), now);
+        timers.start(Timer::repeat("repeat", Duration::from_secs(2)), now);
+        assert_eq!(timers.next_deadline(), Some(now + Duration::from_secs(1)));
+        assert!(timers.take_due(now).is_empty());
+
+        assert_eq!(timers.take_due(now + Duration::from_secs(1)), vec!["once"]);
+        assert!(!timers.is_running("once"));
+        assert_eq!(
+            timers.take_due(now + Duration::from_secs(2)),
+            vec!["repeat"]
+        );
+        assert_eq!(timers.next_deadline(), Some(now + Duration::from_secs(4)));
+    }
+    #[test]
+    fn test_timers_restart_replaces() {
+        let now: Instant = Instant::now();
+        let mut timers: Timers = Timers::default();
+        timers.start(Timer::once("save", Duration::from_secs(1)), now);
+        timers.start(Timer::once("save", Duration::from_secs(5)), now);
+        assert_eq!(timers.next_deadline(), Some(now + Duration::from_secs(5)));
+        timers.stop("save");
+        assert_eq!(timers.next_deadline(), None);
+    }
+}
@@ -0,0 +1,573 @@
+use crate::utils::config_dir;
+use color_eyre::eyre::{Report, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Everything the user could trigger with a key outside of text inputs.
+///
+/// The snake case name of an action is the key used in `keys.toml`/`keys.json`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    // Global
+    Quit,
+    OpenPalette,
+    Help,
+    SwitchTheme,
+    /// Hidden from the help overlay and the palette, meant for debugging.
+    ShowLog,
+    OpenGlyph,
+    CreateGlyph,
+    // Navigation
+    NextItem,
+    PreviousItem,
+    Interact,
+    Back,
+    ScrollUp,
+    ScrollDown,
+    ScrollPageUp,
+    ScrollPageDown,
+    // Glyph Tabs
+    NextTab,
+    PreviousTab,
+    MoveTabLeft,
+    MoveTabRight,
+    CloseTab,
+    CopyEntryToGlyph,
+    CopySectionToGlyph,
+    // Glyph Page
+    FindEntry,
+    ToggleNavigationBar,
+    Undo,
+    Redo,
+    // Navigation Bar
+    CreateEntry,
+    FilterEntry,
+    RenameEntry,
+    DuplicateEntry,
+    DeleteEntry,
+    CreateEntryFromTemplate,
+    // Glyph View
+    CycleMode,
+    SaveEntry,
+    // Read Mode
+    PrintEntry,
+    NextPanel,
+    PreviousPanel,
+    NextStackTab,
+    PreviousStackTab,
+    NextStack,
+    // Edit Mode
+    EditSection,
+    CreateSection,
+    RenameSection,
+    DeleteSection,
+    IncreaseSectionPosition,
+    DecreaseSectionPosition,
+    // Layout Mode
+    EditLayout,
+    CreateLayout,
+    CloneLayout,
+    DeleteLayout,
+    SwapLayoutForward,
+    SwapLayoutBackward,
+    MoveLayout,
+    WrapLayout,
+    UnwrapLayout,
+    DuplicateLayout,
+    EditLayoutText,
+    NextLayoutSection,
+    PreviousLayoutSection,
+    ToggleLayoutOrientation,
+    PreviewBreakpoint,
+    SaveLayoutTemplate,
+    ApplyLayoutTemplate,
+    DeleteLayoutTemplate,
+    LinkSharedLayout,
+    DetachSharedLayout,
+}
+impl Action {
+    pub const ALL: [Action; 66] = [
+        Action::Quit,
+        Action::OpenPalette,
+        Action::Help,
+        Action::SwitchTheme,
+        Action::ShowLog,
+        Action::OpenGlyph,
+        Action::CreateGlyph,
+        Action::NextItem,
+        Action::PreviousItem,
+        Action::Interact,
+        Action::Back,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ScrollPageUp,
+        Action::ScrollPageDown,
+        Action::NextTab,
+        Action::PreviousTab,
+        Action::MoveTabLeft,
+        Action::MoveTabRight,
+        Action::CloseTab,
+        Action::CopyEntryToGlyph,
+        Action::CopySectionToGlyph,
+        Action::FindEntry,
+        Action::ToggleNavigationBar,
+        Action::Undo,
+        Action::Redo,
+        Action::CreateEntry,
+        Action::FilterEntry,
+        Action::RenameEntry,
+        Action::DuplicateEntry,
+        Action::DeleteEntry,
+        Action::CreateEntryFromTemplate,
+        Action::CycleMode,
+        Action::SaveEntry,
+        Action::PrintEntry,
+        Action::NextPanel,
+        Action::PreviousPanel,
+        Action::NextStackTab,
+        Action::PreviousStackTab,
+        Action::NextStack,
+        Action::EditSection,
+        Action::CreateSection,
+        Action::RenameSection,
+        Action::DeleteSection,
+        Action::IncreaseSectionPosition,
+        Action::DecreaseSectionPosition,
+        Action::EditLayout,
+        Action::CreateLayout,
+        Action::CloneLayout,
+        Action::DeleteLayout,
+        Action::SwapLayoutForward,
+        Action::SwapLayoutBackward,
+        Action::MoveLayout,
+        Action::WrapLayout,
+        Action::UnwrapLayout,
+        Action::DuplicateLayout,
+        Action::EditLayoutText,
+        Action::NextLayoutSection,
+        Action::PreviousLayoutSection,
+        Action::ToggleLayoutOrientation,
+        Action::PreviewBreakpoint,
+        Action::SaveLayoutTemplate,
+        Action::ApplyLayoutTemplate,
+        Action::DeleteLayoutTemplate,
+        Action::LinkSharedLayout,
+        Action::DetachSharedLayout,
+    ];
+    /// Descriptive name shown to the user.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::OpenPalette => "Command Palette",
+            Action::Help => "Help",
+            Action::SwitchTheme => "Switch Theme",
+            Action::ShowLog => "Show Log",
+            Action::OpenGlyph => "Open Glyph",
+            Action::CreateGlyph => "Create Glyph",
+            Action::NextItem => "Next",
+            Action::PreviousItem => "Previous",
+            Action::Interact => "Interact",
+            Action::Back => "Back",
+            Action::ScrollUp => "Scroll Up",
+            Action::ScrollDown => "Scroll Down",
+            Action::ScrollPageUp => "Page Up",
+            Action::ScrollPageDown => "Page Down",
+            Action::NextTab => "Next Tab",
+            Action::PreviousTab => "Previous Tab",
+            Action::MoveTabLeft => "Move Tab Left",
+            Action::MoveTabRight => "Move Tab Right",
+            Action::CloseTab => "Close Tab",
+            Action::CopyEntryToGlyph => "Copy Active Entry to other Glyph",
+            Action::CopySectionToGlyph => "Copy Active Section to other Glyph",
+            Action::FindEntry => "Find Entry",
+            Action::ToggleNavigationBar => "Fold Navigation Bar",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::CreateEntry => "Create Entry",
+            Action::FilterEntry => "Filter Entry",
+            Action::RenameEntry => "Rename Active Entry",
+            Action::DuplicateEntry => "Duplicate Active Entry",
+            Action::DeleteEntry => "Delete Active Entry",
+            Action::CreateEntryFromTemplate => "Create Entry from Template",
+            Action::CycleMode => "Switch Mode",
+            Action::SaveEntry => "Save Active Entry",
+            Action::PrintEntry => "Print to txt",
+            Action::NextPanel => "Focus Next Panel",
+            Action::PreviousPanel => "Focus Previous Panel",
+            Action::NextStackTab => "Next Stacked Panel",
+            Action::PreviousStackTab => "Previous Stacked Panel",
+            Action::NextStack => "Target Next Stack",
+            Action::EditSection => "Edit Active Section",
+            Action::CreateSection => "Create Default Section",
+            Action::RenameSection => "Rename Active Section",
+            Action::DeleteSection => "Delete Active Section",
+            Action::IncreaseSectionPosition => "Increase Section Position",
+            Action::DecreaseSectionPosition => "Decrease Section Position",
+            Action::EditLayout => "Edit (With Active Layout)",
+            Action::CreateLayout => "Create Sub Layout",
+            Action::CloneLayout => "Clone Layout to other Entry",
+            Action::DeleteLayout => "Delete Active Layout",
+            Action::SwapLayoutForward => "Swap Layout with Next Sibling",
+            Action::SwapLayoutBackward => "Swap Layout with Previous Sibling",
+            Action::MoveLayout => "Mark Layout / Move Marked Layout Here",
+            Action::WrapLayout => "Wrap Layout in Container",
+            Action::UnwrapLayout => "Unwrap Layout Container",
+            Action::DuplicateLayout => "Duplicate Layout",
+            Action::EditLayoutText => "Edit Layout as Text",
+            Action::NextLayoutSection => "Target Next Section",
+            Action::PreviousLayoutSection => "Target Previous Section",
+            Action::ToggleLayoutOrientation => "Change Layout Orientation",
+            Action::PreviewBreakpoint => "Preview Next Breakpoint",
+            Action::SaveLayoutTemplate => "Save Layout as Template",
+            Action::ApplyLayoutTemplate => "Apply Layout Template",
+            Action::DeleteLayoutTemplate => "Delete Layout Template",
+            Action::LinkSharedLayout => "Link Layout to Shared Template",
+            Action::DetachSharedLayout => "Detach Shared Layout",
+        }
+    }
+    fn default_bindings(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["F1"],
+            Action::OpenPalette => &["c-p"],
+            Action::Help => &["F2", "?"],
+            Action::SwitchTheme => &["F3"],
+            Action::ShowLog => &["F12"],
+            Action::OpenGlyph => &[],
+            Action::CreateGlyph => &[],
+            Action::NextItem => &["j", "down", "right", "tab"],
+            Action::PreviousItem => &["k", "up", "left", "backtab"],
+            Action::Interact => &["enter"],
+            Action::Back => &["esc"],
+            Action::ScrollUp => &["up"],
+            Action::ScrollDown => &["down"],
+            Action::ScrollPageUp => &["pageup"],
+            Action::ScrollPageDown => &["pagedown"],
+            Action::NextTab => &["a-l", "a-right"],
+            Action::PreviousTab => &["a-h", "a-left"],
+            Action::MoveTabLeft => &["a-H"],
+            Action::MoveTabRight => &["a-L"],
+            Action::CloseTab => &["a-w"],
+            Action::CopyEntryToGlyph => &["a-e"],
+            Action::CopySectionToGlyph => &["a-s"],
+            Action::FindEntry => &["F"],
+            Action::ToggleNavigationBar => &["c-b"],
+            Action::Undo => &["u"],
+            Action::Redo => &["c-r"],
+            Action::CreateEntry => &["A"],
+            Action::FilterEntry => &["F"],
+            Action::RenameEntry => &["R"],
+            Action::DuplicateEntry => &["D"],
+            Action::DeleteEntry => &["x"],
+            Action::CreateEntryFromTemplate => &["T"],
+            Action::CycleMode => &["\\"],
+            Action::SaveEntry => &["c-s"],
+            Action::PrintEntry => &["P"],
+            Action::NextPanel => &["tab"],
+            Action::PreviousPanel => &["backtab"],
+            Action::NextStackTab => &["]"],
+            Action::PreviousStackTab => &["["],
+            Action::NextStack => &["}"],
+            Action::EditSection => &["e"],
+            Action::CreateSection => &["A"],
+            Action::RenameSection => &["R"],
+            Action::DeleteSection => &["x"],
+            Action::IncreaseSectionPosition => &["+"],
+            Action::DecreaseSectionPosition => &["-"],
+            Action::EditLayout => &["e"],
+            Action::CreateLayout => &["A"],
+            Action::CloneLayout => &["D"],
+            Action::DeleteLayout => &["x"],
+            Action::SwapLayoutForward => &["J"],
+            Action::SwapLayoutBackward => &["K"],
+            Action::MoveLayout => &["m"],
+            Action::WrapLayout => &["w"],
+            Action::UnwrapLayout => &["W"],
+            Action::DuplicateLayout => &["y"],
+            Action::EditLayoutText => &["E"],
+            Action::NextLayoutSection => &["+"],
+            Action::PreviousLayoutSection => &["-"],
+            Action::ToggleLayoutOrientation => &["t"],
+            Action::PreviewBreakpoint => &["b"],
+            Action::SaveLayoutTemplate => &["S"],
+            Action::ApplyLayoutTemplate => &["T"],
+            Action::DeleteLayoutTemplate => &["X"],
+            Action::LinkSharedLayout => &["L"],
+            Action::DetachSharedLayout => &["U"],
+        }
+    }
+}
+
+/// A key along with the modifiers that must be held, written like `A`, `c-s` or `pagedown`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+impl KeyBinding {
+    /// Shift is carried by the char itself, so only Control and Alt are compared.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code
+            && self.modifiers == key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+    /// A key press triggering the binding.
+    pub fn to_key_event(self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+impl FromStr for KeyBinding {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut modifiers: KeyModifiers = KeyModifiers::NONE;
+        let mut rest: &str = s;
+        loop {
+            if rest.len() > 2 && rest.starts_with("c-") {
+                modifiers |= KeyModifiers::CONTROL;
+            } else if rest.len() > 2 && rest.starts_with("a-") {
+                modifiers |= KeyModifiers::ALT;
+            } else {
+                break;
+            }
+            rest = &rest[2..];
+        }
+        let mut chars = rest.chars();
+        let code: KeyCode = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').map(|num| num.parse::<u8>()) {
+                    Some(Ok(num)) if (1..=12).contains(&num) => KeyCode::F(num),
+                    _ => return Err(Report::msg(format!("Unknown key \"{}\"", s))),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "c-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "a-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(num) => write!(f, "F{}", num),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::BackTab => write!(f, "backtab"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            code => write!(f, "{}", code.to_string().to_lowercase()),
+        }
+    }
+}
+
+/// Either a single key or a list of keys in the config file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Bindings {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The keys bound to each action.
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+    descriptions: HashMap<Action, String>,
+}
+impl Keymap {
+    /// The default bindings.
+    pub fn new() -> Self {
+        let mut keymap = Self {
+            bindings: HashMap::new(),
+            descriptions: HashMap::new(),
+        };
+        for action in Action::ALL {
+            let bindings: Vec<KeyBinding> = action
+                .default_bindings()
+                .iter()
+                .map(|binding| binding.parse().unwrap())
+                .collect();
+            keymap.bind(action, bindings);
+        }
+        keymap
+    }
+    /// Load the default bindings overridden by `keys.toml` or `keys.json` in the config directory.
+    pub fn load() -> Result<Self> {
+        let keymap: Keymap = Self::new();
+        let Some(dir) = config_dir() else {
+            return Ok(keymap);
+        };
+        let toml_path = dir.join("keys.toml");
+        if toml_path.exists() {
+            let overrides = toml::from_str(&read_config(&toml_path)?)
+                .map_err(|e| Report::msg(format!("{}: {}", toml_path.display(), e)))?;
+            return keymap.with_overrides(overrides);
+        }
+        let json_path = dir.join("keys.json");
+        if json_path.exists() {
+            let overrides = serde_json::from_str(&read_config(&json_path)?)
+                .map_err(|e| Report::msg(format!("{}: {}", json_path.display(), e)))?;
+            return keymap.with_overrides(overrides);
+        }
+        Ok(keymap)
+    }
+    /// Check whether the key triggers the action.
+    pub fn matches(&self, key: &KeyEvent, action: Action) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.matches(key))
+    }
+    /// Find the first of the candidate actions triggered by the key.
+    pub fn action(&self, key: &KeyEvent, candidates: &[Action]) -> Option<Action> {
+        candidates
+            .iter()
+            .find(|action| self.matches(key, **action))
+            .copied()
+    }
+    pub fn bindings(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map_or(&[], |v| v.as_slice())
+    }
+    /// The keys bound to the action joined like `j/down`.
+    pub fn describe(&self, action: Action) -> &str {
+        self.descriptions.get(&action).map_or("", |v| v.as_str())
+    }
+    // Helper functions
+    fn bind(&mut self, action: Action, bindings: Vec<KeyBinding>) {
+        let description: String = bindings
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect::<Vec<String>>()
+            .join("/");
+        self.descriptions.insert(action, description);
+        self.bindings.insert(action, bindings);
+    }
+    fn with_overrides(mut self, overrides: HashMap<Action, Bindings>) -> Result<Self> {
+        for (action, bindings) in overrides {
+            let bindings: Vec<String> = match bindings {
+                Bindings::One(binding) => vec![binding],
+                Bindings::Many(bindings) => bindings,
+            };
+            let bindings: Vec<KeyBinding> = bindings
+                .iter()
+                .map(|binding| binding.parse())
+                .collect::<Result<Vec<KeyBinding>>>()?;
+            self.bind(action, bindings);
+        }
+        Ok(self)
+    }
+}
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_config(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| Report::msg(format!("{}: {}", path.display(), e)))
+}
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// The bindings in use, the default ones unless `init` loaded the user's.
+pub fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::new)
+}
+/// Load the user's bindings once at startup, the default ones are kept if they are invalid.
+pub fn init() -> Result<()> {
+    let (keymap, result) = match Keymap::load() {
+        Ok(keymap) => (keymap, Ok(())),
+        Err(report) => (Keymap::new(), Err(report)),
+    };
+    let _ = KEYMAP.set(keymap);
+    result
+}
+/// Pair the actions with their keys, ready for `keymap_to_line`.
+pub fn describe(actions: &[Action]) -> Vec<(&'static str, &'static str)> {
+    let keymap: &'static Keymap = keymap();
+    actions
+        .iter()
+        .map(|action| (keymap.describe(*action), action.label()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_key_bindings() {
+        let binding: KeyBinding = "c-s".parse().unwrap();
+        assert_eq!(binding.code, KeyCode::Char('s'));
+        assert_eq!(binding.modifiers, KeyModifiers::CONTROL);
+        assert_eq!("-".parse::<KeyBinding>().unwrap().code, KeyCode::Char('-'));
+        assert_eq!(
+            "c--".parse::<KeyBinding>().unwrap().code,
+            KeyCode::Char('-')
+        );
+        assert_eq!(
+            "PageDown".parse::<KeyBinding>().unwrap().code,
+            KeyCode::PageDown
+        );
+        assert_eq!("f12".parse::<KeyBinding>().unwrap().code, KeyCode::F(12));
+        assert!("f13".parse::<KeyBinding>().is_err());
+        assert!("nothing".parse::<KeyBinding>().is_err());
+        assert_eq!(binding.to_string(), "c-s");
+    }
+
+    #[test]
+    fn match_key_events() {
+        let keymap = Keymap::new();
+        let shifted = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert!(keymap.matches(&shifted, Action::CreateEntry));
+        let control = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::CONTROL);
+        assert!(!keymap.matches(&control, Action::CreateEntry));
+        let candidates = [Action::EditLayout, Action::CreateLayout];
+        assert_eq!(
+            keymap.action(&shifted, &candidates),
+            Some(Action::CreateLayout)
+        );
+        assert_eq!(keymap.describe(Action::NextItem), "j/down/right/tab");
+    }
+
+    #[test]
+    fn override_bindings() {
+        let overrides =
+            toml::from_str("create_entry = \"a\"\nsave_entry = [\"c-w\", \"F5\"]").unwrap();
+        let keymap = Keymap::new().with_overrides(overrides).unwrap();
+        assert_eq!(keymap.describe(Action::CreateEntry), "a");
+        assert_eq!(keymap.describe(Action::SaveEntry), "c-w/F5");
+        assert_eq!(keymap.describe(Action::DeleteEntry), "x");
+
+        let overrides = serde_json::from_str("{\"quit_forever\": \"q\"}");
+        assert!(overrides.map(|o| Keymap::new().with_overrides(o)).is_err());
+        let overrides = serde_json::from_str("{\"back\": \"c-\"}").unwrap();
+        assert!(Keymap::new().with_overrides(overrides).is_err());
+    }
+}
@@ -4,5 +4,6 @@ pub mod glyph_edit_view;
 pub mod glyph_layout_view;
 pub mod glyph_open_page;
 pub mod glyph_page;
+pub mod glyph_tabs_page;
 pub mod glyph_read_view;
 pub mod glyph_view;
@@ -1,21 +1,24 @@
 use crate::app::page::glyph_create_page::GlyphCreatePage;
 use crate::app::page::glyph_open_page::GlyphOpenPage;
+use crate::app::page::glyph_page::GlyphPage;
 use crate::app::popup::confirm_popup::ConfirmPopup;
 use crate::app::widget::button::Button;
-use crate::app::AppCommand::{PushPage, PushPopup};
+use crate::app::AppCommand::{OpenTab, PushPage, PushPopup};
 use crate::app::Command::AppCommand;
 use crate::app::{
     is_cycle_backward_hover_key, is_cycle_forward_hover_key, AppState, Command, Component, Container, DrawFlag, Drawable,
     Focusable, Interactable,
 };
 use crate::block;
+use crate::db::GlyphRepository;
+use crate::recent::{self, RecentGlyph};
 use crate::theme::Theme;
 use crate::utils::cycle_offset;
-use color_eyre::Result;
+use color_eyre::{Report, Result};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::layout::{Constraint, Flex, HorizontalAlignment, Layout, Rect};
-use ratatui::prelude::Line;
 use ratatui::prelude::Stylize;
+use ratatui::prelude::{Line, Span};
 use ratatui::widgets::Block;
 use ratatui::widgets::{BorderType, Widget};
 use ratatui::Frame;
@@ -27,6 +30,8 @@ pub struct EntrancePageState {
     pub is_focused: bool,
     pub is_hovered: bool,
     pub hovered_index: Option<usize>,
+    /// Reopened with the digit keys, from the latest one.
+    pub recent_glyphs: Vec<RecentGlyph>,
 }
 pub struct EntrancePage {
     pub components: Vec<Box<dyn Component>>,
@@ -37,13 +42,19 @@ impl EntrancePage {
         Self {
             components: vec![
                 Button::new("Create")
-                    .on_interact(Box::new(|_| {
-                        Ok(vec![AppCommand(PushPage(GlyphCreatePage::new().into()))])
+                    .on_interact(Box::new(|app_state| {
+                        let _app_state = app_state.unwrap().downcast_mut::<AppState>().unwrap();
+                        Ok(vec![AppCommand(PushPage(
+                            GlyphCreatePage::new(&_app_state.config).into(),
+                        ))])
                     }))
                     .into(),
                 Button::new("Open")
-                    .on_interact(Box::new(|_| {
-                        Ok(vec![AppCommand(PushPage(GlyphOpenPage::new().into()))])
+                    .on_interact(Box::new(|app_state| {
+                        let _app_state = app_state.unwrap().downcast_mut::<AppState>().unwrap();
+                        Ok(vec![AppCommand(PushPage(
+                            GlyphOpenPage::new(&_app_state.config).into(),
+                        ))])
                     }))
                     .into(),
                 Button::new("Quit")
@@ -65,6 +76,7 @@ impl EntrancePage {
                 is_focused: true,
                 is_hovered: false,
                 hovered_index: None,
+                recent_glyphs: recent::load(),
             },
         }
     }
@@ -83,7 +95,7 @@ impl From<EntrancePage> for Box<dyn Container> {
     }
 }
 impl Drawable for EntrancePage {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -98,6 +110,7 @@ impl Drawable for EntrancePage {
             Constraint::Length(8),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(self.state.recent_glyphs.len() as u16 + 2),
         ])
         .flex(Flex::Center)
         .split(rect);
@@ -136,10 +149,50 @@ impl Drawable for EntrancePage {
             }
             button_interactable.render(frame, button_rects[i], DrawFlag::DEFAULT, theme);
         }
+        /*
+          Recent Glyphs
+        */
+        if !self.state.recent_glyphs.is_empty() {
+            let recent_area: Rect = areas[3].centered_horizontally(Constraint::Length(64));
+            let mut rows = recent_area.rows().skip(1);
+            if let Some(row) = rows.next() {
+                Line::from("Recent")
+                    .bold()
+                    .centered()
+                    .render(row, frame.buffer_mut());
+            }
+            for (row, (index, recent_glyph)) in
+                rows.zip(self.state.recent_glyphs.iter().enumerate())
+            {
+                let name: String = recent_glyph
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let dir: String = recent_glyph
+                    .path
+                    .parent()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::from(format!("{}  ", index + 1)).bold(),
+                    Span::from(name),
+                ])
+                .render(row, frame.buffer_mut());
+                Line::from(dir)
+                    .dim()
+                    .right_aligned()
+                    .render(row, frame.buffer_mut());
+            }
+        }
     }
 }
 impl Interactable for EntrancePage {
-    fn handle(&mut self, key: &KeyEvent, _: Option<&mut dyn Any>) -> Result<Vec<Command>> {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
         if key.kind == KeyEventKind::Press {
             if is_cycle_forward_hover_key(key) {
                 self.cycle_hover(1);
@@ -161,7 +214,24 @@ impl Interactable for EntrancePage {
             if let KeyCode::Enter = key.code
                 && let Some(index) = self.state.hovered_index
             {
-                return self.components[index].as_mut().handle(key, None);
+                return self.components[index].as_mut().handle(key, parent_state);
+            }
+            if let KeyCode::Char(digit @ '1'..='9') = key.code
+                && let Some(recent_glyph) =
+                    self.state.recent_glyphs.get(digit as usize - '1' as usize)
+            {
+                let _app_state = parent_state.unwrap().downcast_mut::<AppState>().unwrap();
+                if !recent_glyph.path.exists() {
+                    return Err(Report::msg(format!(
+                        "Glyph does not exist: {}",
+                        recent_glyph.path.display()
+                    )));
+                }
+                let connection = GlyphRepository::init_glyph_db(&recent_glyph.path)?;
+                return Ok(vec![AppCommand(OpenTab(Box::new(GlyphPage::new(
+                    connection,
+                    &_app_state.config,
+                ))))]);
             }
         }
         Ok(Vec::new())
@@ -170,6 +240,7 @@ impl Interactable for EntrancePage {
         [
             ("j/k/up/down/tab/backtab", "Navigate"),
             ("Enter", "Interact"),
+            ("1-9", "Open Recent Glyph"),
         ]
         .into()
     }
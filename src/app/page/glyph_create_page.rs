@@ -2,7 +2,7 @@ use crate::app::dialog::text_input_dialog::{TextInputDialog, TextInputDialogStat
 use crate::app::page::glyph_page::GlyphPage;
 use crate::app::widget::button::Button;
 use crate::app::widget::directory_list::{DirectoryList, DirectoryListState};
-use crate::app::AppCommand::{PopPage, PushPage};
+use crate::app::AppCommand::{OpenTab, PopPage};
 use crate::app::Command::{AppCommand, PageCommand};
 use crate::app::PageCommand::{PopDialog, PushDialog};
 use crate::app::{
@@ -10,18 +10,20 @@ use crate::app::{
     Focusable, Interactable,
 };
 use crate::block;
+use crate::config::Config;
 use crate::db::GlyphRepository;
 use crate::theme::Theme;
 use crate::utils::cycle_offset;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Position, Rect};
 use ratatui::prelude::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::BorderType;
 use ratatui::widgets::{Block, Widget};
 use ratatui::Frame;
 use std::any::Any;
+use std::cell::Cell;
 use std::path::PathBuf;
 
 pub struct GlyphCreatePageState {
@@ -29,6 +31,8 @@ pub struct GlyphCreatePageState {
     pub is_hovered: bool,
     pub hovered_index: Option<usize>,
     pub path_to_create: PathBuf,
+    pub directory_list_area: Cell<Rect>,
+    pub config: Config,
 }
 pub struct GlyphCreatePage {
     pub dialogs: Vec<Box<dyn Container>>,
@@ -37,11 +41,12 @@ pub struct GlyphCreatePage {
     pub state: GlyphCreatePageState,
 }
 impl GlyphCreatePage {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
             dialogs: Vec::new(),
             containers: vec![
                 DirectoryList::new("Directory", false, true)
+                    .directory(config.directory())
                     .on_exit(Box::new(|parent_state, state| {
                         let _parent_state = parent_state
                             .unwrap()
@@ -65,7 +70,9 @@ impl GlyphCreatePage {
                 is_focused: true,
                 is_hovered: false,
                 hovered_index: None,
-                path_to_create: std::env::current_dir().unwrap(),
+                path_to_create: config.directory(),
+                directory_list_area: Cell::new(Rect::default()),
+                config: config.clone(),
             },
         }
     }
@@ -84,7 +91,7 @@ impl From<GlyphCreatePage> for Box<dyn Container> {
     }
 }
 impl Drawable for GlyphCreatePage {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Outer Frame
         */
@@ -100,6 +107,7 @@ impl Drawable for GlyphCreatePage {
             .split(inner_area);
 
         let file_explorer_area = chunks[0].centered(Constraint::Max(64), Constraint::Min(42));
+        self.state.directory_list_area.set(file_explorer_area);
         let button_areas = Layout::horizontal([Constraint::Max(20), Constraint::Max(20)])
             .flex(Flex::Center)
             .split(chunks[1]);
@@ -228,9 +236,10 @@ impl Interactable for GlyphCreatePage {
                                     );
                                     Ok(vec![
                                         PageCommand(PopDialog),
-                                        AppCommand(PushPage(
-                                            GlyphPage::new(connection.unwrap()).into(),
-                                        )),
+                                        AppCommand(OpenTab(Box::new(GlyphPage::new(
+                                            connection.unwrap(),
+                                            &_parent_state.config,
+                                        )))),
                                         AppCommand(PopPage),
                                     ])
                                 }))
@@ -249,6 +258,29 @@ impl Interactable for GlyphCreatePage {
             self.containers[index].handle(key, Some(&mut self.state))
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        // Dialogs are modal, the page underneath does not react meanwhile.
+        if !self.dialogs.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !self
+            .state
+            .directory_list_area
+            .get()
+            .contains(Position::new(mouse.column, mouse.row))
+        {
+            return Ok(Vec::new());
+        }
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+            self.containers[0].set_focus(true);
+            self.state.hovered_index = Some(0);
+        }
+        self.containers[0].handle_mouse(mouse, Some(&mut self.state))
+    }
     fn keymap(&self) -> Vec<(&str, &str)> {
         [
             ("j/k/up/down/tab/backtab", "Navigate"),
@@ -1,3 +1,4 @@
+use crate::app::keymap::{keymap, Action};
 use crate::app::dialog::text_input_dialog::{TextInputDialog, TextInputDialogState};
 use crate::app::page::glyph_page::GlyphPageState;
 use crate::app::widget::text_editor::{TextEditor, TextEditorState};
@@ -14,13 +15,15 @@ use crate::theme::Theme;
 use crate::utils::cycle_offset;
 use crate::utils::markdown_renderer::MarkdownRenderer;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use ratatui::layout::{Constraint, Layout, Rect};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
 use ratatui::prelude::{Line, Stylize};
 use ratatui::widgets::{Block, BorderType, Widget};
 use ratatui::Frame;
 use std::any::Any;
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::rc::Rc;
 
 pub struct GlyphEditState {
@@ -28,6 +31,8 @@ pub struct GlyphEditState {
     pub hovered_index: Option<usize>,
     // Shared Data
     pub is_editing: bool, // It is either Ordering or Editing
+    /// Area of the editor as last drawn, used to locate mouse events.
+    pub editor_area: Cell<Rect>,
     pub active_sid: Rc<RefCell<Option<i64>>>,
     pub entry_state: Rc<RefCell<LocalEntryState>>,
 }
@@ -50,13 +55,18 @@ impl From<GlyphEditView> for Box<dyn Container> {
     }
 }
 impl GlyphEditView {
-    pub fn new(shared_focus: Rc<RefCell<bool>>, entry_state: Rc<RefCell<LocalEntryState>>) -> Self {
+    pub fn new(
+        shared_focus: Rc<RefCell<bool>>,
+        entry_state: Rc<RefCell<LocalEntryState>>,
+        tab_width: u16,
+    ) -> Self {
         let editing_sid: Rc<RefCell<Option<i64>>> = Rc::new(RefCell::new(None));
         let is_editing: bool = false;
         Self {
             containers: vec![
                 GlyphEditOrderView::new(editing_sid.clone(), entry_state.clone()).into(),
                 TextEditor::new("Editor")
+                    .tab_width(tab_width)
                     .on_exit(Box::new(|parent_state, state| {
                         let _parent_state: &mut GlyphEditState = parent_state
                             .unwrap()
@@ -73,12 +83,7 @@ impl GlyphEditView {
                         let eid: i64 = local_entry_state.active_entry_id.unwrap();
                         let sid: i64 = _parent_state.active_sid.borrow().unwrap();
                         if let Some(section) = local_entry_state.get_section_mut(&eid, &sid) {
-                            let mut lines: Vec<Vec<char>> = _state.lines.clone();
-                            let line_number = lines.len();
-                            for line in &mut lines[0..line_number - 1] {
-                                line.push('\n');
-                            }
-                            let buffer_content = lines.concat().iter().collect::<String>();
+                            let buffer_content = _state.buffer.to_string();
                             if section.content != buffer_content {
                                 section.content = buffer_content;
                                 _parent_state.is_editing = false;
@@ -96,6 +101,7 @@ impl GlyphEditView {
                 shared_focus,
                 is_editing,
                 hovered_index: None,
+                editor_area: Cell::new(Rect::default()),
 
                 active_sid: editing_sid,
                 entry_state,
@@ -126,7 +132,7 @@ impl GlyphEditView {
     }
 }
 impl Drawable for GlyphEditView {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -136,6 +142,7 @@ impl Drawable for GlyphEditView {
             Constraint::Fill(1),
         ])
         .split(area);
+        self.state.editor_area.set(edit_areas[2]);
         self.containers[0].render(
             frame,
             edit_areas[0],
@@ -177,11 +184,10 @@ impl Interactable for GlyphEditView {
             self.containers[1].handle(key, Some(&mut self.state))
         } else {
             if key.kind == KeyEventKind::Press {
-                if let KeyCode::Esc = key.code {
+                if keymap().matches(key, Action::Back) {
                     self.state.shared_focus.replace(false);
                 }
-                if let KeyCode::Char(c) = key.code
-                    && c == 'e'
+                if keymap().matches(key, Action::EditSection)
                     && self.state.active_sid.borrow().is_some()
                 {
                     self.state.is_editing = true;
@@ -216,6 +222,35 @@ impl Interactable for GlyphEditView {
             }
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        if !self
+            .state
+            .editor_area
+            .get()
+            .contains(Position::new(mouse.column, mouse.row))
+        {
+            return Ok(Vec::new());
+        }
+        // Clicking the editor starts editing the active section, as the key does.
+        if !self.state.is_editing && mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+            if self.state.active_sid.borrow().is_none() {
+                return Ok(Vec::new());
+            }
+            self.state.is_editing = true;
+            self.containers[1].set_focus(true);
+        }
+        self.containers[1].handle_mouse(mouse, Some(&mut self.state))
+    }
+    fn actions(&self) -> Vec<Action> {
+        if self.state.is_editing {
+            return Vec::new();
+        }
+        [vec![Action::EditSection], self.containers[0].actions()].concat()
+    }
 }
 
 impl Focusable for GlyphEditView {
@@ -315,7 +350,7 @@ impl From<GlyphEditOrderView> for Box<dyn Container> {
     }
 }
 impl Drawable for GlyphEditOrderView {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -408,13 +443,13 @@ impl Interactable for GlyphEditOrderView {
                 if is_cycle_backward_hover_key(key) {
                     self.cycle_section_hover(-1);
                 }
-                if let KeyCode::PageUp = key.code {
+                if keymap().matches(key, Action::ScrollPageUp) {
                     self.state.scroll_offset = self.state.scroll_offset.saturating_sub(1);
                 }
-                if let KeyCode::PageDown = key.code {
+                if keymap().matches(key, Action::ScrollPageDown) {
                     self.state.scroll_offset = self.state.scroll_offset.saturating_add(1);
                 }
-                if let KeyCode::Enter = key.code
+                if keymap().matches(key, Action::Interact)
                     && let Some(index) = self.state.hovered_index
                 {
                     let state: Ref<LocalEntryState> = self.state.local_entry_state_ref().unwrap();
@@ -435,7 +470,7 @@ impl Interactable for GlyphEditOrderView {
                     *self.state.active_sid.borrow_mut() = Some(sections.get(index).unwrap().0);
                     return Ok(vec![GlyphCommand(RefreshEditSectionEditor)]);
                 }
-                if let KeyCode::Esc = key.code {
+                if keymap().matches(key, Action::Back) {
                     // Directly mutating parent state to lose focus
                     let parent_state = parent_state
                         .unwrap()
@@ -444,9 +479,9 @@ impl Interactable for GlyphEditOrderView {
                     *parent_state.shared_focus.borrow_mut() = false;
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Char(c) = key.code {
-                    return match c {
-                        '+' => {
+                if let Some(action) = keymap().action(key, &self.actions()) {
+                    return match action {
+                        Action::IncreaseSectionPosition => {
                             if self
                                 .state
                                 .entry_state
@@ -469,7 +504,7 @@ impl Interactable for GlyphEditOrderView {
 
                             Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))])
                         }
-                        '-' => {
+                        Action::DecreaseSectionPosition => {
                             if self
                                 .state
                                 .entry_state
@@ -491,7 +526,7 @@ impl Interactable for GlyphEditOrderView {
                             drop(state);
                             Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))])
                         }
-                        'x' => {
+                        Action::DeleteSection => {
                             if self.state.active_sid.borrow().is_none() {
                                 return Ok(Vec::new());
                             }
@@ -501,14 +536,14 @@ impl Interactable for GlyphEditOrderView {
                             state.delete_section_db(&sid)?;
                             Ok(Vec::new())
                         }
-                        'A' => {
+                        Action::CreateSection => {
                             let mut local_entry_state: RefMut<LocalEntryState> =
                                 self.state.local_entry_state_mut().unwrap();
                             local_entry_state
                                 .create_section_to_active_entry_db("untitled", "Blank")?;
                             Ok(Vec::new())
                         }
-                        'R' => {
+                        Action::RenameSection => {
                             let local_entry_state = self.state.local_entry_state_ref().unwrap();
                             if self.state.active_sid.borrow().is_none() {
                                 return Ok(Vec::new());
@@ -555,6 +590,20 @@ impl Interactable for GlyphEditOrderView {
             _ => Ok(Vec::new()),
         }
     }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::NextItem,
+            Action::PreviousItem,
+            Action::Interact,
+            Action::ScrollPageUp,
+            Action::ScrollPageDown,
+            Action::CreateSection,
+            Action::RenameSection,
+            Action::DeleteSection,
+            Action::IncreaseSectionPosition,
+            Action::DecreaseSectionPosition,
+        ]
+    }
 }
 impl Focusable for GlyphEditOrderView {
     fn is_focused(&self) -> bool {
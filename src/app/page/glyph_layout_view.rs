@@ -1,44 +1,69 @@
+use crate::app::keymap::{keymap, Action};
 use crate::app::dialog::search_entry_dialog::{SearchEntryDialog, SearchEntryDialogState};
+use crate::app::dialog::template_dialog::{TemplateDialog, TemplateDialogState};
+use crate::app::dialog::text_input_dialog::{TextInputDialog, TextInputDialogState};
 use crate::app::page::glyph_page::GlyphPageState;
 use crate::app::widget::button::Button;
 use crate::app::widget::number_field::{NumberField, NumberFieldState};
 use crate::app::widget::option_menu::{OptionMenu, OptionMenuState};
+use crate::app::widget::text_editor::{TextEditor, TextEditorState};
 use crate::app::widget::text_field::{TextField, TextFieldState};
 use crate::app::Command::{GlyphCommand, PageCommand};
 use crate::app::GlyphCommand::{RefreshLayoutEditPanel, SetEntryUnsavedState};
 use crate::app::PageCommand::{PopDialog, PushDialog};
 use crate::app::{
     get_draw_flag, is_cycle_backward_hover_key, is_cycle_forward_hover_key, Command, Component, Container, DrawFlag, Drawable,
-    Focusable, Interactable,
+    Focusable, Interactable, MOUSE_SCROLL_LINES,
 };
 use crate::block;
 use crate::models::entry::Entry;
-use crate::models::layout::{BorderMode, Layout, LayoutOrientation, SizeMode};
+use crate::models::layout::{parse_ratio, BorderMode, Layout, LayoutOrientation, SizeMode};
+use crate::models::template::LayoutTemplate;
 use crate::services::LocalEntryState;
 use crate::theme::Theme;
 use crate::utils::cycle_offset;
 use color_eyre::{Report, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::buffer::Buffer;
-use ratatui::layout::{Constraint, Rect, Size};
+use ratatui::layout::{Constraint, Margin, Position, Rect, Size};
 use ratatui::prelude::Stylize;
 use ratatui::prelude::{Line, Style};
-use ratatui::widgets::{Block, BorderType, Padding, StatefulWidget, Widget};
+use ratatui::style::Color;
+use ratatui::widgets::{
+    Block, BorderType, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    StatefulWidget, Widget, Wrap,
+};
 use ratatui::Frame;
 use std::any::Any;
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::rc::Rc;
 use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
 
+/// Rows of the warnings shown below the overview, the rest is cut.
+const MAX_WARNING_ROWS: usize = 5;
+
 pub struct GlyphLayoutState {
     pub shared_focus: Rc<RefCell<bool>>, // Shared state across all layout view
     pub is_editing: bool,                // It is either Ordering or Editing
+    /// Whether the layout is edited as text, the editor takes the place of the overview.
+    pub is_editing_text: bool,
+    /// Area of the overview as last drawn, used to locate mouse events.
+    pub overview_area: Cell<Rect>,
 
     // Shared Data
     pub selected_coordinate: Rc<RefCell<Vec<usize>>>,
     pub entry_state: Rc<RefCell<LocalEntryState>>,
 }
-impl GlyphLayoutState {}
+impl GlyphLayoutState {
+    pub(crate) fn local_entry_state_ref(&'_ self) -> Option<Ref<'_, LocalEntryState>> {
+        Ref::filter_map(self.entry_state.try_borrow().ok()?, |state| Some(state)).ok()
+    }
+    pub(crate) fn local_entry_state_mut(&'_ mut self) -> Option<RefMut<'_, LocalEntryState>> {
+        RefMut::filter_map(self.entry_state.try_borrow_mut().ok()?, |state| Some(state)).ok()
+    }
+}
 pub struct GlyphLayoutView {
     pub dialogs: Vec<Box<dyn Container>>,
     pub containers: Vec<Box<dyn Container>>,
@@ -54,15 +79,83 @@ impl GlyphLayoutView {
             containers: vec![
                 GlyphLayoutOverview::new(selected_coordinate.clone(), entry_state.clone()).into(),
                 GlyphLayoutEditView::new(selected_coordinate.clone(), entry_state.clone()).into(),
+                TextEditor::new("Layout")
+                    .on_exit(Box::new(|parent_state, state| {
+                        let _parent_state: &mut GlyphLayoutState = parent_state
+                            .unwrap()
+                            .downcast_mut::<GlyphLayoutState>()
+                            .unwrap();
+                        let _state: &mut TextEditorState =
+                            state.unwrap().downcast_mut::<TextEditorState>().unwrap();
+                        let text: String = _state.buffer.to_string();
+                        // Emptying the text leaves the layout as it is.
+                        if text.trim().is_empty() {
+                            _parent_state.is_editing_text = false;
+                            return Ok(Vec::new());
+                        }
+                        let layout: Layout = match Layout::from_dsl(&text) {
+                            Ok(layout) => layout,
+                            Err(e) => {
+                                // The editor is kept open to fix the text.
+                                _state.is_focused = true;
+                                return Err(Report::msg(format!(
+                                    "{}\nEmpty the text to leave the layout as it is.",
+                                    e
+                                )));
+                            }
+                        };
+                        _parent_state.is_editing_text = false;
+                        let mut local_entry_state: RefMut<LocalEntryState> =
+                            _parent_state.local_entry_state_mut().unwrap();
+                        let eid: i64 = local_entry_state.active_entry_id.unwrap();
+                        let entry: &mut Entry = local_entry_state.get_entry_mut(&eid).unwrap();
+                        if entry.layout.to_dsl() == text {
+                            return Ok(Vec::new());
+                        }
+                        entry.layout = layout;
+                        drop(local_entry_state);
+                        _parent_state.selected_coordinate.borrow_mut().clear();
+                        Ok(vec![
+                            GlyphCommand(SetEntryUnsavedState(eid, true)),
+                            GlyphCommand(RefreshLayoutEditPanel),
+                        ])
+                    }))
+                    .into(),
             ],
             state: GlyphLayoutState {
                 shared_focus,
                 selected_coordinate,
                 is_editing,
+                is_editing_text: false,
+                overview_area: Cell::new(Rect::default()),
                 entry_state,
             },
         }
     }
+    /// Refresh the edit panel when requested, the other commands are bubbled up in order.
+    fn process_layout_commands(&mut self, mut commands: Vec<Command>) -> Vec<Command> {
+        let mut processed_commands: Vec<Command> = Vec::new();
+        while let Some(command) = commands.pop() {
+            match command {
+                GlyphCommand(com) => match com {
+                    RefreshLayoutEditPanel => {
+                        (*self.containers[1])
+                            .as_any_mut()
+                            .downcast_mut::<GlyphLayoutEditView>()
+                            .unwrap()
+                            .refresh_layout_edit_panel();
+                    }
+                    _ => {
+                        processed_commands.insert(0, GlyphCommand(com));
+                    }
+                },
+                _ => {
+                    processed_commands.insert(0, command);
+                }
+            }
+        }
+        processed_commands
+    }
 }
 impl From<GlyphLayoutView> for Box<dyn Container> {
     fn from(container: GlyphLayoutView) -> Self {
@@ -71,7 +164,7 @@ impl From<GlyphLayoutView> for Box<dyn Container> {
 }
 
 impl Drawable for GlyphLayoutView {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -81,18 +174,62 @@ impl Drawable for GlyphLayoutView {
             Constraint::Length(24),
         ])
         .split(area);
-        self.containers[0].render(
-            frame,
-            edit_areas[0],
-            if !self.is_focused() {
-                DrawFlag::DEFAULT
-            } else if !self.state.is_editing {
-                DrawFlag::FOCUSED
-            } else {
-                DrawFlag::DEFAULT
-            },
-            theme,
-        );
+        // The problems of the layout are listed below the overview, each wrapped to its width.
+        let warnings: Vec<Line> = self
+            .state
+            .local_entry_state_ref()
+            .and_then(|state| {
+                let entry: &Entry = state.get_active_entry_ref()?;
+                Some(entry.layout.check(&entry.sections))
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|warning| Line::from(format!("! {}", warning)))
+            .collect();
+        let text_width: usize = edit_areas[0].width.saturating_sub(2).max(1) as usize;
+        let rows: usize = warnings
+            .iter()
+            .map(|line| line.width().div_ceil(text_width).max(1))
+            .sum();
+        let overview_areas = ratatui::layout::Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(match warnings.is_empty() {
+                true => 0,
+                false => rows.min(MAX_WARNING_ROWS) as u16 + 2,
+            }),
+        ])
+        .split(edit_areas[0]);
+        if !warnings.is_empty() {
+            let title: String = format!("Warnings ({})", warnings.len());
+            Paragraph::new(warnings)
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::bordered()
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::new().fg(theme.color(Color::Yellow)))
+                        .title(title),
+                )
+                .style(theme.on_surface())
+                .bg(theme.surface_low())
+                .render(overview_areas[1], frame.buffer_mut());
+        }
+        self.state.overview_area.set(overview_areas[0]);
+        if self.state.is_editing_text {
+            self.containers[2].render(frame, overview_areas[0], DrawFlag::FOCUSED, theme);
+        } else {
+            self.containers[0].render(
+                frame,
+                overview_areas[0],
+                if !self.is_focused() {
+                    DrawFlag::DEFAULT
+                } else if !self.state.is_editing {
+                    DrawFlag::FOCUSED
+                } else {
+                    DrawFlag::DEFAULT
+                },
+                theme,
+            );
+        }
         self.containers[1].render(
             frame,
             edit_areas[2],
@@ -113,47 +250,90 @@ impl Interactable for GlyphLayoutView {
         key: &KeyEvent,
         _parent_state: Option<&mut dyn Any>,
     ) -> Result<Vec<Command>> {
-        if self.state.is_editing {
+        if self.state.is_editing_text {
+            let result = self.containers[2]
+                .as_mut()
+                .handle(key, Some(&mut self.state));
+            if !self.state.is_editing_text {
+                let overview: &mut GlyphLayoutOverview = (*self.containers[0])
+                    .as_any_mut()
+                    .downcast_mut::<GlyphLayoutOverview>()
+                    .unwrap();
+                overview.state.hovered_index = None;
+                overview.state.marked_layout = None;
+            }
+            Ok(self.process_layout_commands(result?))
+        } else if self.state.is_editing {
             self.containers[1]
                 .as_mut()
                 .handle(key, Some(&mut self.state))
         } else {
             if key.kind == KeyEventKind::Press {
-                if let KeyCode::Esc = key.code
+                if keymap().matches(key, Action::Back)
                     && self.state.selected_coordinate.borrow().is_empty()
                 {
                     self.state.shared_focus.replace(false);
                 }
+                if keymap().matches(key, Action::EditLayoutText)
+                    && let Some(text) = self.state.local_entry_state_ref().and_then(|state| {
+                        state
+                            .get_active_entry_ref()
+                            .map(|entry| entry.layout.to_dsl())
+                    })
+                {
+                    let editor: &mut TextEditor = (*self.containers[2])
+                        .as_any_mut()
+                        .downcast_mut::<TextEditor>()
+                        .unwrap();
+                    editor.replace(text);
+                    editor.state.cursor_line_index = 0;
+                    editor.state.scroll_offset = 0;
+                    editor.set_focus(true);
+                    self.state.is_editing_text = true;
+                    return Ok(Vec::new());
+                }
             }
             let result = self.containers[0]
                 .as_mut()
                 .handle(key, Some(&mut self.state));
-            if result.is_err() {
-                result
-            } else {
-                let mut processed_commands: Vec<Command> = Vec::new();
-                let mut commands = result?;
-                while let Some(command) = commands.pop() {
-                    match command {
-                        GlyphCommand(com) => match com {
-                            RefreshLayoutEditPanel => {
-                                (*self.containers[1])
-                                    .as_any_mut()
-                                    .downcast_mut::<GlyphLayoutEditView>()
-                                    .unwrap()
-                                    .refresh_layout_edit_panel();
-                            }
-                            _ => {
-                                processed_commands.insert(0, GlyphCommand(com));
-                            }
-                        },
-                        _ => {
-                            processed_commands.insert(0, command);
-                        }
-                    }
-                }
-                Ok(processed_commands)
-            }
+            Ok(self.process_layout_commands(result?))
+        }
+    }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let is_on_overview: bool = self
+            .state
+            .overview_area
+            .get()
+            .contains(Position::new(mouse.column, mouse.row));
+        if !is_on_overview {
+            return Ok(Vec::new());
+        }
+        if self.state.is_editing_text {
+            let result = self.containers[2]
+                .as_mut()
+                .handle_mouse(mouse, Some(&mut self.state));
+            return Ok(self.process_layout_commands(result?));
+        }
+        // Clicking the overview leaves the edit panel.
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+            self.state.is_editing = false;
+        }
+        let result = self.containers[0]
+            .as_mut()
+            .handle_mouse(mouse, Some(&mut self.state));
+        Ok(self.process_layout_commands(result?))
+    }
+    fn actions(&self) -> Vec<Action> {
+        if self.state.is_editing_text {
+            self.containers[2].actions()
+        } else if self.state.is_editing {
+            self.containers[1].actions()
+        } else {
+            [vec![Action::EditLayoutText], self.containers[0].actions()].concat()
         }
     }
 }
@@ -167,21 +347,27 @@ impl Focusable for GlyphLayoutView {
         *focus = value;
     }
     fn focused_child_ref(&self) -> Option<&dyn Container> {
-        if self.state.is_editing {
+        if self.state.is_editing_text {
+            Some(self.containers[2].as_ref())
+        } else if self.state.is_editing {
             Some(self.containers[1].as_ref())
         } else {
             Some(self.containers[0].as_ref())
         }
     }
     fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
-        if self.state.is_editing {
+        if self.state.is_editing_text {
+            Some(self.containers[2].as_mut())
+        } else if self.state.is_editing {
             Some(self.containers[1].as_mut())
         } else {
             Some(self.containers[0].as_mut())
         }
     }
     fn focused_child_index(&self) -> Option<usize> {
-        if self.state.is_editing {
+        if self.state.is_editing_text {
+            Some(2)
+        } else if self.state.is_editing {
             Some(1)
         } else {
             Some(0)
@@ -192,6 +378,15 @@ impl Focusable for GlyphLayoutView {
 pub struct GlyphLayoutOverviewState {
     pub hovered_index: Option<usize>, // Note this is the hovered index for sub-layouts, not widgets.
     pub scroll_state: RefCell<ScrollViewState>,
+    /// (coordinate, area) of every panel as last drawn, in the coordinates of the scroll view
+    /// when the root layout has a fixed length.
+    pub panel_areas: RefCell<Vec<(Vec<usize>, Rect)>>,
+    /// (eid, coordinate) of the layout marked to be moved under the next selected one.
+    pub marked_layout: Option<(i64, Vec<usize>)>,
+    /// Width the breakpoints are previewed at, the width of the terminal otherwise.
+    pub preview_width: Option<u16>,
+    /// Width of the terminal as last drawn.
+    pub terminal_width: Cell<u16>,
 
     // Shared Data
     pub selected_coordinate: Rc<RefCell<Vec<usize>>>,
@@ -219,11 +414,36 @@ impl GlyphLayoutOverview {
                 hovered_index: None,
                 selected_coordinate,
                 scroll_state,
+                panel_areas: RefCell::new(Vec::new()),
+                marked_layout: None,
+                preview_width: None,
+                terminal_width: Cell::new(0),
 
                 entry_state,
             },
         }
     }
+    /// Preview the layout just below its next breakpoint, back to the width of the terminal after
+    /// the narrowest one.
+    fn cycle_preview_width(&mut self) -> Result<()> {
+        let widths: Vec<u16> = self
+            .state
+            .local_entry_state_ref()
+            .unwrap()
+            .get_active_entry_ref()
+            .map(|entry| entry.layout.breakpoint_widths())
+            .unwrap_or_default();
+        if widths.is_empty() {
+            self.state.preview_width = None;
+            return Err(Report::msg("The layout has no breakpoints to preview"));
+        }
+        self.state.preview_width = match self.state.preview_width {
+            // The widths may have changed since, the preview goes on with the next narrower one.
+            Some(preview_width) => widths.into_iter().find(|width| *width < preview_width),
+            None => widths.first().copied(),
+        };
+        Ok(())
+    }
     pub(crate) fn cycle_layout_hover(&mut self, offset: i16) {
         let select_coordinate: Vec<usize> = self.state.selected_coordinate.borrow().clone();
         let state = self.state.local_entry_state_ref().unwrap();
@@ -242,6 +462,28 @@ impl GlyphLayoutOverview {
             self.state.hovered_index = Some(0);
         }
     }
+    /// Change the structure of the active layout at the selected coordinate,
+    /// then select the layout at the coordinate returned.
+    fn restructure_layout(
+        &mut self,
+        restructure: impl FnOnce(&mut Layout, &Vec<usize>) -> Result<Vec<usize>>,
+    ) -> Result<Vec<Command>> {
+        let target_coor: Vec<usize> = self.state.selected_coordinate.borrow().clone();
+        let mut state = self.state.local_entry_state_mut().unwrap();
+        let Some(eid) = state.active_entry_id else {
+            return Ok(Vec::new());
+        };
+        let layout: &mut Layout = &mut state.get_entry_mut(&eid).unwrap().layout;
+        let new_coor: Vec<usize> = restructure(layout, &target_coor)?;
+        drop(state);
+        *self.state.selected_coordinate.borrow_mut() = new_coor;
+        self.state.hovered_index = None;
+        self.state.marked_layout = None;
+        Ok(vec![
+            GlyphCommand(SetEntryUnsavedState(eid, true)),
+            GlyphCommand(RefreshLayoutEditPanel),
+        ])
+    }
 }
 
 impl From<GlyphLayoutOverview> for Box<dyn Container> {
@@ -250,7 +492,7 @@ impl From<GlyphLayoutOverview> for Box<dyn Container> {
     }
 }
 impl Drawable for GlyphLayoutOverview {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
         /*
            Evaluate Page Layout via the root layout
         */
@@ -258,8 +500,15 @@ impl Drawable for GlyphLayoutOverview {
         let entry_state: Ref<LocalEntryState> = self.state.local_entry_state_ref().unwrap();
         let eid: i64 = entry_state.active_entry_id.unwrap();
         let layout = &entry_state.get_entry_ref(&eid).unwrap().layout;
+        self.state.panel_areas.borrow_mut().clear();
+        self.state.terminal_width.set(frame.area().width);
         match layout.details.size_mode {
-            SizeMode::Flex => {
+            // Only a fixed length could overflow the view, the root fills it in the other modes.
+            SizeMode::Flex
+            | SizeMode::Percentage
+            | SizeMode::Ratio
+            | SizeMode::Min
+            | SizeMode::Max => {
                 evaluate_layout(self, area, frame.buffer_mut(), layout, 0, Vec::new(), theme);
             }
             SizeMode::Length => {
@@ -295,7 +544,7 @@ impl Interactable for GlyphLayoutOverview {
     ) -> Result<Vec<Command>> {
         match key.kind {
             KeyEventKind::Press => {
-                if let KeyCode::Esc = key.code {
+                if keymap().matches(key, Action::Back) {
                     return if !self.state.selected_coordinate.borrow_mut().is_empty() {
                         let index = self.state.selected_coordinate.borrow_mut().pop();
                         self.state.hovered_index = index;
@@ -309,19 +558,19 @@ impl Interactable for GlyphLayoutOverview {
                         Ok(Vec::new())
                     };
                 }
-                if let KeyCode::PageUp = key.code {
+                if keymap().matches(key, Action::ScrollPageUp) {
                     self.state.scroll_state.borrow_mut().scroll_page_up();
                     return Ok(Vec::new());
                 }
-                if let KeyCode::PageDown = key.code {
+                if keymap().matches(key, Action::ScrollPageDown) {
                     self.state.scroll_state.borrow_mut().scroll_page_down();
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Up = key.code {
+                if keymap().matches(key, Action::ScrollUp) {
                     self.state.scroll_state.borrow_mut().scroll_up();
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Down = key.code {
+                if keymap().matches(key, Action::ScrollDown) {
                     self.state.scroll_state.borrow_mut().scroll_down();
                     return Ok(Vec::new());
                 }
@@ -332,7 +581,7 @@ impl Interactable for GlyphLayoutOverview {
                 if is_cycle_backward_hover_key(key) {
                     self.cycle_layout_hover(-1);
                 }
-                if let KeyCode::Enter = key.code
+                if keymap().matches(key, Action::Interact)
                     && let Some(hovered_index) = self.state.hovered_index
                 {
                     self.state
@@ -342,9 +591,9 @@ impl Interactable for GlyphLayoutOverview {
                     self.state.hovered_index = None;
                     return Ok(vec![GlyphCommand(RefreshLayoutEditPanel)]);
                 }
-                if let KeyCode::Char(c) = key.code {
-                    match c {
-                        'e' => {
+                if let Some(action) = keymap().action(key, &self.actions()) {
+                    match action {
+                        Action::EditLayout => {
                             let parent_state = parent_state
                                 .unwrap()
                                 .downcast_mut::<GlyphLayoutState>()
@@ -352,7 +601,7 @@ impl Interactable for GlyphLayoutOverview {
                             parent_state.is_editing = true;
                             return Ok(Vec::new());
                         }
-                        'A' => {
+                        Action::CreateLayout => {
                             let target_coor = self.state.selected_coordinate.borrow_mut().clone();
                             let mut state = self.state.local_entry_state_mut().unwrap();
                             let eid = state.active_entry_id.unwrap();
@@ -361,7 +610,7 @@ impl Interactable for GlyphLayoutOverview {
                             layout.insert_sublayout_under(Layout::new(""), &target_coor);
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
-                        'D' => {
+                        Action::CloneLayout => {
                             return Ok(vec![PageCommand(PushDialog(
                                 SearchEntryDialog::new(
                                     "Clone Active Entry Layout to",
@@ -401,13 +650,206 @@ impl Interactable for GlyphLayoutOverview {
                                         .unwrap();
                                     entry.1.layout = active_entry_layout;
                                     // _entry_state.update_entry_layout_db((*entry).0, active_entry_layout)?;
-                                    _entry_state.updated_entries.insert(*selected_eid);
+                                    _entry_state.set_entry_unsaved(*selected_eid, true)?;
                                     Ok(vec![PageCommand(PopDialog)])
                                 }))
                                 .into(),
                             ))]);
                         }
-                        'x' => {
+                        Action::SaveLayoutTemplate => {
+                            if self
+                                .state
+                                .local_entry_state_ref()
+                                .unwrap()
+                                .active_entry_id
+                                .is_none()
+                            {
+                                return Ok(Vec::new());
+                            }
+                            return Ok(vec![PageCommand(PushDialog(
+                                TextInputDialog::new(
+                                    "Template Name",
+                                    "untitled",
+                                    Box::new(|value| !value.is_empty()),
+                                )
+                                .on_submit(Box::new(|parent_state, state| {
+                                    let _parent_state = parent_state
+                                        .unwrap()
+                                        .downcast_mut::<GlyphPageState>()
+                                        .unwrap();
+                                    let mut local_entry_state =
+                                        _parent_state.local_entry_state_mut().unwrap();
+                                    let _state = state
+                                        .unwrap()
+                                        .downcast_mut::<TextInputDialogState>()
+                                        .unwrap();
+                                    local_entry_state
+                                        .save_layout_template_db(_state.text_input.as_str())?;
+                                    Ok(vec![])
+                                }))
+                                .into(),
+                            ))]);
+                        }
+                        Action::ApplyLayoutTemplate => {
+                            let local_entry_state = self.state.local_entry_state_ref().unwrap();
+                            if local_entry_state.active_entry_id.is_none() {
+                                return Ok(Vec::new());
+                            }
+                            let templates: Vec<LayoutTemplate> =
+                                local_entry_state.read_layout_templates()?;
+                            // The coordinates of the replaced layout may not exist in the new one
+                            let selected_coordinate: Rc<RefCell<Vec<usize>>> =
+                                self.state.selected_coordinate.clone();
+                            return Ok(vec![PageCommand(PushDialog(
+                                TemplateDialog::new("Apply Layout Template", templates)
+                                    .on_submit(Box::new(move |parent_state, state| {
+                                        let _parent_state = parent_state
+                                            .unwrap()
+                                            .downcast_mut::<GlyphPageState>()
+                                            .unwrap();
+                                        let mut local_entry_state =
+                                            _parent_state.local_entry_state_mut().unwrap();
+                                        let _state = state
+                                            .unwrap()
+                                            .downcast_mut::<TemplateDialogState>()
+                                            .unwrap();
+                                        local_entry_state.apply_layout_template(
+                                            _state.hovered_template().unwrap(),
+                                        )?;
+                                        selected_coordinate.borrow_mut().clear();
+                                        Ok(vec![GlyphCommand(RefreshLayoutEditPanel)])
+                                    }))
+                                    .into(),
+                            ))]);
+                        }
+                        Action::DeleteLayoutTemplate => {
+                            // Only the templates saved in the glyph could be deleted
+                            let templates: Vec<LayoutTemplate> = self
+                                .state
+                                .local_entry_state_ref()
+                                .unwrap()
+                                .read_layout_templates()?
+                                .into_iter()
+                                .filter(|template| template.id.is_some())
+                                .collect();
+                            return Ok(vec![PageCommand(PushDialog(
+                                TemplateDialog::new("Delete Layout Template", templates)
+                                    .on_submit(Box::new(|parent_state, state| {
+                                        let _parent_state = parent_state
+                                            .unwrap()
+                                            .downcast_mut::<GlyphPageState>()
+                                            .unwrap();
+                                        let mut local_entry_state =
+                                            _parent_state.local_entry_state_mut().unwrap();
+                                        let _state = state
+                                            .unwrap()
+                                            .downcast_mut::<TemplateDialogState>()
+                                            .unwrap();
+                                        let id: i64 =
+                                            _state.hovered_template().unwrap().id.unwrap();
+                                        local_entry_state.delete_layout_template_db(&id)?;
+                                        Ok(vec![])
+                                    }))
+                                    .into(),
+                            ))]);
+                        }
+                        Action::LinkSharedLayout => {
+                            let local_entry_state = self.state.local_entry_state_ref().unwrap();
+                            let Some(eid) = local_entry_state.active_entry_id else {
+                                return Ok(Vec::new());
+                            };
+                            // Only the templates saved in the glyph could be shared
+                            let templates: Vec<LayoutTemplate> = local_entry_state
+                                .read_layout_templates()?
+                                .into_iter()
+                                .filter(|template| template.id.is_some())
+                                .collect();
+                            let selected_coordinate: Rc<RefCell<Vec<usize>>> =
+                                self.state.selected_coordinate.clone();
+                            return Ok(vec![PageCommand(PushDialog(
+                                TemplateDialog::new("Link Shared Layout", templates)
+                                    .on_submit(Box::new(move |parent_state, state| {
+                                        let _parent_state = parent_state
+                                            .unwrap()
+                                            .downcast_mut::<GlyphPageState>()
+                                            .unwrap();
+                                        let mut local_entry_state =
+                                            _parent_state.local_entry_state_mut().unwrap();
+                                        let _state = state
+                                            .unwrap()
+                                            .downcast_mut::<TemplateDialogState>()
+                                            .unwrap();
+                                        local_entry_state.link_shared_layout_db(
+                                            &eid,
+                                            _state.hovered_template().unwrap(),
+                                        )?;
+                                        selected_coordinate.borrow_mut().clear();
+                                        Ok(vec![GlyphCommand(RefreshLayoutEditPanel)])
+                                    }))
+                                    .into(),
+                            ))]);
+                        }
+                        Action::DetachSharedLayout => {
+                            let mut local_entry_state = self.state.local_entry_state_mut().unwrap();
+                            if let Some(eid) = local_entry_state.active_entry_id {
+                                local_entry_state.detach_shared_layout_db(&eid)?;
+                            }
+                            return Ok(Vec::new());
+                        }
+                        Action::SwapLayoutForward => {
+                            return self.restructure_layout(|layout, coordinates| {
+                                layout.swap_sublayout(coordinates, 1)
+                            });
+                        }
+                        Action::SwapLayoutBackward => {
+                            return self.restructure_layout(|layout, coordinates| {
+                                layout.swap_sublayout(coordinates, -1)
+                            });
+                        }
+                        // Mark the selected layout, then move it under the one selected next
+                        Action::MoveLayout => {
+                            let Some(eid) =
+                                self.state.local_entry_state_ref().unwrap().active_entry_id
+                            else {
+                                return Ok(Vec::new());
+                            };
+                            let target_coor: Vec<usize> =
+                                self.state.selected_coordinate.borrow().clone();
+                            match self.state.marked_layout.take() {
+                                Some((marked_eid, marked_coor))
+                                    if marked_eid == eid && marked_coor != target_coor =>
+                                {
+                                    return self.restructure_layout(|layout, coordinates| {
+                                        layout.move_sublayout(&marked_coor, coordinates)
+                                    });
+                                }
+                                Some((marked_eid, _marked_coor)) if marked_eid == eid => {}
+                                _ => {
+                                    if !target_coor.is_empty() {
+                                        self.state.marked_layout = Some((eid, target_coor));
+                                    }
+                                }
+                            }
+                            return Ok(Vec::new());
+                        }
+                        Action::WrapLayout => {
+                            return self.restructure_layout(|layout, coordinates| {
+                                layout.wrap_sublayout(coordinates)?;
+                                Ok(coordinates.clone())
+                            });
+                        }
+                        Action::UnwrapLayout => {
+                            return self.restructure_layout(|layout, coordinates| {
+                                layout.unwrap_sublayout(coordinates)?;
+                                Ok(coordinates.clone())
+                            });
+                        }
+                        Action::DuplicateLayout => {
+                            return self.restructure_layout(|layout, coordinates| {
+                                layout.duplicate_sublayout(coordinates)
+                            });
+                        }
+                        Action::DeleteLayout => {
                             if self
                                 .state
                                 .entry_state
@@ -433,7 +875,7 @@ impl Interactable for GlyphLayoutOverview {
                             layout.remove_sublayout(&target_coor)?;
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
-                        '+' => {
+                        Action::NextLayoutSection => {
                             if self
                                 .state
                                 .entry_state
@@ -461,7 +903,7 @@ impl Interactable for GlyphLayoutOverview {
                             }
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
-                        '-' => {
+                        Action::PreviousLayoutSection => {
                             if self
                                 .state
                                 .entry_state
@@ -491,7 +933,7 @@ impl Interactable for GlyphLayoutOverview {
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
                         // Transpose the alignment
-                        't' => {
+                        Action::ToggleLayoutOrientation => {
                             if self
                                 .state
                                 .entry_state
@@ -516,11 +958,17 @@ impl Interactable for GlyphLayoutOverview {
                                     sublayout.details.orientation = LayoutOrientation::Vertical;
                                 }
                                 LayoutOrientation::Vertical => {
+                                    sublayout.details.orientation = LayoutOrientation::Stack;
+                                }
+                                LayoutOrientation::Stack => {
                                     sublayout.details.orientation = LayoutOrientation::Horizontal;
                                 }
                             }
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
+                        Action::PreviewBreakpoint => {
+                            self.cycle_preview_width()?;
+                        }
                         _ => {}
                     }
                 }
@@ -529,6 +977,94 @@ impl Interactable for GlyphLayoutOverview {
             _ => Ok(Vec::new()),
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                for _ in 0..MOUSE_SCROLL_LINES {
+                    self.state.scroll_state.borrow_mut().scroll_up();
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                for _ in 0..MOUSE_SCROLL_LINES {
+                    self.state.scroll_state.borrow_mut().scroll_down();
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let area: Rect = parent_state
+                    .unwrap()
+                    .downcast_mut::<GlyphLayoutState>()
+                    .unwrap()
+                    .overview_area
+                    .get();
+                let state = self.state.local_entry_state_ref().unwrap();
+                let eid: i64 = state.active_entry_id.unwrap();
+                // A root layout with a fixed length is drawn in a scroll view.
+                let is_scrolled: bool = matches!(
+                    state.get_entry_ref(&eid).unwrap().layout.details.size_mode,
+                    SizeMode::Length
+                );
+                drop(state);
+                let mut position: Position = Position::new(mouse.column, mouse.row);
+                if is_scrolled {
+                    let offset: Position = self.state.scroll_state.borrow().offset();
+                    position = Position::new(
+                        position.x - area.x + offset.x,
+                        position.y - area.y + offset.y,
+                    );
+                }
+                // Panels are nested, the deepest one containing the click is selected.
+                let clicked_coordinate: Option<Vec<usize>> = self
+                    .state
+                    .panel_areas
+                    .borrow()
+                    .iter()
+                    .filter(|(_coordinate, panel_area)| panel_area.contains(position))
+                    .max_by_key(|(coordinate, _panel_area)| coordinate.len())
+                    .map(|(coordinate, _panel_area)| coordinate.clone());
+                if let Some(coordinate) = clicked_coordinate {
+                    *self.state.selected_coordinate.borrow_mut() = coordinate;
+                    self.state.hovered_index = None;
+                    return Ok(vec![GlyphCommand(RefreshLayoutEditPanel)]);
+                }
+            }
+            _ => {}
+        }
+        Ok(Vec::new())
+    }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::NextItem,
+            Action::PreviousItem,
+            Action::Interact,
+            Action::ScrollUp,
+            Action::ScrollDown,
+            Action::ScrollPageUp,
+            Action::ScrollPageDown,
+            Action::EditLayout,
+            Action::CreateLayout,
+            Action::CloneLayout,
+            Action::DeleteLayout,
+            Action::SwapLayoutForward,
+            Action::SwapLayoutBackward,
+            Action::MoveLayout,
+            Action::WrapLayout,
+            Action::UnwrapLayout,
+            Action::DuplicateLayout,
+            Action::NextLayoutSection,
+            Action::PreviousLayoutSection,
+            Action::ToggleLayoutOrientation,
+            Action::PreviewBreakpoint,
+            Action::SaveLayoutTemplate,
+            Action::ApplyLayoutTemplate,
+            Action::DeleteLayoutTemplate,
+            Action::LinkSharedLayout,
+            Action::DetachSharedLayout,
+        ]
+    }
 }
 impl Focusable for GlyphLayoutOverview {
     fn is_focused(&self) -> bool {
@@ -553,7 +1089,7 @@ fn evaluate_layout(
     layout: &Layout,
     depth: u16,
     at: Vec<usize>,
-    theme: &dyn Theme,
+    theme: &Theme,
 ) -> Vec<(u16, Rect)> {
     let mut target_section_text: String = "None".to_string();
     if let Some(position_target) = layout.section_index {
@@ -580,6 +1116,61 @@ fn evaluate_layout(
         }
     }
 
+    // The root tells the width previewed, if not the width of the terminal.
+    let view_width: u16 = me
+        .state
+        .preview_width
+        .unwrap_or(me.state.terminal_width.get());
+    if depth == 0
+        && let Some(preview_width) = me.state.preview_width
+    {
+        block = block.title(Line::from(format!("(Preview: {} columns)", preview_width)).italic());
+    }
+    let orientation: &LayoutOrientation = layout.details.orientation_at(view_width);
+
+    // A stack shows the child on the path to the selected or hovered layout, the first otherwise.
+    let mut visible_index: usize = 0;
+    if *orientation == LayoutOrientation::Stack {
+        let mut hover_coordinate: Vec<usize> = focused_coordinate.clone();
+        hover_coordinate.extend(me.state.hovered_index);
+        for coordinate in [focused_coordinate, &hover_coordinate] {
+            if coordinate.len() > at.len()
+                && coordinate.starts_with(&at)
+                && coordinate[at.len()] < layout.sub_layouts.len()
+            {
+                visible_index = coordinate[at.len()];
+            }
+        }
+        if !layout.sub_layouts.is_empty() {
+            block = block.title(Line::from(format!(
+                "(Stack {}/{})",
+                visible_index + 1,
+                layout.sub_layouts.len()
+            )));
+        }
+    }
+
+    if me
+        .state
+        .marked_layout
+        .as_ref()
+        .is_some_and(|(_eid, marked_coor)| *marked_coor == at)
+    {
+        block = block.title(Line::from("(Marked)").italic());
+    }
+
+    // The root tells the shared layout it follows, if any.
+    if depth == 0
+        && let Some(entry) = me
+            .state
+            .local_entry_state_ref()
+            .unwrap()
+            .get_active_entry_ref()
+        && let Some((_id, name)) = &entry.shared_layout
+    {
+        block = block.title(Line::from(format!("(Shared: {})", name)).italic());
+    }
+
     // Determine the section index render.
     if !layout.sub_layouts.is_empty() {
         block = block.title(Line::from("(Disabled)").dim());
@@ -587,13 +1178,13 @@ fn evaluate_layout(
         block = block.title(Line::from(format!("(Target Pos: {})", target_section_text)));
     }
 
-    // Process the child
+    // Process the child, the hidden ones take no space.
     let constraints: Vec<Constraint> = layout
         .sub_layouts
         .iter()
-        .map(|sub| match sub.details.size_mode {
-            SizeMode::Flex => Constraint::Fill(sub.details.flex),
-            SizeMode::Length => Constraint::Length(sub.details.length),
+        .map(|sub| match sub.details.is_hidden_at(view_width) {
+            true => Constraint::Length(0),
+            false => sub.details.constraint(),
         })
         .collect();
 
@@ -606,14 +1197,19 @@ fn evaluate_layout(
         .inner(area);
     let recursive_area: Rect = block.inner(intermediate_area);
     block.render(intermediate_area, buffer);
+    me.state
+        .panel_areas
+        .borrow_mut()
+        .push((at.clone(), intermediate_area));
 
-    let sub_areas = match layout.details.orientation {
+    let sub_areas = match orientation {
         LayoutOrientation::Vertical => {
             ratatui::layout::Layout::vertical(constraints).split(recursive_area)
         }
         LayoutOrientation::Horizontal => {
             ratatui::layout::Layout::horizontal(constraints).split(recursive_area)
         }
+        LayoutOrientation::Stack => Rc::from(vec![recursive_area; layout.sub_layouts.len()]),
     };
 
     let mut areas: Vec<(u16, Rect)> = vec![];
@@ -621,6 +1217,11 @@ fn evaluate_layout(
         areas.push((section_index, recursive_area));
     }
     for (i, sub_layout) in layout.sub_layouts.iter().enumerate() {
+        if (*orientation == LayoutOrientation::Stack && i != visible_index)
+            || sub_layout.details.is_hidden_at(view_width)
+        {
+            continue;
+        }
         let mut sub_at = at.clone();
         sub_at.push(i);
         areas = [
@@ -642,6 +1243,8 @@ fn evaluate_layout(
 
 pub struct GlyphLayoutEditState {
     pub hovered_index: Option<usize>,
+    /// Index of the first field drawn, when the fields do not all fit the panel.
+    pub scroll_offset: Cell<usize>,
 
     // Shared Data
     pub selected_coordinate: Rc<RefCell<Vec<usize>>>,
@@ -826,15 +1429,56 @@ impl GlyphLayoutEditView {
                     Ok(Vec::new())
                 }))
                 .into(),
-            ],
-            components: vec![
-                OptionMenu::new(vec![("Flex".to_string(), 0), ("Length".to_string(), 1)], 0)
-                    .on_interact(Box::new(|parent_state, state| {
+                NumberField::new(
+                    "Percentage",
+                    0,
+                    Box::new(|value| {
+                        value
+                            .parse::<u16>()
+                            .is_ok_and(|value| (0..=100).contains(&value))
+                    }),
+                )
+                .on_exit(Box::new(|parent_state, state| {
+                    let _parent_state = parent_state
+                        .unwrap()
+                        .downcast_mut::<GlyphLayoutEditState>()
+                        .unwrap();
+                    let _state = state.unwrap().downcast_mut::<NumberFieldState>().unwrap();
+                    let Some(new_value) = _state
+                        .chars
+                        .iter()
+                        .collect::<String>()
+                        .parse::<u16>()
+                        .ok()
+                        .filter(|value| *value <= 100)
+                    else {
+                        return Ok(Vec::new());
+                    };
+
+                    let coor: Vec<usize> = _parent_state.selected_coordinate.borrow().clone();
+                    let mut local_entry_state: RefMut<LocalEntryState> =
+                        _parent_state.local_entry_state_mut().unwrap();
+                    let eid: i64 = local_entry_state.active_entry_id.unwrap();
+                    let entry: &mut Entry = local_entry_state.get_entry_mut(&eid).unwrap();
+                    let sublayout_to_update = entry.layout.get_layout_at_mut(&coor).unwrap();
+                    if sublayout_to_update.details.percentage != new_value {
+                        sublayout_to_update.details.percentage = new_value;
+                        return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
+                    }
+                    Ok(Vec::new())
+                }))
+                .into(),
+                TextField::new("Ratio", "", Box::new(|value| parse_ratio(value).is_some()))
+                    .on_exit(Box::new(|parent_state, state| {
                         let _parent_state = parent_state
                             .unwrap()
                             .downcast_mut::<GlyphLayoutEditState>()
                             .unwrap();
-                        let _state = state.unwrap().downcast_mut::<OptionMenuState>().unwrap();
+                        let _state = state.unwrap().downcast_mut::<TextFieldState>().unwrap();
+                        let Some(new_value) = parse_ratio(&_state.chars.iter().collect::<String>())
+                        else {
+                            return Ok(Vec::new());
+                        };
 
                         let coor: Vec<usize> = _parent_state.selected_coordinate.borrow().clone();
                         let mut local_entry_state: RefMut<LocalEntryState> =
@@ -842,26 +1486,62 @@ impl GlyphLayoutEditView {
                         let eid: i64 = local_entry_state.active_entry_id.unwrap();
                         let entry: &mut Entry = local_entry_state.get_entry_mut(&eid).unwrap();
                         let sublayout_to_update = entry.layout.get_layout_at_mut(&coor).unwrap();
-
-                        let selected_item_index: u8 = _state.current_index;
-                        let selected_item_value: u8 =
-                            _state.options[selected_item_index as usize].1;
-                        let parsed_selected_item = match selected_item_value {
-                            0 => SizeMode::Flex,
-                            1 => SizeMode::Length,
-                            _ => {
-                                return Err(Report::msg(
-                                    "Impossible to have another value for size mode.",
-                                ));
-                            }
-                        };
-                        if sublayout_to_update.details.size_mode != parsed_selected_item {
-                            sublayout_to_update.details.size_mode = parsed_selected_item;
+                        if sublayout_to_update.details.ratio != new_value {
+                            sublayout_to_update.details.ratio = new_value;
                             return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
                         }
                         Ok(Vec::new())
                     }))
                     .into(),
+            ],
+            components: vec![
+                OptionMenu::new(
+                    vec![
+                        ("Flex".to_string(), 0),
+                        ("Length".to_string(), 1),
+                        ("Percentage".to_string(), 2),
+                        ("Ratio".to_string(), 3),
+                        ("Min".to_string(), 4),
+                        ("Max".to_string(), 5),
+                    ],
+                    0,
+                )
+                .on_interact(Box::new(|parent_state, state| {
+                    let _parent_state = parent_state
+                        .unwrap()
+                        .downcast_mut::<GlyphLayoutEditState>()
+                        .unwrap();
+                    let _state = state.unwrap().downcast_mut::<OptionMenuState>().unwrap();
+
+                    let coor: Vec<usize> = _parent_state.selected_coordinate.borrow().clone();
+                    let mut local_entry_state: RefMut<LocalEntryState> =
+                        _parent_state.local_entry_state_mut().unwrap();
+                    let eid: i64 = local_entry_state.active_entry_id.unwrap();
+                    let entry: &mut Entry = local_entry_state.get_entry_mut(&eid).unwrap();
+                    let sublayout_to_update = entry.layout.get_layout_at_mut(&coor).unwrap();
+
+                    let selected_item_index: u8 = _state.current_index;
+                    let selected_item_value: u8 = _state.options[selected_item_index as usize].1;
+                    let parsed_selected_item = match selected_item_value {
+                        0 => SizeMode::Flex,
+                        1 => SizeMode::Length,
+                        2 => SizeMode::Percentage,
+                        3 => SizeMode::Ratio,
+                        4 => SizeMode::Min,
+                        5 => SizeMode::Max,
+                        _ => {
+                            return Err(Report::msg(
+                                "Impossible to have another value for size mode.",
+                            ));
+                        }
+                    };
+                    if sublayout_to_update.details.size_mode != parsed_selected_item {
+                        sublayout_to_update.details.size_mode = parsed_selected_item;
+                        return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
+                    }
+                    Ok(Vec::new())
+                }))
+                .into(),
                 OptionMenu::new(
                     vec![
                         ("Border: None".to_string(), 0),
@@ -904,6 +1584,36 @@ impl GlyphLayoutEditView {
                     Ok(Vec::new())
                 }))
                 .into(),
+                OptionMenu::new(
+                    vec![
+                        ("Length: Fixed".to_string(), 0),
+                        ("Length: Fit Content".to_string(), 1),
+                    ],
+                    0,
+                )
+                .on_interact(Box::new(|parent_state, state| {
+                    let _parent_state = parent_state
+                        .unwrap()
+                        .downcast_mut::<GlyphLayoutEditState>()
+                        .unwrap();
+                    let _state = state.unwrap().downcast_mut::<OptionMenuState>().unwrap();
+
+                    let coor: Vec<usize> = _parent_state.selected_coordinate.borrow().clone();
+                    let mut local_entry_state: RefMut<LocalEntryState> =
+                        _parent_state.local_entry_state_mut().unwrap();
+                    let eid: i64 = local_entry_state.active_entry_id.unwrap();
+                    let entry: &mut Entry = local_entry_state.get_entry_mut(&eid).unwrap();
+                    let sublayout_to_update: &mut Layout =
+                        entry.layout.get_layout_at_mut(&coor).unwrap();
+
+                    let fit_content: bool = _state.options[_state.current_index as usize].1 == 1;
+                    if sublayout_to_update.details.fit_content != fit_content {
+                        sublayout_to_update.details.fit_content = fit_content;
+                        return Ok(vec![GlyphCommand(SetEntryUnsavedState(eid, true))]);
+                    }
+                    Ok(Vec::new())
+                }))
+                .into(),
                 Button::new("Revert All")
                     .on_interact(Box::new(|parent_state| {
                         let _parent_state: &mut GlyphLayoutEditState = parent_state
@@ -927,6 +1637,7 @@ impl GlyphLayoutEditView {
 
             state: GlyphLayoutEditState {
                 hovered_index: None,
+                scroll_offset: Cell::new(0),
                 selected_coordinate,
 
                 entry_state,
@@ -955,6 +1666,10 @@ impl GlyphLayoutEditView {
         let root_layout_size_mode: u8 = match sub_layout.details.size_mode {
             SizeMode::Flex => 0,
             SizeMode::Length => 1,
+            SizeMode::Percentage => 2,
+            SizeMode::Ratio => 3,
+            SizeMode::Min => 4,
+            SizeMode::Max => 5,
         };
         let root_layout_border_mode: u8 = match sub_layout.details.border_mode {
             BorderMode::None => 0,
@@ -962,6 +1677,8 @@ impl GlyphLayoutEditView {
             BorderMode::Dashed => 2,
             BorderMode::Rounded => 3,
         };
+        let root_layout_percentage: u16 = sub_layout.details.percentage;
+        let (ratio_numerator, ratio_denominator): (u32, u32) = sub_layout.details.ratio;
         let root_layout_padding: u16 = sub_layout.details.padding;
         let root_layout_margin: u16 = sub_layout.details.margin;
         // Label
@@ -982,6 +1699,12 @@ impl GlyphLayoutEditView {
             .downcast_mut::<OptionMenu>()
             .unwrap()
             .replace(root_layout_border_mode);
+        // Fit Content
+        (*self.components[2])
+            .as_any_mut()
+            .downcast_mut::<OptionMenu>()
+            .unwrap()
+            .replace(sub_layout.details.fit_content as u8);
         // Length
         (*self.containers[1])
             .as_any_mut()
@@ -1006,6 +1729,18 @@ impl GlyphLayoutEditView {
             .downcast_mut::<NumberField>()
             .unwrap()
             .replace(root_layout_margin as i16);
+        // Percentage
+        (*self.containers[5])
+            .as_any_mut()
+            .downcast_mut::<NumberField>()
+            .unwrap()
+            .replace(root_layout_percentage as i16);
+        // Ratio
+        (*self.containers[6])
+            .as_any_mut()
+            .downcast_mut::<TextField>()
+            .unwrap()
+            .replace(format!("{}/{}", ratio_numerator, ratio_denominator));
     }
 }
 impl From<GlyphLayoutEditView> for Box<dyn Container> {
@@ -1015,7 +1750,7 @@ impl From<GlyphLayoutEditView> for Box<dyn Container> {
 }
 
 impl Drawable for GlyphLayoutEditView {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -1026,84 +1761,96 @@ impl Drawable for GlyphLayoutEditView {
         let chunks =
             ratatui::layout::Layout::vertical([Constraint::Percentage(100), Constraint::Length(1)])
                 .split(inner_area);
-        let field_areas =
-            ratatui::layout::Layout::vertical([Constraint::Length(3)].repeat(7)).split(chunks[0]);
-
-        // Label Field
-        self.containers[0].render(
-            frame,
-            field_areas[0],
-            get_draw_flag(
-                self.state.hovered_index,
-                0,
+        // The fields along with whether they are focused, in the order of the hover.
+        let fields: [(&dyn Drawable, Option<bool>); 10] = [
+            // Label Field
+            (
+                self.containers[0].as_drawable_ref(),
                 Some(self.containers[0].is_focused()),
             ),
-            theme,
-        );
-        // Size Mode Field
-        self.components[0].render(
-            frame,
-            field_areas[1],
-            get_draw_flag(self.state.hovered_index, 1, None),
-            theme,
-        );
-        // Border Mode Field
-        self.components[1].render(
-            frame,
-            field_areas[2],
-            get_draw_flag(self.state.hovered_index, 2, None),
-            theme,
-        );
-        // Length Field
-        self.containers[1].render(
-            frame,
-            field_areas[3],
-            get_draw_flag(
-                self.state.hovered_index,
-                3,
+            // Size Mode Field
+            (self.components[0].as_drawable_ref(), None),
+            // Border Mode Field
+            (self.components[1].as_drawable_ref(), None),
+            // Length Field
+            (
+                self.containers[1].as_drawable_ref(),
                 Some(self.containers[1].is_focused()),
             ),
-            theme,
-        );
-        // Flex Field
-        self.containers[2].render(
-            frame,
-            field_areas[4],
-            get_draw_flag(
-                self.state.hovered_index,
-                4,
+            // Fit Content Field
+            (self.components[2].as_drawable_ref(), None),
+            // Flex Field
+            (
+                self.containers[2].as_drawable_ref(),
                 Some(self.containers[2].is_focused()),
             ),
-            theme,
-        );
-        // Padding Field
-        self.containers[3].render(
-            frame,
-            field_areas[5],
-            get_draw_flag(
-                self.state.hovered_index,
-                5,
+            // Percentage Field
+            (
+                self.containers[5].as_drawable_ref(),
+                Some(self.containers[5].is_focused()),
+            ),
+            // Ratio Field
+            (
+                self.containers[6].as_drawable_ref(),
+                Some(self.containers[6].is_focused()),
+            ),
+            // Padding Field
+            (
+                self.containers[3].as_drawable_ref(),
                 Some(self.containers[3].is_focused()),
             ),
-            theme,
-        );
-        // Margin Field
-        self.containers[4].render(
-            frame,
-            field_areas[6],
-            get_draw_flag(
-                self.state.hovered_index,
-                6,
+            // Margin Field
+            (
+                self.containers[4].as_drawable_ref(),
                 Some(self.containers[4].is_focused()),
             ),
-            theme,
-        );
+        ];
+        // Only the fields fitting the panel are drawn, scrolled to keep the hovered one in view.
+        let num_visible: usize = (chunks[0].height / 3) as usize;
+        let mut scroll_offset: usize = self.state.scroll_offset.get();
+        if let Some(index) = self
+            .state
+            .hovered_index
+            .filter(|index| *index < fields.len())
+        {
+            scroll_offset = scroll_offset
+                .min(index)
+                .max((index + 1).saturating_sub(num_visible));
+        }
+        scroll_offset = scroll_offset.min(fields.len().saturating_sub(num_visible));
+        self.state.scroll_offset.set(scroll_offset);
+        let field_areas =
+            ratatui::layout::Layout::vertical([Constraint::Length(3)].repeat(num_visible))
+                .split(chunks[0]);
+        for (field_area, (index, (field, is_focused))) in field_areas
+            .iter()
+            .zip(fields.into_iter().enumerate().skip(scroll_offset))
+        {
+            field.render(
+                frame,
+                *field_area,
+                get_draw_flag(self.state.hovered_index, index, is_focused),
+                theme,
+            );
+        }
+        if num_visible < fields.len() {
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(
+                    area.inner(Margin::new(0, 1)),
+                    frame.buffer_mut(),
+                    &mut ScrollbarState::new(fields.len() - num_visible + 1)
+                        .position(scroll_offset)
+                        .viewport_content_length(num_visible),
+                );
+        }
 
         // Revert Button
-        self.components[2].render(
+        self.components[3].render(
             frame,
             chunks[1],
-            get_draw_flag(self.state.hovered_index, 7, None),
+            get_draw_flag(self.state.hovered_index, 10, None),
             theme,
         );
     }
@@ -1155,19 +1902,31 @@ impl Interactable for GlyphLayoutEditView {
                                 self.containers[1].set_focus(true);
                             }
                             4 => {
+                                // Fit Content Field
+                                return self.components[2].handle(key, Some(&mut self.state));
+                            }
+                            5 => {
                                 // Flex Field
                                 self.containers[2].set_focus(true);
                             }
-                            5 => {
+                            6 => {
+                                // Percentage Field
+                                self.containers[5].set_focus(true);
+                            }
+                            7 => {
+                                // Ratio Field
+                                self.containers[6].set_focus(true);
+                            }
+                            8 => {
                                 // Padding Field
                                 self.containers[3].set_focus(true);
                             }
-                            6 => {
-                                // Padding Field
+                            9 => {
+                                // Margin Field
                                 self.containers[4].set_focus(true);
                             }
-                            7 => {
-                                return self.components[2].handle(key, Some(&mut self.state));
+                            10 => {
+                                return self.components[3].handle(key, Some(&mut self.state));
                             }
                             _ => {}
                         }
@@ -1,25 +1,27 @@
 use crate::app::page::glyph_page::GlyphPage;
 use crate::app::widget::button::Button;
 use crate::app::widget::directory_list::{DirectoryList, DirectoryListState};
-use crate::app::AppCommand::{PopPage, PushPage};
+use crate::app::AppCommand::{OpenTab, PopPage};
 use crate::app::Command::AppCommand;
 use crate::app::{
     get_draw_flag, is_cycle_backward_hover_key, is_cycle_forward_hover_key, Command, Component, Container, DrawFlag, Drawable,
     Focusable, Interactable,
 };
 use crate::block;
+use crate::config::Config;
 use crate::db::GlyphRepository;
 use crate::theme::Theme;
 use crate::utils::cycle_offset;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Position, Rect};
 use ratatui::prelude::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::BorderType;
 use ratatui::widgets::{Block, Widget};
 use ratatui::Frame;
 use std::any::Any;
+use std::cell::Cell;
 use std::path::PathBuf;
 
 pub struct GlyphOpenPageState {
@@ -27,6 +29,8 @@ pub struct GlyphOpenPageState {
     pub is_hovered: bool,
     pub hovered_index: Option<usize>,
     pub path_to_open: PathBuf,
+    pub directory_list_area: Cell<Rect>,
+    pub config: Config,
 }
 pub struct GlyphOpenPage {
     pub containers: Vec<Box<dyn Container>>,
@@ -34,11 +38,12 @@ pub struct GlyphOpenPage {
     pub state: GlyphOpenPageState,
 }
 impl GlyphOpenPage {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
             containers: vec![Box::new(
-                DirectoryList::new("Directory", true, false).on_exit(Box::new(
-                    |parent_state, state| {
+                DirectoryList::new("Directory", true, false)
+                    .directory(config.directory())
+                    .on_exit(Box::new(|parent_state, state| {
                         let _parent_state = parent_state
                             .unwrap()
                             .downcast_mut::<GlyphOpenPageState>()
@@ -46,8 +51,7 @@ impl GlyphOpenPage {
                         let _state = state.unwrap().downcast_mut::<DirectoryListState>().unwrap();
                         _parent_state.path_to_open = _state.selected_file_path.clone().unwrap();
                         Ok(Vec::new())
-                    },
-                )),
+                    })),
             )],
 
             components: vec![
@@ -63,7 +67,10 @@ impl GlyphOpenPage {
                         let connection =
                             GlyphRepository::init_glyph_db(&_parent_state.path_to_open)?;
                         Ok(vec![
-                            AppCommand(PushPage(Box::new(GlyphPage::new(connection)))),
+                            AppCommand(OpenTab(Box::new(GlyphPage::new(
+                                connection,
+                                &_parent_state.config,
+                            )))),
                             AppCommand(PopPage),
                         ])
                     }))
@@ -73,7 +80,9 @@ impl GlyphOpenPage {
                 is_focused: true,
                 is_hovered: false,
                 hovered_index: None,
-                path_to_open: std::env::current_dir().unwrap(),
+                path_to_open: config.directory(),
+                directory_list_area: Cell::new(Rect::default()),
+                config: config.clone(),
             },
         }
     }
@@ -92,7 +101,7 @@ impl From<GlyphOpenPage> for Box<dyn Container> {
     }
 }
 impl Drawable for GlyphOpenPage {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Outer Frame
         */
@@ -108,6 +117,7 @@ impl Drawable for GlyphOpenPage {
             .split(inner_area);
 
         let file_explorer_area = chunks[0].centered(Constraint::Max(64), Constraint::Min(42));
+        self.state.directory_list_area.set(file_explorer_area);
         let button_areas = Layout::horizontal([Constraint::Max(20), Constraint::Max(20)])
             .flex(Flex::Center)
             .split(chunks[1]);
@@ -179,6 +189,25 @@ impl Interactable for GlyphOpenPage {
             self.containers[index].handle(key, Some(&mut self.state))
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        if !self
+            .state
+            .directory_list_area
+            .get()
+            .contains(Position::new(mouse.column, mouse.row))
+        {
+            return Ok(Vec::new());
+        }
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+            self.containers[0].set_focus(true);
+            self.state.hovered_index = Some(0);
+        }
+        self.containers[0].handle_mouse(mouse, Some(&mut self.state))
+    }
     fn keymap(&self) -> Vec<(&str, &str)> {
         [
             ("j/k/up/down/tab/backtab", "Navigate"),
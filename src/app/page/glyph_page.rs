@@ -1,39 +1,67 @@
 use crate::app::dialog::confirm_dialog::ConfirmDialog;
 use crate::app::dialog::search_entry_dialog::{SearchEntryDialog, SearchEntryDialogState};
+use crate::app::dialog::template_dialog::{TemplateDialog, TemplateDialogState};
 use crate::app::dialog::text_input_dialog::{TextInputDialog, TextInputDialogState};
+use crate::app::keymap::{keymap, Action};
 use crate::app::page::glyph_view::GlyphView;
-use crate::app::AppCommand::PopPage;
+use crate::app::event::{AppEvent, Timer};
+use crate::app::AppCommand::{PopPage, StartTimer};
 use crate::app::Command::{AppCommand, PageCommand};
 use crate::app::PageCommand::{PopDialog, PushDialog};
 use crate::app::{
-    get_draw_flag, is_cycle_backward_hover_key, is_cycle_forward_hover_key, Command, Component, Container, DrawFlag, Drawable,
+    focus_chain, get_draw_flag, is_cycle_backward_hover_key, is_cycle_forward_hover_key, AppState, Command, Component, Container, DrawFlag, Drawable,
     Focusable, Interactable,
 };
 use crate::block;
+use crate::config::Config;
 use crate::models::entry::Entry;
+use crate::models::section::Section;
+use crate::models::template::LayoutTemplate;
+use crate::recent::{self, RecentGlyph};
 use crate::services::LocalEntryState;
 use crate::theme::Theme;
 use crate::utils::cycle_offset;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use ratatui::layout::{Constraint, Layout, Offset, Rect};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Constraint, Layout, Offset, Position, Rect};
 use ratatui::prelude::{Line, Span, Widget};
 use ratatui::style::Stylize;
 use ratatui::widgets::Block;
 use ratatui::widgets::BorderType;
+use ratatui::widgets::Clear;
 use ratatui::Frame;
 pub use rusqlite::fallible_iterator::FallibleIterator;
 use rusqlite::Connection;
 use std::any::Any;
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Name of the timer saving the unsaved entries of a glyph page, followed by the page id.
+pub const AUTOSAVE_TIMER: &str = "autosave";
+/// Name of the timer hiding the toast of a glyph page, followed by the page id.
+pub const TOAST_TIMER: &str = "toast";
+/// Id of the next glyph page, so that the glyphs opened together keep their own timers.
+static NEXT_PAGE_ID: AtomicUsize = AtomicUsize::new(0);
 
 pub struct GlyphPageState {
+    /// Tell the timers of the page apart from those of the other glyph pages.
+    pub id: usize,
     pub is_focused: bool,
     pub is_hovered: bool,
     pub hovered_index: Option<usize>,
     pub hidden_container_index: HashSet<u8>,
+    /// Areas of the navigation bar and the view as last drawn, used to locate mouse events.
+    pub content_areas: Cell<[Rect; 2]>,
+    /// Whether the autosave timer is started for the current unsaved entries.
+    pub is_autosave_scheduled: bool,
+    /// Short notice shown at the bottom of the page, such as the change just undone.
+    pub toast: Option<String>,
 
     // Shared Data
     pub entry_state: Rc<RefCell<LocalEntryState>>,
@@ -54,24 +82,218 @@ pub struct GlyphPage {
 }
 
 impl GlyphPage {
-    pub fn new(connection: Connection) -> Self {
+    pub fn new(connection: Connection, config: &Config) -> Self {
         let entry_state: Rc<RefCell<LocalEntryState>> =
             Rc::new(RefCell::new(LocalEntryState::new(connection)));
-        Self {
-            dialogs: Vec::new(),
+        let mut dialogs: Vec<Box<dyn Container>> = Vec::new();
+        if entry_state.borrow().has_journal() {
+            dialogs.push(
+                ConfirmDialog::new("Restore the unsaved changes of an interrupted session?")
+                    .on_submit(Box::new(|parent_state, _state| {
+                        let _parent_state: &mut GlyphPageState = parent_state
+                            .unwrap()
+                            .downcast_mut::<GlyphPageState>()
+                            .unwrap();
+                        _parent_state
+                            .local_entry_state_mut()
+                            .unwrap()
+                            .restore_journal()?;
+                        Ok(Vec::new())
+                    }))
+                    .on_cancel(Box::new(|parent_state| {
+                        let _parent_state: &mut GlyphPageState = parent_state
+                            .unwrap()
+                            .downcast_mut::<GlyphPageState>()
+                            .unwrap();
+                        _parent_state
+                            .local_entry_state_ref()
+                            .unwrap()
+                            .discard_journal()?;
+                        Ok(Vec::new())
+                    }))
+                    .into(),
+            );
+        }
+        let mut page: GlyphPage = Self {
+            dialogs,
             containers: vec![
                 GlyphNavigationBar::new(entry_state.clone()).into(),
-                GlyphView::new(entry_state.clone()).into(),
+                GlyphView::new(entry_state.clone(), config).into(),
             ],
             components: Vec::new(),
             state: GlyphPageState {
+                id: NEXT_PAGE_ID.fetch_add(1, Ordering::Relaxed),
                 is_focused: false,
                 is_hovered: false,
                 hidden_container_index: HashSet::new(),
+                content_areas: Cell::new([Rect::default(); 2]),
+                is_autosave_scheduled: false,
+                toast: None,
                 hovered_index: None,
                 entry_state,
             },
+        };
+        if let Some(recent_glyph) = page.path().as_deref().and_then(recent::find) {
+            page.restore(&recent_glyph);
+        }
+        page
+    }
+    /// Name of the timer of this page, out of `AUTOSAVE_TIMER` and `TOAST_TIMER`.
+    pub fn timer_name(&self, timer: &str) -> String {
+        format!("{}-{}", timer, self.state.id)
+    }
+    /// Path of the glyph, none for an in-memory one.
+    pub fn path(&self) -> Option<PathBuf> {
+        match self.state.local_entry_state_ref()?.connection.path() {
+            Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+            _ => None,
+        }
+    }
+    /// Where the glyph is left, to be reopened there.
+    pub fn recent_glyph(&self) -> Option<RecentGlyph> {
+        let glyph_view: &GlyphView = self.glyph_view_ref();
+        let scroll_state = glyph_view.read_view_ref().state.scroll_state.borrow();
+        Some(RecentGlyph {
+            path: self.path()?,
+            active_entry_id: self.state.local_entry_state_ref()?.active_entry_id,
+            mode: glyph_view.state.mode,
+            scroll_offset: scroll_state.offset().y,
+        })
+    }
+    /// Reopen the entry where it was left, unless it does not exist anymore.
+    pub fn restore(&mut self, recent_glyph: &RecentGlyph) {
+        let Some(eid) = recent_glyph.active_entry_id else {
+            return;
+        };
+        {
+            let mut local_entry_state = self.state.local_entry_state_mut().unwrap();
+            if local_entry_state.get_entry_ref(&eid).is_none() {
+                return;
+            }
+            local_entry_state.active_entry_id = Some(eid);
         }
+        let glyph_view: &mut GlyphView = self.glyph_view_mut();
+        glyph_view.set_mode(recent_glyph.mode);
+        glyph_view
+            .read_view_mut()
+            .state
+            .scroll_state
+            .borrow_mut()
+            .set_offset(Position::new(0, recent_glyph.scroll_offset));
+    }
+    /// Name shown on the tab of the glyph.
+    pub fn name(&self) -> String {
+        self.path()
+            .and_then(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| String::from("untitled"))
+    }
+    pub fn has_unsaved_entries(&self) -> bool {
+        !self
+            .state
+            .local_entry_state_ref()
+            .unwrap()
+            .updated_entries
+            .is_empty()
+    }
+    /// A copy of the active entry along with its sections.
+    pub fn active_entry(&self) -> Option<Entry> {
+        self.state
+            .local_entry_state_ref()?
+            .get_active_entry_ref()
+            .cloned()
+    }
+    /// A copy of the section selected in the edit mode.
+    pub fn active_section(&self) -> Option<Section> {
+        let local_entry_state = self.state.local_entry_state_ref()?;
+        let eid: i64 = local_entry_state.active_entry_id?;
+        let sid: i64 = (*self
+            .glyph_view_ref()
+            .edit_view_ref()
+            .state
+            .active_sid
+            .borrow())?;
+        local_entry_state.get_section_ref(&eid, &sid).cloned()
+    }
+    /// Leave the glyph, once the user agrees to give up the unsaved changes.
+    pub fn close(&mut self) -> Vec<Command> {
+        if !self.has_unsaved_entries() {
+            return vec![AppCommand(PopPage)];
+        }
+        self.dialogs.push(
+            ConfirmDialog::new("You have unsaved change! Exit anyway?")
+                .on_submit(Box::new(|parent_state, _state| {
+                    let _parent_state: &mut GlyphPageState = parent_state
+                        .unwrap()
+                        .downcast_mut::<GlyphPageState>()
+                        .unwrap();
+                    // The changes are given up, they should not be offered for recovery.
+                    _parent_state
+                        .local_entry_state_ref()
+                        .unwrap()
+                        .discard_journal()?;
+                    Ok(vec![AppCommand(PopPage)])
+                }))
+                .into(),
+        );
+        Vec::new()
+    }
+    fn glyph_view_ref(&self) -> &GlyphView {
+        (*self.containers[1])
+            .as_any()
+            .downcast_ref::<GlyphView>()
+            .unwrap()
+    }
+    fn glyph_view_mut(&mut self) -> &mut GlyphView {
+        (*self.containers[1])
+            .as_any_mut()
+            .downcast_mut::<GlyphView>()
+            .unwrap()
+    }
+    /// Apply the dialog commands to the page, the others are bubbled up in order.
+    fn process_page_commands(&mut self, mut commands: Vec<Command>) -> Vec<Command> {
+        let mut processed_commands: Vec<Command> = Vec::new();
+        while let Some(command) = commands.pop() {
+            match command {
+                PageCommand(page_command) => match page_command {
+                    PopDialog => {
+                        self.dialogs.pop();
+                    }
+                    PushDialog(dialog) => {
+                        self.dialogs.push(dialog);
+                    }
+                },
+                _ => {
+                    processed_commands.insert(0, command);
+                }
+            }
+        }
+        processed_commands
+    }
+    /// Undo or redo the last change of the glyph, and tell which one it was.
+    fn revert_change(&mut self, action: Action) -> Result<Vec<Command>> {
+        let mut state: RefMut<LocalEntryState> = self.state.local_entry_state_mut().unwrap();
+        let (description, prefix) = match action {
+            Action::Undo => (state.undo()?, "Undo"),
+            _ => (state.redo()?, "Redo"),
+        };
+        let has_active_entry: bool = state.active_entry_id.is_some();
+        drop(state);
+        self.glyph_view_mut().reset_selection();
+        // The active entry may be deleted by the change.
+        if !has_active_entry && self.containers[1].is_focused() {
+            self.containers[1].set_focus(false);
+        }
+        self.state.toast = Some(match description {
+            Some(description) => format!("{}: {}", prefix, description),
+            None => format!("Nothing to {}", prefix.to_lowercase()),
+        });
+        Ok(vec![AppCommand(StartTimer(Timer::once(
+            self.timer_name(TOAST_TIMER),
+            Duration::from_secs(3),
+        )))])
     }
     pub(crate) fn cycle_hover(&mut self, offset: i16) {
         let max: u16 = (self.containers.len() + self.components.len()) as u16;
@@ -97,7 +319,7 @@ impl From<GlyphPage> for Box<dyn Container> {
 }
 
 impl Drawable for GlyphPage {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Outer Frame
         */
@@ -112,6 +334,9 @@ impl Drawable for GlyphPage {
         }
 
         page_frame.render(area, frame.buffer_mut());
+        self.state
+            .content_areas
+            .set([content_areas[0], content_areas[1]]);
         self.containers[0].render(
             frame,
             content_areas[0],
@@ -133,6 +358,26 @@ impl Drawable for GlyphPage {
             theme,
         );
 
+        /*
+           Toast
+        */
+        if let Some(toast) = &self.state.toast {
+            let width: u16 = (toast.chars().count() as u16 + 4).min(page_area.width);
+            let toast_area: Rect = Rect::new(
+                page_area.right().saturating_sub(width),
+                page_area.bottom().saturating_sub(3),
+                width,
+                3.min(page_area.height),
+            );
+            let toast_frame: Block = Block::bordered().border_type(BorderType::Rounded);
+            let toast_inner_area: Rect = toast_frame.inner(toast_area);
+            Clear.render(toast_area, frame.buffer_mut());
+            toast_frame.render(toast_area, frame.buffer_mut());
+            Line::from(toast.as_str())
+                .centered()
+                .render(toast_inner_area, frame.buffer_mut());
+        }
+
         /*
            Dialog
         */
@@ -163,33 +408,31 @@ impl Interactable for GlyphPage {
                 .last_mut()
                 .unwrap()
                 .handle(key, Some(&mut self.state));
-            return if result.is_err() {
-                result
-            } else {
-                let mut processed_commands: Vec<Command> = Vec::new();
-                let mut commands = result?;
-                while let Some(command) = commands.pop() {
-                    match command {
-                        PageCommand(page_command) => match page_command {
-                            PopDialog => {
-                                self.dialogs.pop();
-                            }
-                            PushDialog(dialog) => {
-                                self.dialogs.push(dialog);
-                            }
-                        },
-                        _ => {
-                            processed_commands.insert(0, command);
-                        }
-                    }
-                }
-                Ok(processed_commands)
-            };
+            return Ok(self.process_page_commands(result?));
         }
 
         /*
            Process Page
         */
+        // Undo and redo apply anywhere in the page, except while typing or editing a section.
+        if key.kind == KeyEventKind::Press
+            && let Some(action) = keymap().action(key, &[Action::Undo, Action::Redo])
+            && !focus_chain(self)
+                .last()
+                .is_some_and(|container| container.is_typing())
+            && !self.glyph_view_ref().is_editing_text()
+        {
+            return self.revert_change(action);
+        }
+        // Creating an entry is forwarded to the navigation bar, so that it is available right away.
+        if self.focused_child_ref().is_none()
+            && key.kind == KeyEventKind::Press
+            && (keymap().matches(key, Action::CreateEntry)
+                || keymap().matches(key, Action::CreateEntryFromTemplate))
+            && !self.state.hidden_container_index.contains(&0u8)
+        {
+            self.containers[0].set_focus(true);
+        }
         if self.focused_child_ref().is_none() {
             if key.kind == KeyEventKind::Press {
                 if is_cycle_forward_hover_key(key) {
@@ -198,24 +441,10 @@ impl Interactable for GlyphPage {
                 if is_cycle_backward_hover_key(key) {
                     self.cycle_hover(-1);
                 }
-                if let KeyCode::Esc = key.code {
-                    if !self
-                        .state
-                        .local_entry_state_ref()
-                        .unwrap()
-                        .updated_entries
-                        .is_empty()
-                    {
-                        self.dialogs.push(
-                            ConfirmDialog::new("You have unsaved change! Exit anyway?")
-                                .on_submit(Box::new(|_, _| Ok(vec![AppCommand(PopPage)])))
-                                .into(),
-                        );
-                    } else {
-                        return Ok(vec![AppCommand(PopPage)]);
-                    }
+                if keymap().matches(key, Action::Back) {
+                    return Ok(self.close());
                 }
-                if let KeyCode::Enter = key.code
+                if keymap().matches(key, Action::Interact)
                     && let Some(index) = self.state.hovered_index
                 {
                     match index {
@@ -234,7 +463,7 @@ impl Interactable for GlyphPage {
                         _ => {}
                     }
                 }
-                if let KeyCode::Char('F') = key.code {
+                if keymap().matches(key, Action::FindEntry) {
                     self.dialogs.push(
                         SearchEntryDialog::new("Search Entry", self.state.entry_state.clone())
                             .on_submit(Box::new(|parent_state, state| {
@@ -266,9 +495,7 @@ impl Interactable for GlyphPage {
                             .into(),
                     );
                 }
-                if let KeyCode::Char('b') = key.code
-                    && key.modifiers.contains(KeyModifiers::CONTROL)
-                {
+                if keymap().matches(key, Action::ToggleNavigationBar) {
                     if self.state.hidden_container_index.contains(&0u8) {
                         self.state.hidden_container_index.remove(&0u8);
                     } else {
@@ -288,39 +515,114 @@ impl Interactable for GlyphPage {
             */
             let index: usize = self.focused_child_index().unwrap();
             let result = self.containers[index].handle(key, Some(&mut self.state));
-            if result.is_err() {
-                result
-            } else {
-                let mut processed_commands: Vec<Command> = Vec::new();
-                let mut commands = result?;
-                while let Some(command) = commands.pop() {
-                    match command {
-                        PageCommand(page_command) => match page_command {
-                            PopDialog => {
-                                self.dialogs.pop();
-                            }
-                            PushDialog(dialog) => {
-                                self.dialogs.push(dialog);
-                            }
-                        },
-                        _ => {
-                            processed_commands.insert(0, command);
-                        }
-                    }
+            Ok(self.process_page_commands(result?))
+        }
+    }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        // Dialogs are modal, the page underneath does not react meanwhile.
+        if let Some(dialog) = self.dialogs.last_mut() {
+            let commands: Vec<Command> = dialog.handle_mouse(mouse, Some(&mut self.state))?;
+            return Ok(self.process_page_commands(commands));
+        }
+        let position: Position = Position::new(mouse.column, mouse.row);
+        let Some(index) = self
+            .state
+            .content_areas
+            .get()
+            .iter()
+            .position(|area| area.contains(position))
+        else {
+            return Ok(Vec::new());
+        };
+        // Clicking a container moves the focus to it, as long as it could be interacted with.
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+            && !self.containers[index].is_focused()
+        {
+            if index == 1
+                && self
+                    .state
+                    .local_entry_state_ref()
+                    .unwrap()
+                    .active_entry_id
+                    .is_none()
+            {
+                return Ok(Vec::new());
+            }
+            for container in self.containers.iter_mut() {
+                container.set_focus(false);
+            }
+            self.containers[index].set_focus(true);
+            self.state.hovered_index = Some(index);
+        }
+        let commands: Vec<Command> =
+            self.containers[index].handle_mouse(mouse, Some(&mut self.state))?;
+        Ok(self.process_page_commands(commands))
+    }
+    fn handle_event(
+        &mut self,
+        event: &AppEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match event {
+            AppEvent::Tick => {
+                // The timer is started by the first unsaved change, so that it is saved after the interval.
+                let autosave_interval = parent_state
+                    .and_then(|state| state.downcast_mut::<AppState>())
+                    .and_then(|state| state.config.autosave_interval());
+                if let Some(interval) = autosave_interval
+                    && !self.state.is_autosave_scheduled
+                    && !self
+                        .state
+                        .local_entry_state_ref()
+                        .unwrap()
+                        .updated_entries
+                        .is_empty()
+                {
+                    self.state.is_autosave_scheduled = true;
+                    return Ok(vec![AppCommand(StartTimer(Timer::once(
+                        self.timer_name(AUTOSAVE_TIMER),
+                        interval,
+                    )))]);
                 }
-                Ok(processed_commands)
             }
+            AppEvent::Timer(name) if *name == self.timer_name(TOAST_TIMER) => {
+                self.state.toast = None;
+            }
+            AppEvent::Timer(name) if *name == self.timer_name(AUTOSAVE_TIMER) => {
+                self.state.is_autosave_scheduled = false;
+                self.state
+                    .local_entry_state_mut()
+                    .unwrap()
+                    .save_updated_entries_db()?;
+            }
+            _ => {}
         }
+        Ok(Vec::new())
     }
 
-    fn keymap(&self) -> Vec<(&str, &str)> {
-        [
-            ("j/k/up/down/tab/backtab", "Navigate"),
-            ("c-B", "Fold Navigation Bar"),
-            ("F", "Find Entry"),
-            ("Enter", "Interact"),
+    fn actions(&self) -> Vec<Action> {
+        // Keys go to the dialog or the focused child instead, except for undo and redo.
+        if !self.dialogs.is_empty() {
+            return Vec::new();
+        }
+        if self.focused_child_ref().is_some() {
+            return vec![Action::Undo, Action::Redo];
+        }
+        vec![
+            Action::NextItem,
+            Action::PreviousItem,
+            Action::ToggleNavigationBar,
+            Action::FindEntry,
+            Action::CreateEntry,
+            Action::CreateEntryFromTemplate,
+            Action::Interact,
+            Action::Undo,
+            Action::Redo,
         ]
-        .into()
     }
 }
 
@@ -362,6 +664,8 @@ pub struct GlyphNavigationBarState {
     pub line_height: usize,
     pub hovered_index: Option<usize>,
     pub offset: usize,
+    /// Area of the entries as last drawn, used to locate mouse clicks.
+    pub list_area: Cell<Rect>,
     // Shared Data
     pub entry_state: Rc<RefCell<LocalEntryState>>,
 }
@@ -386,6 +690,7 @@ impl GlyphNavigationBar {
                 line_height: 1,
                 hovered_index: None,
                 offset: 0,
+                list_area: Cell::new(Rect::default()),
 
                 entry_state,
             },
@@ -460,7 +765,7 @@ impl From<GlyphNavigationBar> for Box<dyn Container> {
 }
 
 impl Drawable for GlyphNavigationBar {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -491,7 +796,7 @@ impl Drawable for GlyphNavigationBar {
                     line = line.bold();
                 }
                 if is_hovered {
-                    line = line.bg(theme.surface_low_highlight());
+                    line = line.patch_style(theme.highlight());
                 }
                 if self
                     .state
@@ -508,6 +813,7 @@ impl Drawable for GlyphNavigationBar {
 
         let inner_area: Rect = widget_frame.inner(area);
         widget_frame.render(area, frame.buffer_mut());
+        self.state.list_area.set(inner_area);
         for (i, line) in list_items[self.state.offset..].iter().enumerate() {
             if i * self.state.line_height >= inner_area.height as usize {
                 break;
@@ -539,11 +845,11 @@ impl Interactable for GlyphNavigationBar {
                 if is_cycle_backward_hover_key(key) {
                     self.previous_entry();
                 }
-                if let KeyCode::Esc = key.code {
+                if keymap().matches(key, Action::Back) {
                     self.set_focus(false);
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Enter = key.code {
+                if keymap().matches(key, Action::Interact) {
                     if self.state.hovered_index.is_some() {
                         let index: usize = self.state.hovered_index.unwrap();
                         let _parent_state = parent_state
@@ -557,9 +863,9 @@ impl Interactable for GlyphNavigationBar {
                     }
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Char(c) = key.code {
-                    match c {
-                        'A' => {
+                if let Some(action) = keymap().action(key, &self.actions()) {
+                    match action {
+                        Action::CreateEntry => {
                             return Ok(vec![PageCommand(PushDialog(
                                 TextInputDialog::new(
                                     "New Entry Name",
@@ -587,7 +893,52 @@ impl Interactable for GlyphNavigationBar {
                                 .into(),
                             ))]);
                         }
-                        'F' => {
+                        Action::CreateEntryFromTemplate => {
+                            let templates: Vec<LayoutTemplate> = self
+                                .state
+                                .local_entry_state_ref()
+                                .unwrap()
+                                .read_layout_templates()?;
+                            return Ok(vec![PageCommand(PushDialog(
+                                TemplateDialog::new("New Entry from Template", templates)
+                                    .on_submit(Box::new(|_parent_state, state| {
+                                        let _state = state
+                                            .unwrap()
+                                            .downcast_mut::<TemplateDialogState>()
+                                            .unwrap();
+                                        let template: LayoutTemplate =
+                                            _state.hovered_template().unwrap().clone();
+                                        // Named after the entry once the template dialog is popped
+                                        Ok(vec![PageCommand(PushDialog(
+                                            TextInputDialog::new(
+                                                "New Entry Name",
+                                                template.name.as_str(),
+                                                Box::new(|value| !value.is_empty()),
+                                            )
+                                            .on_submit(Box::new(move |parent_state, state| {
+                                                let _parent_state = parent_state
+                                                    .unwrap()
+                                                    .downcast_mut::<GlyphPageState>()
+                                                    .unwrap();
+                                                let mut local_entry_state =
+                                                    _parent_state.local_entry_state_mut().unwrap();
+                                                let _state = state
+                                                    .unwrap()
+                                                    .downcast_mut::<TextInputDialogState>()
+                                                    .unwrap();
+                                                local_entry_state.create_entry_from_template_db(
+                                                    _state.text_input.as_str(),
+                                                    &template,
+                                                )?;
+                                                Ok(vec![])
+                                            }))
+                                            .into(),
+                                        ))])
+                                    }))
+                                    .into(),
+                            ))]);
+                        }
+                        Action::FilterEntry => {
                             return Ok(vec![PageCommand(PushDialog(
                                 TextInputDialog::new("Filter Entry", "", Box::new(|_value| true))
                                     .on_submit(
@@ -612,7 +963,7 @@ impl Interactable for GlyphNavigationBar {
                                     .into(),
                             ))]);
                         }
-                        'R' => {
+                        Action::RenameEntry => {
                             let active_entry_name: String =
                                 self.get_focused_entry_ref().unwrap().entry_name.clone();
                             return Ok(vec![PageCommand(PushDialog(
@@ -645,7 +996,7 @@ impl Interactable for GlyphNavigationBar {
                             ))]);
                         }
                         // Clone Entry
-                        'D' => {
+                        Action::DuplicateEntry => {
                             if self
                                 .state
                                 .entry_state
@@ -659,14 +1010,10 @@ impl Interactable for GlyphNavigationBar {
                                 self.state.local_entry_state_mut().unwrap();
                             let active_entry: Entry =
                                 local_entry_state.get_active_entry_ref().unwrap().clone();
-                            let cloned_eid =
-                                local_entry_state.insert_entry(active_entry.clone())?;
-                            for (sid, section) in active_entry.sections {
-                                local_entry_state.insert_section(&cloned_eid, section)?;
-                            }
+                            local_entry_state.insert_entry_with_sections(active_entry)?;
                             return Ok(Vec::new());
                         }
-                        'x' => {
+                        Action::DeleteEntry => {
                             if self
                                 .state
                                 .entry_state
@@ -701,14 +1048,38 @@ impl Interactable for GlyphNavigationBar {
             Ok(Vec::new())
         }
     }
-    fn keymap(&self) -> Vec<(&str, &str)> {
-        [
-            ("R", "Rename Active Entry"),
-            ("A", "Create Entry"),
-            ("D", "Duplicate Active Entry"),
-            ("Enter", "Open Entry"),
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let list_area: Rect = self.state.list_area.get();
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left)
+            || !list_area.contains(Position::new(mouse.column, mouse.row))
+        {
+            return Ok(Vec::new());
+        }
+        // Clicking an entry activates it, unlike Enter it never deactivates it.
+        let index: usize =
+            self.state.offset + (mouse.row - list_area.y) as usize / self.state.line_height;
+        let mut local_entry_state = self.state.local_entry_state_mut().unwrap();
+        if let Some((eid, _name)) = local_entry_state.ordered_entries.get(index) {
+            local_entry_state.active_entry_id = Some(*eid);
+            drop(local_entry_state);
+            self.state.hovered_index = Some(index);
+        }
+        Ok(Vec::new())
+    }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::RenameEntry,
+            Action::CreateEntry,
+            Action::CreateEntryFromTemplate,
+            Action::FilterEntry,
+            Action::DuplicateEntry,
+            Action::DeleteEntry,
+            Action::Interact,
         ]
-        .into()
     }
 }
 impl Focusable for GlyphNavigationBar {
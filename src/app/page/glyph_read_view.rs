@@ -1,31 +1,59 @@
+use crate::app::keymap::{keymap, Action};
 use crate::app::dialog::text_input_dialog::{TextInputDialog, TextInputDialogState};
 use crate::app::page::glyph_page::GlyphPageState;
 use crate::app::Command::PageCommand;
 use crate::app::PageCommand::PushDialog;
-use crate::app::{Command, Component, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::app::{
+    Command, Component, Container, DrawFlag, Drawable, Focusable, Interactable, MOUSE_SCROLL_LINES,
+};
 use crate::models::layout::{BorderMode, LayoutOrientation, SizeMode};
 use crate::models::section::Section;
 use crate::services::LocalEntryState;
-use crate::theme::{Iceberg, Theme};
+use crate::theme::Theme;
 use crate::utils::markdown_renderer::MarkdownRenderer;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use ratatui::buffer::Buffer;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::{Buffer, Cell};
 use ratatui::layout::{Constraint, Layout, Margin, Position, Rect, Size};
 use ratatui::style::Stylize;
-use ratatui::widgets::{Block, BorderType, Padding, StatefulWidget, Widget};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Padding, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+    Widget,
+};
 use ratatui::Frame;
 use std::any::Any;
 use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
 use std::rc::Rc;
 use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
 
+/// What the reader left shown of an entry, kept while the glyph is open.
+#[derive(Default, Clone)]
+pub struct EntryReadState {
+    /// Child shown by each stacked layout, keyed by the coordinate of the stack.
+    pub stack_selections: HashMap<Vec<usize>, usize>,
+    /// Coordinate of the stack switched by the keys, the first one drawn when not set.
+    pub targeted_stack: Option<Vec<usize>>,
+    /// Rows scrolled of each panel, keyed by the position of its section.
+    pub panel_scrolls: HashMap<u16, u16>,
+    /// Position of the section of the panel scrolled by the keys, the whole entry is scrolled
+    /// when not set.
+    pub focused_panel: Option<u16>,
+}
 pub struct GlyphReadState {
     pub is_focused: Rc<RefCell<bool>>, // Shared state across all view
     pub scroll_state: RefCell<ScrollViewState>,
+    /// Theme of the last render, used to print the entry.
+    pub theme: RefCell<Theme>,
+    /// Number of columns of the printed entry.
+    pub print_width: u16,
+    pub entry_read_states: HashMap<i64, EntryReadState>,
+    /// Stacks and panels of the last render, translated to the screen.
+    pub rendered: RefCell<RenderedEntry>,
 
     // Shared Data
     pub entry_state: Rc<RefCell<LocalEntryState>>,
@@ -49,7 +77,11 @@ impl From<GlyphReadView> for Box<dyn Container> {
     }
 }
 impl GlyphReadView {
-    pub fn new(shared_focus: Rc<RefCell<bool>>, entry_state: Rc<RefCell<LocalEntryState>>) -> Self {
+    pub fn new(
+        shared_focus: Rc<RefCell<bool>>,
+        entry_state: Rc<RefCell<LocalEntryState>>,
+        print_width: u16,
+    ) -> Self {
         let scroll_state = RefCell::new(ScrollViewState::default());
         Self {
             containers: vec![],
@@ -57,13 +89,116 @@ impl GlyphReadView {
             state: GlyphReadState {
                 is_focused: shared_focus,
                 scroll_state,
+                theme: RefCell::new(Theme::default()),
+                print_width,
+                entry_read_states: HashMap::new(),
+                rendered: RefCell::new(RenderedEntry::default()),
                 entry_state,
             },
         }
     }
+    /// What is shown of the active entry.
+    fn entry_read_state_mut(&mut self) -> Option<&mut EntryReadState> {
+        let eid: i64 = self.state.local_entry_state_ref()?.active_entry_id?;
+        Some(self.state.entry_read_states.entry(eid).or_default())
+    }
+    /// Show the child at the offset from the one shown by the targeted stack, wrapping around.
+    fn switch_stack_tab(&mut self, offset: isize) {
+        let Some((coordinate, index)) = self
+            .state
+            .rendered
+            .borrow()
+            .stacks
+            .iter()
+            .find(|stack| stack.is_targeted)
+            .map(|stack| {
+                let count: isize = stack.labels.len() as isize;
+                let index = (stack.selected_index as isize + offset).rem_euclid(count) as usize;
+                (stack.coordinate.clone(), index)
+            })
+        else {
+            return;
+        };
+        if let Some(read_state) = self.entry_read_state_mut() {
+            read_state
+                .stack_selections
+                .insert(coordinate.clone(), index);
+            read_state.targeted_stack = Some(coordinate);
+        }
+    }
+    /// Target the stack drawn after the targeted one, wrapping around.
+    fn target_next_stack(&mut self) {
+        let rendered = self.state.rendered.borrow();
+        let Some(index) = rendered.stacks.iter().position(|stack| stack.is_targeted) else {
+            return;
+        };
+        let coordinate: Vec<usize> = rendered.stacks[(index + 1) % rendered.stacks.len()]
+            .coordinate
+            .clone();
+        drop(rendered);
+        if let Some(read_state) = self.entry_read_state_mut() {
+            read_state.targeted_stack = Some(coordinate);
+        }
+    }
+    /// Focus the panel at the offset from the focused one, wrapping around.
+    fn cycle_panel(&mut self, offset: isize) {
+        let rendered = self.state.rendered.borrow();
+        let count: isize = rendered.panels.len() as isize;
+        if count == 0 {
+            return;
+        }
+        let index: usize = match rendered.panels.iter().position(|panel| panel.is_focused) {
+            Some(index) => (index as isize + offset).rem_euclid(count) as usize,
+            None if offset < 0 => (count - 1) as usize,
+            None => 0,
+        };
+        let position: u16 = rendered.panels[index].position;
+        drop(rendered);
+        if let Some(read_state) = self.entry_read_state_mut() {
+            read_state.focused_panel = Some(position);
+        }
+    }
+    /// Scroll the panel at the position by a number of rows, as long as its section overflows it.
+    /// Return whether it did.
+    fn scroll_panel(&mut self, position: u16, rows: isize) -> bool {
+        let Some(overflow) = self
+            .state
+            .rendered
+            .borrow()
+            .panels
+            .iter()
+            .find(|panel| panel.position == position && panel.overflow > 0)
+            .map(|panel| panel.overflow)
+        else {
+            return false;
+        };
+        if let Some(read_state) = self.entry_read_state_mut() {
+            let scroll: &mut u16 = read_state.panel_scrolls.entry(position).or_default();
+            *scroll = (*scroll as isize + rows).clamp(0, overflow as isize) as u16;
+        }
+        true
+    }
+    /// Scroll the focused panel by a number of rows, or by its height times the number of pages.
+    fn scroll_focused_panel(&mut self, rows: isize, pages: isize) -> bool {
+        let Some((position, viewport_height)) = self
+            .state
+            .rendered
+            .borrow()
+            .panels
+            .iter()
+            .find(|panel| panel.is_focused)
+            .map(|panel| (panel.position, panel.viewport_height))
+        else {
+            return false;
+        };
+        self.scroll_panel(position, rows + pages * viewport_height as isize)
+    }
 }
 impl Drawable for GlyphReadView {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        if *self.state.theme.borrow() != *theme {
+            self.state.theme.replace(theme.clone());
+        }
         /*
            Evaluate Page Layout via the root layout
         */
@@ -71,40 +206,35 @@ impl Drawable for GlyphReadView {
         let entry_state: Ref<LocalEntryState> = self.state.local_entry_state_ref().unwrap();
         let eid: i64 = entry_state.active_entry_id.unwrap();
         let layout = &entry_state.get_entry_ref(&eid).unwrap().layout;
-        match layout.details.size_mode {
-            SizeMode::Flex => {
-                let areas: Vec<(u16, Rect, BorderMode, u16)> = evaluate_read_areas(area, layout, 0);
-                let ref_sections: &Vec<(i64, Section)> = entry_state.get_sections_ref(&eid);
-                for (_sid, _section) in ref_sections {
-                    if let Some((_position, area, border_mode, padding)) =
-                        areas
-                            .iter()
-                            .find(|(_position, _area, _border_mode, _padding)| {
-                                *_position as i64 == _section.position
-                            })
-                    {
-                        let block = match border_mode {
-                            BorderMode::None => Block::new().title(_section.title.clone().bold()),
-                            BorderMode::Plain => {
-                                Block::bordered().title(_section.title.clone().bold())
-                            }
-                            BorderMode::Dashed => Block::bordered()
-                                .border_type(BorderType::LightDoubleDashed)
-                                .title(_section.title.clone().bold()),
-                            BorderMode::Rounded => Block::bordered()
-                                .border_type(BorderType::Rounded)
-                                .title(_section.title.clone().bold()),
-                        }
-                        .padding(Padding::uniform(*padding));
-                        let inner_area: Rect = block.inner(*area);
-                        block.render(*area, frame.buffer_mut());
-                        MarkdownRenderer::create(inner_area, theme)
-                            .render(_section.content.as_str(), frame.buffer_mut());
-                    }
-                }
-            }
+        let ref_sections: &Vec<(i64, Section)> = entry_state.get_sections_ref(&eid);
+        let read_state: EntryReadState = self
+            .state
+            .entry_read_states
+            .get(&eid)
+            .cloned()
+            .unwrap_or_default();
+        // The breakpoints of the layout follow the width of the terminal.
+        let view_width: u16 = frame.area().width;
+        let rendered: RenderedEntry = match layout.details.size_mode {
+            // Only a fixed length could overflow the view, the root fills it in the other modes.
+            SizeMode::Flex
+            | SizeMode::Percentage
+            | SizeMode::Ratio
+            | SizeMode::Min
+            | SizeMode::Max => render_entry(
+                frame.buffer_mut(),
+                area,
+                layout,
+                view_width,
+                ref_sections,
+                &read_state,
+                theme,
+            ),
             SizeMode::Length => {
-                let height = layout.details.length;
+                let height = fitted_length(layout, area.width, &|position, width| {
+                    measure_section(ref_sections, position, width, theme)
+                })
+                .unwrap_or(layout.details.length);
                 let mut scroll_view: ScrollView = ScrollView::new(Size {
                     width: area.width,
                     height,
@@ -112,64 +242,319 @@ impl Drawable for GlyphReadView {
                 .scrollbars_visibility(ScrollbarVisibility::Never);
                 let background: Block = Block::new().bg(theme.background());
                 background.render(scroll_view.area(), scroll_view.buf_mut());
-                let areas: Vec<(u16, Rect, BorderMode, u16)> =
-                    evaluate_read_areas(scroll_view.area(), layout, 0);
-                let ref_sections: &Vec<(i64, Section)> = entry_state.get_sections_ref(&eid);
-                for (_sid, section) in ref_sections {
-                    if let Some((_position, area, border_mode, padding)) =
-                        areas
-                            .iter()
-                            .find(|(_position, _area, _border_mode, _padding)| {
-                                *_position as i64 == section.position
-                            })
-                    {
-                        let block = match border_mode {
-                            BorderMode::None => Block::new().title(section.title.clone().bold()),
-                            BorderMode::Plain => {
-                                Block::bordered().title(section.title.clone().bold())
-                            }
-                            BorderMode::Dashed => Block::bordered()
-                                .border_type(BorderType::LightDoubleDashed)
-                                .title(section.title.clone().bold()),
-                            BorderMode::Rounded => Block::bordered()
-                                .border_type(BorderType::Rounded)
-                                .title(section.title.clone().bold()),
-                        }
-                        .padding(Padding::uniform(*padding));
-                        let inner_area: Rect = block.inner(*area);
-                        block.render(*area, scroll_view.buf_mut());
-                        MarkdownRenderer::create(inner_area, theme)
-                            .render(section.content.as_str(), scroll_view.buf_mut());
-                    }
-                }
+                let scroll_area: Rect = scroll_view.area();
+                let mut rendered: RenderedEntry = render_entry(
+                    scroll_view.buf_mut(),
+                    scroll_area,
+                    layout,
+                    view_width,
+                    ref_sections,
+                    &read_state,
+                    theme,
+                );
                 scroll_view.render(
                     area,
                     frame.buffer_mut(),
                     &mut *self.state.scroll_state.borrow_mut(),
                 );
+                // The entry is drawn in the scroll view, move the areas to where they are shown.
+                let offset: Position = self.state.scroll_state.borrow().offset();
+                let to_screen = |scrolled_area: Rect| match scrolled_area.bottom() > offset.y {
+                    true => Rect::new(
+                        area.x + scrolled_area.x,
+                        (area.y + scrolled_area.y).saturating_sub(offset.y),
+                        scrolled_area.width,
+                        scrolled_area.height,
+                    )
+                    .intersection(area),
+                    false => Rect::default(),
+                };
+                for stack in rendered.stacks.iter_mut() {
+                    for tab_area in stack.tab_areas.iter_mut() {
+                        *tab_area = to_screen(*tab_area);
+                    }
+                }
+                for panel in rendered.panels.iter_mut() {
+                    panel.area = to_screen(panel.area);
+                }
+                rendered
+            }
+        };
+        self.state.rendered.replace(rendered);
+    }
+}
+/// The stacks and panels drawn of an entry.
+#[derive(Default)]
+pub struct RenderedEntry {
+    pub stacks: Vec<StackPanel>,
+    pub panels: Vec<ReadPanel>,
+}
+/// A stacked layout as evaluated, only its selected child is drawn and the others are listed as
+/// tabs in its border.
+pub struct StackPanel {
+    pub coordinate: Vec<usize>,
+    pub area: Rect,
+    pub border_mode: BorderMode,
+    pub labels: Vec<String>,
+    pub selected_index: usize,
+    pub is_targeted: bool,
+    /// Areas of the tabs as last drawn, relative to the buffer drawn into.
+    pub tab_areas: Vec<Rect>,
+}
+/// A panel drawn with the section at its position.
+pub struct ReadPanel {
+    pub position: u16,
+    pub area: Rect,
+    /// Rows of the section shown at once.
+    pub viewport_height: u16,
+    /// Rows of the section that could be scrolled to.
+    pub overflow: u16,
+    pub is_focused: bool,
+}
+/// Rows of the section at the position rendered in the width.
+fn measure_section(
+    sections: &[(i64, Section)],
+    position: u16,
+    width: u16,
+    theme: &Theme,
+) -> Option<u16> {
+    sections
+        .iter()
+        .find(|(_sid, section)| section.position == position as i64)
+        .map(|(_sid, section)| MarkdownRenderer::measure(width, section.content.as_str(), theme))
+}
+/// Draw the sections at their position within the layout, along with the tabs of the stacked
+/// layouts and the scrollbars of the panels overflowing.
+fn render_entry(
+    buffer: &mut Buffer,
+    area: Rect,
+    layout: &crate::models::layout::Layout,
+    view_width: u16,
+    sections: &[(i64, Section)],
+    read_state: &EntryReadState,
+    theme: &Theme,
+) -> RenderedEntry {
+    let mut rendered: RenderedEntry = RenderedEntry::default();
+    let areas: Vec<(u16, Rect, BorderMode, u16)> = evaluate_read_areas(
+        area,
+        layout,
+        view_width,
+        Vec::new(),
+        &read_state.stack_selections,
+        &|position, width| measure_section(sections, position, width, theme),
+        &mut rendered.stacks,
+    );
+    for (_sid, section) in sections {
+        if let Some((position, area, border_mode, padding)) =
+            areas
+                .iter()
+                .find(|(_position, _area, _border_mode, _padding)| {
+                    *_position as i64 == section.position
+                })
+        {
+            let is_focused: bool = read_state.focused_panel == Some(*position);
+            let mut block = border_block(border_mode)
+                .title(section.title.clone().bold())
+                .padding(Padding::uniform(*padding));
+            if is_focused {
+                block = block
+                    .border_style(theme.highlight())
+                    .title_style(theme.highlight());
             }
+            let inner_area: Rect = block.inner(*area);
+            block.render(*area, buffer);
+
+            let mut content_area: Rect = inner_area;
+            let mut content_height: u16 =
+                MarkdownRenderer::measure(content_area.width, section.content.as_str(), theme);
+            // Without a border, the scrollbar takes the last column of the panel.
+            if content_height > content_area.height && *border_mode == BorderMode::None {
+                content_area.width = content_area.width.saturating_sub(1);
+                content_height =
+                    MarkdownRenderer::measure(content_area.width, section.content.as_str(), theme);
+            }
+            let overflow: u16 = content_height.saturating_sub(content_area.height);
+            if overflow == 0 {
+                MarkdownRenderer::create(content_area, theme)
+                    .render(section.content.as_str(), buffer);
+            } else {
+                let scroll: u16 = read_state
+                    .panel_scrolls
+                    .get(position)
+                    .copied()
+                    .unwrap_or(0)
+                    .min(overflow);
+                // Draw the whole section aside, then copy the rows scrolled to.
+                let mut section_buffer: Buffer =
+                    Buffer::empty(Rect::new(0, 0, content_area.width, content_height));
+                MarkdownRenderer::create(section_buffer.area, theme)
+                    .render(section.content.as_str(), &mut section_buffer);
+                for y in 0..content_area.height {
+                    for x in 0..content_area.width {
+                        let cell = &section_buffer[(x, y + scroll)];
+                        if *cell != Cell::EMPTY {
+                            buffer[(content_area.x + x, content_area.y + y)] = cell.clone();
+                        }
+                    }
+                }
+                let scrollbar_area: Rect = match border_mode {
+                    BorderMode::None => {
+                        Rect::new(content_area.right(), content_area.y, 1, content_area.height)
+                    }
+                    _ => area.inner(Margin::new(0, 1)),
+                };
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .render(
+                        scrollbar_area,
+                        buffer,
+                        &mut ScrollbarState::new(overflow as usize + 1)
+                            .position(scroll as usize)
+                            .viewport_content_length(content_area.height as usize),
+                    );
+            }
+            rendered.panels.push(ReadPanel {
+                position: *position,
+                area: *area,
+                viewport_height: content_area.height,
+                overflow,
+                is_focused,
+            });
         }
     }
+    // Keep the key target on a stack drawn.
+    let targeted_index: usize = rendered
+        .stacks
+        .iter()
+        .position(|stack| Some(&stack.coordinate) == read_state.targeted_stack.as_ref())
+        .unwrap_or(0);
+    for (stack_index, stack) in rendered.stacks.iter_mut().enumerate() {
+        stack.is_targeted = stack_index == targeted_index;
+        let block: Block = border_block(&stack.border_mode);
+        // Titles are drawn between the corners of the top border.
+        let inner_area: Rect = block.inner(stack.area);
+        let title_area: Rect = Rect::new(inner_area.x, stack.area.y, inner_area.width, 1);
+        let mut x: u16 = title_area.x;
+        let mut title: Line = Line::default();
+        for (index, label) in stack.labels.iter().enumerate() {
+            let mut tab: Span = Span::from(format!(" {} ", label));
+            if index == stack.selected_index {
+                tab = tab.bold().underlined();
+                if stack.is_targeted {
+                    tab = tab.patch_style(theme.highlight());
+                }
+            } else {
+                tab = tab.dim();
+            }
+            let width: u16 = tab.width() as u16;
+            stack
+                .tab_areas
+                .push(Rect::new(x, title_area.y, width, 1).intersection(title_area));
+            x += width;
+            title.push_span(tab);
+        }
+        block.title(title).render(stack.area, buffer);
+    }
+    rendered
+}
+/// An empty frame of the border mode.
+fn border_block(border_mode: &BorderMode) -> Block<'static> {
+    match border_mode {
+        BorderMode::None => Block::new(),
+        BorderMode::Plain => Block::bordered(),
+        BorderMode::Dashed => Block::bordered().border_type(BorderType::LightDoubleDashed),
+        BorderMode::Rounded => Block::bordered().border_type(BorderType::Rounded),
+    }
+}
+/// Height of a `Length` panel fitted to its section in the width, including its frame.
+fn fitted_length(
+    layout: &crate::models::layout::Layout,
+    width: u16,
+    measure: &dyn Fn(u16, u16) -> Option<u16>,
+) -> Option<u16> {
+    if layout.details.size_mode != SizeMode::Length
+        || !layout.details.fit_content
+        || !layout.sub_layouts.is_empty()
+    {
+        return None;
+    }
+    // The title takes the top row when there is no border.
+    let (border_width, border_height): (u16, u16) = match layout.details.border_mode {
+        BorderMode::None => (0, 1),
+        _ => (2, 2),
+    };
+    let spacing: u16 = layout
+        .details
+        .margin
+        .saturating_add(layout.details.padding)
+        .saturating_mul(2);
+    let content_height: u16 = measure(
+        layout.section_index?,
+        width.saturating_sub(spacing.saturating_add(border_width)),
+    )?;
+    Some(content_height.saturating_add(spacing.saturating_add(border_height)))
 }
 fn evaluate_read_areas(
     area: Rect,
     layout: &crate::models::layout::Layout,
-    depth: u16,
+    view_width: u16,
+    at: Vec<usize>,
+    selections: &HashMap<Vec<usize>, usize>,
+    measure: &dyn Fn(u16, u16) -> Option<u16>,
+    stacks: &mut Vec<StackPanel>,
 ) -> Vec<(u16, Rect, BorderMode, u16)> {
-    let recursive_area: Rect = Block::default().inner(area);
+    let mut recursive_area: Rect = Block::default().inner(area);
+    let orientation: &LayoutOrientation = layout.details.orientation_at(view_width);
+
+    // Children of a stack share its area, below the tabs.
+    let selected_index: usize = selections
+        .get(&at)
+        .copied()
+        .unwrap_or(0)
+        .min(layout.sub_layouts.len().saturating_sub(1));
+    if *orientation == LayoutOrientation::Stack && !layout.sub_layouts.is_empty() {
+        let panel_area: Rect =
+            area.inner(Margin::new(layout.details.margin, layout.details.margin));
+        recursive_area = border_block(&layout.details.border_mode)
+            .title("Tabs")
+            .inner(panel_area);
+        stacks.push(StackPanel {
+            coordinate: at.clone(),
+            area: panel_area,
+            border_mode: layout.details.border_mode.clone(),
+            labels: layout
+                .sub_layouts
+                .iter()
+                .enumerate()
+                .map(|(index, sub_layout)| match sub_layout.label.is_empty() {
+                    true => format!("Tab {}", index + 1),
+                    false => sub_layout.label.clone(),
+                })
+                .collect(),
+            selected_index,
+            is_targeted: false,
+            tab_areas: Vec::new(),
+        });
+    }
 
-    // Process the child
+    // Process the child, the panels laid vertically may be fitted to their section and the hidden
+    // ones take no space.
     let constraints: Vec<Constraint> = layout
         .sub_layouts
         .iter()
-        .map(|sub| match sub.details.size_mode {
-            SizeMode::Flex => Constraint::Fill(sub.details.flex),
-            SizeMode::Length => Constraint::Length(sub.details.length),
+        .map(|sub| match orientation {
+            _ if sub.details.is_hidden_at(view_width) => Constraint::Length(0),
+            LayoutOrientation::Vertical => fitted_length(sub, recursive_area.width, measure)
+                .map(Constraint::Length)
+                .unwrap_or(sub.details.constraint()),
+            _ => sub.details.constraint(),
         })
         .collect();
-    let sub_areas = match layout.details.orientation {
+    let sub_areas: Rc<[Rect]> = match orientation {
         LayoutOrientation::Vertical => Layout::vertical(constraints).split(recursive_area),
         LayoutOrientation::Horizontal => Layout::horizontal(constraints).split(recursive_area),
+        LayoutOrientation::Stack => Rc::from(vec![recursive_area; layout.sub_layouts.len()]),
     };
 
     let mut areas: Vec<(u16, Rect, BorderMode, u16)> = vec![];
@@ -185,9 +570,25 @@ fn evaluate_read_areas(
     }
 
     for (i, sub_layout) in layout.sub_layouts.iter().enumerate() {
+        // The children of a stack behind the selected one are not drawn, nor the hidden ones.
+        if (*orientation == LayoutOrientation::Stack && i != selected_index)
+            || sub_layout.details.is_hidden_at(view_width)
+        {
+            continue;
+        }
+        let mut sub_at: Vec<usize> = at.clone();
+        sub_at.push(i);
         areas = [
             areas,
-            evaluate_read_areas(sub_areas[i], sub_layout, depth + 1),
+            evaluate_read_areas(
+                sub_areas[i],
+                sub_layout,
+                view_width,
+                sub_at,
+                selections,
+                measure,
+                stacks,
+            ),
         ]
         .concat()
     }
@@ -202,34 +603,74 @@ impl Interactable for GlyphReadView {
     ) -> Result<Vec<Command>> {
         match key.kind {
             KeyEventKind::Press => {
-                if let KeyCode::Esc = key.code {
+                if keymap().matches(key, Action::Back) {
+                    // Leave the focused panel first.
+                    if let Some(read_state) = self.entry_read_state_mut()
+                        && read_state.focused_panel.is_some()
+                    {
+                        read_state.focused_panel = None;
+                        return Ok(Vec::new());
+                    }
                     self.set_focus(false);
                     return Ok(Vec::new());
                 }
-                if let KeyCode::PageUp = key.code {
-                    self.state.scroll_state.borrow_mut().scroll_page_up();
+                if keymap().matches(key, Action::NextPanel) {
+                    self.cycle_panel(1);
                     return Ok(Vec::new());
                 }
-                if let KeyCode::PageDown = key.code {
-                    self.state.scroll_state.borrow_mut().scroll_page_down();
+                if keymap().matches(key, Action::PreviousPanel) {
+                    self.cycle_panel(-1);
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Up = key.code {
-                    self.state.scroll_state.borrow_mut().scroll_up();
+                // The focused panel is scrolled when its section overflows it, the entry otherwise.
+                if keymap().matches(key, Action::ScrollPageUp) {
+                    if !self.scroll_focused_panel(0, -1) {
+                        self.state.scroll_state.borrow_mut().scroll_page_up();
+                    }
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Down = key.code {
-                    self.state.scroll_state.borrow_mut().scroll_down();
+                if keymap().matches(key, Action::ScrollPageDown) {
+                    if !self.scroll_focused_panel(0, 1) {
+                        self.state.scroll_state.borrow_mut().scroll_page_down();
+                    }
                     return Ok(Vec::new());
                 }
-                if let KeyCode::Char('P') = key.code {
+                if keymap().matches(key, Action::ScrollUp) {
+                    if !self.scroll_focused_panel(-1, 0) {
+                        self.state.scroll_state.borrow_mut().scroll_up();
+                    }
+                    return Ok(Vec::new());
+                }
+                if keymap().matches(key, Action::ScrollDown) {
+                    if !self.scroll_focused_panel(1, 0) {
+                        self.state.scroll_state.borrow_mut().scroll_down();
+                    }
+                    return Ok(Vec::new());
+                }
+                if keymap().matches(key, Action::NextStackTab) {
+                    self.switch_stack_tab(1);
+                    return Ok(Vec::new());
+                }
+                if keymap().matches(key, Action::PreviousStackTab) {
+                    self.switch_stack_tab(-1);
+                    return Ok(Vec::new());
+                }
+                if keymap().matches(key, Action::NextStack) {
+                    self.target_next_stack();
+                    return Ok(Vec::new());
+                }
+                if keymap().matches(key, Action::PrintEntry) {
+                    let theme: Theme = self.state.theme.borrow().clone();
+                    let print_width: u16 = self.state.print_width;
+                    let entry_read_states: HashMap<i64, EntryReadState> =
+                        self.state.entry_read_states.clone();
                     return Ok(vec![PageCommand(PushDialog(
                         TextInputDialog::new(
                             "Path (TODO: Allow user to input size)",
                             "./",
                             Box::new(|input| !input.ends_with("/")),
                         )
-                        .on_submit(Box::new(|parent_state, state| {
+                        .on_submit(Box::new(move |parent_state, state| {
                             let _parent_state = parent_state
                                 .unwrap()
                                 .downcast_ref::<GlyphPageState>()
@@ -247,40 +688,33 @@ impl Interactable for GlyphReadView {
                             let eid: i64 = entry_state.active_entry_id.unwrap();
                             let layout: &crate::models::layout::Layout =
                                 &entry_state.get_entry_ref(&eid).unwrap().layout;
-                            let mut buffer: Buffer =
-                                Buffer::empty(Rect::new(0, 0, 96, layout.details.length));
-                            let areas: Vec<(u16, Rect, BorderMode, u16)> =
-                                evaluate_read_areas(*buffer.area(), layout, 0);
                             let ref_sections: &Vec<(i64, Section)> =
                                 entry_state.get_sections_ref(&eid);
-                            for (_sid, section) in ref_sections {
-                                if let Some((_position, area, border_mode, padding)) = areas
-                                    .iter()
-                                    .find(|(_position, _area, _border_mode, _padding)| {
-                                        *_position as i64 == section.position
-                                    })
-                                {
-                                    let block = match border_mode {
-                                        BorderMode::None => {
-                                            Block::new().title(section.title.clone().bold())
-                                        }
-                                        BorderMode::Plain => {
-                                            Block::bordered().title(section.title.clone().bold())
-                                        }
-                                        BorderMode::Dashed => Block::bordered()
-                                            .border_type(BorderType::LightDoubleDashed)
-                                            .title(section.title.clone().bold()),
-                                        BorderMode::Rounded => Block::bordered()
-                                            .border_type(BorderType::Rounded)
-                                            .title(section.title.clone().bold()),
-                                    }
-                                    .padding(Padding::uniform(*padding));
-                                    let inner_area: Rect = block.inner(*area);
-                                    block.render(*area, &mut buffer);
-                                    MarkdownRenderer::create(inner_area, &Iceberg)
-                                        .render(section.content.as_str(), &mut buffer);
-                                }
-                            }
+                            let height: u16 =
+                                fitted_length(layout, print_width, &|position, width| {
+                                    measure_section(ref_sections, position, width, &theme)
+                                })
+                                .unwrap_or(layout.details.length);
+                            let print_area: Rect = Rect::new(0, 0, print_width, height);
+                            let mut buffer: Buffer = Buffer::empty(print_area);
+                            // Stacks are printed with the child shown by the reader, panels from
+                            // their top.
+                            let read_state: EntryReadState = EntryReadState {
+                                stack_selections: entry_read_states
+                                    .get(&eid)
+                                    .map(|read_state| read_state.stack_selections.clone())
+                                    .unwrap_or_default(),
+                                ..Default::default()
+                            };
+                            render_entry(
+                                &mut buffer,
+                                print_area,
+                                layout,
+                                print_width,
+                                ref_sections,
+                                &read_state,
+                                &theme,
+                            );
                             let final_area = buffer.area();
                             for y in final_area.y..final_area.y + final_area.height {
                                 let mut line_bytes = Vec::new();
@@ -301,6 +735,83 @@ impl Interactable for GlyphReadView {
             _ => Ok(Vec::new()),
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let position: Position = Position::new(mouse.column, mouse.row);
+        let rendered = self.state.rendered.borrow();
+        let clicked_tab: Option<(Vec<usize>, usize)> = rendered.stacks.iter().find_map(|stack| {
+            stack
+                .tab_areas
+                .iter()
+                .position(|tab_area| tab_area.contains(position))
+                .map(|index| (stack.coordinate.clone(), index))
+        });
+        let hovered_panel: Option<u16> = rendered
+            .panels
+            .iter()
+            .find(|panel| panel.area.contains(position))
+            .map(|panel| panel.position);
+        drop(rendered);
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+            && let Some(read_state) = self.entry_read_state_mut()
+        {
+            // Clicking a tab of a stack shows its child and targets the stack, clicking a panel
+            // focuses it.
+            if let Some((coordinate, index)) = clicked_tab {
+                read_state
+                    .stack_selections
+                    .insert(coordinate.clone(), index);
+                read_state.targeted_stack = Some(coordinate);
+            } else if hovered_panel.is_some() {
+                read_state.focused_panel = hovered_panel;
+            }
+            return Ok(Vec::new());
+        }
+        // The panel under the cursor is scrolled when its section overflows it.
+        let rows: isize = match mouse.kind {
+            MouseEventKind::ScrollUp => -(MOUSE_SCROLL_LINES as isize),
+            MouseEventKind::ScrollDown => MOUSE_SCROLL_LINES as isize,
+            _ => 0,
+        };
+        if rows != 0
+            && let Some(position) = hovered_panel
+            && self.scroll_panel(position, rows)
+        {
+            return Ok(Vec::new());
+        }
+        let mut scroll_state = self.state.scroll_state.borrow_mut();
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                for _ in 0..MOUSE_SCROLL_LINES {
+                    scroll_state.scroll_up();
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                for _ in 0..MOUSE_SCROLL_LINES {
+                    scroll_state.scroll_down();
+                }
+            }
+            _ => {}
+        }
+        Ok(Vec::new())
+    }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::ScrollUp,
+            Action::ScrollDown,
+            Action::ScrollPageUp,
+            Action::ScrollPageDown,
+            Action::PrintEntry,
+            Action::NextPanel,
+            Action::PreviousPanel,
+            Action::NextStackTab,
+            Action::PreviousStackTab,
+            Action::NextStack,
+        ]
+    }
 }
 impl Focusable for GlyphReadView {
     fn is_focused(&self) -> bool {
@@ -320,3 +831,177 @@ impl Focusable for GlyphReadView {
         None
     }
 }
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::layout::{Breakpoint, BreakpointRule, Layout};
+
+    #[test]
+    fn test_stacked_layout() {
+        // A main section beside a stack of the references and the changelog.
+        let mut root: Layout = Layout::new("root");
+        root.details.orientation = LayoutOrientation::Horizontal;
+        let mut main: Layout = Layout::new("main");
+        main.section_index = Some(0);
+        let mut stack: Layout = Layout::new("");
+        stack.details.orientation = LayoutOrientation::Stack;
+        stack.details.border_mode = BorderMode::Plain;
+        let mut references: Layout = Layout::new("References");
+        references.section_index = Some(1);
+        let mut changelog: Layout = Layout::new("");
+        changelog.section_index = Some(2);
+        stack.sub_layouts = vec![references, changelog];
+        root.sub_layouts = vec![main, stack];
+
+        let area: Rect = Rect::new(0, 0, 40, 10);
+        let mut read_state: EntryReadState = EntryReadState::default();
+        let mut stacks: Vec<StackPanel> = Vec::new();
+        let areas = evaluate_read_areas(
+            area,
+            &root,
+            area.width,
+            Vec::new(),
+            &read_state.stack_selections,
+            &|_position, _width| None,
+            &mut stacks,
+        );
+        let positions: Vec<u16> = areas.iter().map(|(position, ..)| *position).collect();
+        assert_eq!(positions, vec![0, 1]);
+        assert_eq!(stacks.len(), 1);
+        assert_eq!(stacks[0].coordinate, vec![1]);
+        assert_eq!(stacks[0].labels, vec!["References", "Tab 2"]);
+        // The child is drawn within the border of the stack.
+        assert_eq!(areas[1].1, Rect::new(21, 1, 18, 8));
+
+        // The selection is clamped to the children.
+        read_state.stack_selections.insert(vec![1], 5);
+        let mut buffer: Buffer = Buffer::empty(area);
+        let stacks: Vec<StackPanel> = render_entry(
+            &mut buffer,
+            area,
+            &root,
+            area.width,
+            &[],
+            &read_state,
+            &Theme::default(),
+        )
+        .stacks;
+        assert_eq!(stacks[0].selected_index, 1);
+        assert_eq!(
+            stacks[0].tab_areas,
+            vec![Rect::new(21, 0, 12, 1), Rect::new(33, 0, 6, 1)]
+        );
+        let strip: String = (21..39)
+            .map(|x| buffer[(x, 0)].symbol().to_string())
+            .collect();
+        assert_eq!(strip, " References  Tab 2");
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        // Two panels side by side, stacked below 30 columns, the side one hidden below 20.
+        let mut root: Layout = Layout::new("root");
+        root.details.orientation = LayoutOrientation::Horizontal;
+        root.details.breakpoints = vec![Breakpoint {
+            below: 30,
+            rule: BreakpointRule::Orientation(LayoutOrientation::Vertical),
+        }];
+        let mut main: Layout = Layout::new("main");
+        main.section_index = Some(0);
+        let mut side: Layout = Layout::new("side");
+        side.section_index = Some(1);
+        side.details.breakpoints = vec![Breakpoint {
+            below: 20,
+            rule: BreakpointRule::Hide,
+        }];
+        root.sub_layouts = vec![main, side];
+
+        let area: Rect = Rect::new(0, 0, 40, 10);
+        let evaluate = |view_width: u16| -> Vec<(u16, Rect)> {
+            evaluate_read_areas(
+                area,
+                &root,
+                view_width,
+                Vec::new(),
+                &HashMap::new(),
+                &|_position, _width| None,
+                &mut Vec::new(),
+            )
+            .into_iter()
+            .map(|(position, area, ..)| (position, area))
+            .collect()
+        };
+        assert_eq!(
+            evaluate(30),
+            vec![(0, Rect::new(0, 0, 20, 10)), (1, Rect::new(20, 0, 20, 10))]
+        );
+        assert_eq!(
+            evaluate(29),
+            vec![(0, Rect::new(0, 0, 40, 5)), (1, Rect::new(0, 5, 40, 5))]
+        );
+        assert_eq!(evaluate(19), vec![(0, Rect::new(0, 0, 40, 10))]);
+    }
+
+    #[test]
+    fn test_panel_scroll() {
+        let content: String = (1..=10)
+            .map(|line| format!("line {}", line))
+            .collect::<Vec<String>>()
+            .join("\n\n");
+        let sections: Vec<(i64, Section)> = vec![(1, Section::new("Notes", &content, 0))];
+        let mut root: Layout = Layout::new("root");
+        root.section_index = Some(0);
+        root.details.border_mode = BorderMode::Plain;
+        let theme: Theme = Theme::default();
+
+        // Ten lines in a panel showing four of them.
+        let area: Rect = Rect::new(0, 0, 20, 6);
+        let mut read_state: EntryReadState = EntryReadState::default();
+        read_state.panel_scrolls.insert(0, 4);
+        let mut buffer: Buffer = Buffer::empty(area);
+        let rendered: RenderedEntry = render_entry(
+            &mut buffer,
+            area,
+            &root,
+            area.width,
+            &sections,
+            &read_state,
+            &theme,
+        );
+        assert_eq!(rendered.panels[0].viewport_height, 4);
+        assert_eq!(rendered.panels[0].overflow, 6);
+        let row: String = (1..8)
+            .map(|x| buffer[(x, 1)].symbol().to_string())
+            .collect();
+        assert_eq!(row, "line 5 ");
+
+        // The scroll stops at the last line.
+        read_state.panel_scrolls.insert(0, 42);
+        let mut buffer: Buffer = Buffer::empty(area);
+        render_entry(
+            &mut buffer,
+            area,
+            &root,
+            area.width,
+            &sections,
+            &read_state,
+            &theme,
+        );
+        let row: String = (1..8)
+            .map(|x| buffer[(x, 4)].symbol().to_string())
+            .collect();
+        assert_eq!(row, "line 10");
+
+        // A panel fitted to its section takes its lines along with the border.
+        root.details.size_mode = SizeMode::Length;
+        root.details.fit_content = true;
+        let measure = |position, width| measure_section(&sections, position, width, &theme);
+        assert_eq!(fitted_length(&root, 20, &measure), Some(12));
+        // A spacing too large for the view is capped instead of overflowing.
+        root.details.padding = 40000;
+        assert_eq!(fitted_length(&root, 20, &measure), Some(u16::MAX));
+        root.details.padding = 0;
+        root.details.fit_content = false;
+        assert_eq!(fitted_length(&root, 20, &measure), None);
+    }
+}
@@ -0,0 +1,486 @@
+use crate::app::event::AppEvent;
+use crate::app::keymap::{keymap, Action};
+use crate::app::page::glyph_page::GlyphPage;
+use crate::app::AppCommand::{PopPage, RecordRecentGlyph};
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::models::entry::Entry;
+use crate::models::section::Section;
+use crate::recent;
+use crate::theme::Theme;
+use crate::utils::cycle_offset;
+use color_eyre::eyre::Report;
+use color_eyre::Result;
+use crossterm::event::{KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::prelude::{Line, Span, Stylize, Widget};
+use ratatui::Frame;
+use std::any::Any;
+use std::cell::RefCell;
+
+/// Actions on the tabs, available wherever the focus is inside the glyphs.
+const TAB_ACTIONS: [Action; 7] = [
+    Action::NextTab,
+    Action::PreviousTab,
+    Action::MoveTabLeft,
+    Action::MoveTabRight,
+    Action::CloseTab,
+    Action::CopyEntryToGlyph,
+    Action::CopySectionToGlyph,
+];
+
+/// What is copied to another glyph, taken as it is when the copy starts.
+pub enum Clipping {
+    Entry(Entry),
+    Section(Section),
+}
+impl Clipping {
+    fn name(&self) -> &str {
+        match self {
+            Clipping::Entry(entry) => entry.entry_name.as_str(),
+            Clipping::Section(section) => section.title.as_str(),
+        }
+    }
+}
+
+pub struct GlyphTabsPageState {
+    pub is_focused: bool,
+    pub active_index: usize,
+    /// The clipping and the tab it goes to, while the user is picking the tab.
+    pub copying: Option<(Clipping, usize)>,
+    /// Areas of the tabs as last drawn, used to locate mouse clicks.
+    pub tab_areas: RefCell<Vec<Rect>>,
+}
+/// The opened glyphs, one tab each, only the active one is drawn and handles the keys.
+pub struct GlyphTabsPage {
+    pub tabs: Vec<GlyphPage>,
+    pub state: GlyphTabsPageState,
+}
+
+impl GlyphTabsPage {
+    pub fn new(glyph_page: GlyphPage) -> Self {
+        Self {
+            tabs: vec![glyph_page],
+            state: GlyphTabsPageState {
+                is_focused: true,
+                active_index: 0,
+                copying: None,
+                tab_areas: RefCell::new(Vec::new()),
+            },
+        }
+    }
+    /// Open the glyph next to the active tab, or switch to its tab when it is opened already.
+    pub fn open(&mut self, glyph_page: GlyphPage) {
+        if let Some(path) = glyph_page.path().map(|path| recent::normalize(&path))
+            && let Some(index) = self.tabs.iter().position(|tab| {
+                tab.path().map(|path| recent::normalize(&path)) == Some(path.clone())
+            })
+        {
+            self.state.active_index = index;
+            return;
+        }
+        self.tabs.insert(self.state.active_index + 1, glyph_page);
+        self.state.active_index += 1;
+    }
+    pub fn active_tab_ref(&self) -> &GlyphPage {
+        &self.tabs[self.state.active_index]
+    }
+    pub fn active_tab_mut(&mut self) -> &mut GlyphPage {
+        &mut self.tabs[self.state.active_index]
+    }
+    /// The tabs from the least recently active one, the active tab being the last.
+    pub fn tabs_by_activity(&self) -> impl Iterator<Item = &GlyphPage> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter(|(index, _tab)| *index != self.state.active_index)
+            .map(|(_index, tab)| tab)
+            .chain(self.tabs.get(self.state.active_index))
+    }
+    pub fn switch_tab(&mut self, offset: i16) {
+        self.state.active_index = cycle_offset(
+            self.state.active_index as u16,
+            offset,
+            self.tabs.len() as u16,
+        ) as usize;
+    }
+    /// Swap the active tab with its neighbour, the tabs at both ends stay there.
+    pub fn move_tab(&mut self, offset: i16) {
+        let Some(index) = self
+            .state
+            .active_index
+            .checked_add_signed(offset as isize)
+            .filter(|index| *index < self.tabs.len())
+        else {
+            return;
+        };
+        self.tabs.swap(self.state.active_index, index);
+        self.state.active_index = index;
+    }
+    /// Start picking the tab to copy to, the next one is picked at first.
+    pub fn start_copying(&mut self, clipping: Clipping) -> Result<()> {
+        if self.tabs.len() < 2 {
+            return Err(Report::msg("Open another glyph to copy to"));
+        }
+        let target_index: usize =
+            cycle_offset(self.state.active_index as u16, 1, self.tabs.len() as u16) as usize;
+        self.state.copying = Some((clipping, target_index));
+        Ok(())
+    }
+    /// Pick another tab to copy to, skipping the active one.
+    pub fn cycle_copy_target(&mut self, offset: i16) {
+        let active_index: usize = self.state.active_index;
+        let num_tabs: u16 = self.tabs.len() as u16;
+        if let Some((_clipping, target_index)) = &mut self.state.copying {
+            *target_index = cycle_offset(*target_index as u16, offset, num_tabs) as usize;
+            if *target_index == active_index {
+                *target_index = cycle_offset(*target_index as u16, offset, num_tabs) as usize;
+            }
+        }
+    }
+    /// Copy the clipping into the picked glyph and switch to it. An entry is saved as a new
+    /// entry of the glyph, a section is appended to its active entry.
+    pub fn copy(&mut self) -> Result<()> {
+        let Some((clipping, target_index)) = self.state.copying.take() else {
+            return Ok(());
+        };
+        let target: &mut GlyphPage = &mut self.tabs[target_index];
+        let target_name: String = target.name();
+        let mut local_entry_state = target.state.local_entry_state_mut().unwrap();
+        match clipping {
+            Clipping::Entry(entry) => {
+                let eid: i64 = local_entry_state.insert_entry_with_sections(entry)?;
+                local_entry_state.active_entry_id = Some(eid);
+            }
+            Clipping::Section(section) => {
+                if local_entry_state.active_entry_id.is_none() {
+                    return Err(Report::msg(format!(
+                        "Select an entry of {} to copy the section to",
+                        target_name
+                    )));
+                }
+                local_entry_state
+                    .create_section_to_active_entry_db(&section.title, &section.content)?;
+            }
+        }
+        drop(local_entry_state);
+        self.state.active_index = target_index;
+        Ok(())
+    }
+    /// Close the active tab, the page is left along with the last tab.
+    fn close_active_tab(&mut self) -> Vec<Command> {
+        let tab: GlyphPage = self.tabs.remove(self.state.active_index);
+        self.state.active_index = self
+            .state
+            .active_index
+            .min(self.tabs.len().saturating_sub(1));
+        let mut commands: Vec<Command> = Vec::new();
+        if self.tabs.is_empty() {
+            commands.push(AppCommand(PopPage));
+        }
+        if let Some(recent_glyph) = tab.recent_glyph() {
+            commands.push(AppCommand(RecordRecentGlyph(recent_glyph)));
+        }
+        commands
+    }
+    /// Leaving the glyph of the active tab closes the tab, the others are bubbled up in order.
+    fn process_tab_commands(&mut self, mut commands: Vec<Command>) -> Vec<Command> {
+        let mut processed_commands: Vec<Command> = Vec::new();
+        while let Some(command) = commands.pop() {
+            match command {
+                AppCommand(PopPage) => {
+                    for command in self.close_active_tab().into_iter().rev() {
+                        processed_commands.insert(0, command);
+                    }
+                }
+                _ => {
+                    processed_commands.insert(0, command);
+                }
+            }
+        }
+        processed_commands
+    }
+}
+
+impl From<GlyphTabsPage> for Box<dyn Container> {
+    fn from(page: GlyphTabsPage) -> Self {
+        Box::new(page)
+    }
+}
+
+impl Drawable for GlyphTabsPage {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
+        let [tab_bar_area, page_area]: [Rect; 2] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        /*
+           Tab Bar
+        */
+        let mut line: Line = Line::default().fg(theme.on_surface());
+        if let Some((clipping, _target_index)) = &self.state.copying {
+            line.push_span(Span::from(format!(" Copy \"{}\" to", clipping.name())).bold());
+        }
+        let highlighted_index: usize = match &self.state.copying {
+            Some((_clipping, target_index)) => *target_index,
+            None => self.state.active_index,
+        };
+        let mut tab_areas: Vec<Rect> = Vec::new();
+        for (index, tab) in self.tabs.iter().enumerate() {
+            line.push_span(Span::from(" "));
+            let unsaved_mark: &str = if tab.has_unsaved_entries() { "*" } else { "" };
+            let mut span: Span =
+                Span::from(format!(" {} {}{} ", index + 1, tab.name(), unsaved_mark));
+            if index == self.state.active_index {
+                span = span.bold();
+            } else {
+                span = span.dim();
+            }
+            if index == highlighted_index {
+                span = span.patch_style(theme.highlight());
+            }
+            let x: u16 = tab_bar_area.x + line.width() as u16;
+            tab_areas.push(
+                Rect::new(x, tab_bar_area.y, span.width() as u16, 1).intersection(tab_bar_area),
+            );
+            line.push_span(span);
+        }
+        self.state.tab_areas.replace(tab_areas);
+        line.render(tab_bar_area, frame.buffer_mut());
+        /*
+           Active Glyph
+        */
+        self.active_tab_ref()
+            .render(frame, page_area, draw_flag, theme);
+    }
+}
+
+impl Interactable for GlyphTabsPage {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        if self.state.copying.is_some() {
+            // The tab to copy to is being picked, the glyphs do not react meanwhile.
+            if key.kind == KeyEventKind::Press
+                && let Some(action) = keymap().action(key, &self.actions())
+            {
+                match action {
+                    Action::NextTab | Action::NextItem => self.cycle_copy_target(1),
+                    Action::PreviousTab | Action::PreviousItem => self.cycle_copy_target(-1),
+                    Action::Interact => self.copy()?,
+                    Action::Back => self.state.copying = None,
+                    _ => {}
+                }
+            }
+            return Ok(Vec::new());
+        }
+        if key.kind == KeyEventKind::Press
+            && let Some(action) = keymap().action(key, &TAB_ACTIONS)
+        {
+            match action {
+                Action::NextTab => self.switch_tab(1),
+                Action::PreviousTab => self.switch_tab(-1),
+                Action::MoveTabLeft => self.move_tab(-1),
+                Action::MoveTabRight => self.move_tab(1),
+                Action::CloseTab => {
+                    let commands: Vec<Command> = self.active_tab_mut().close();
+                    return Ok(self.process_tab_commands(commands));
+                }
+                Action::CopyEntryToGlyph => {
+                    let entry: Entry = self
+                        .active_tab_ref()
+                        .active_entry()
+                        .ok_or(Report::msg("No active entry to copy"))?;
+                    self.start_copying(Clipping::Entry(entry))?;
+                }
+                Action::CopySectionToGlyph => {
+                    let section: Section = self.active_tab_ref().active_section().ok_or(
+                        Report::msg("No active section to copy, select one in the edit mode"),
+                    )?;
+                    self.start_copying(Clipping::Section(section))?;
+                }
+                _ => {}
+            }
+            return Ok(Vec::new());
+        }
+        let commands: Vec<Command> = self.active_tab_mut().handle(key, parent_state)?;
+        Ok(self.process_tab_commands(commands))
+    }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let position: Position = Position::new(mouse.column, mouse.row);
+        let clicked_index: Option<usize> = self
+            .state
+            .tab_areas
+            .borrow()
+            .iter()
+            .position(|area| area.contains(position));
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+            && let Some(index) = clicked_index
+        {
+            match &mut self.state.copying {
+                Some((_clipping, target_index)) if index != self.state.active_index => {
+                    *target_index = index;
+                    self.copy()?;
+                }
+                Some(_) => {}
+                None => self.state.active_index = index,
+            }
+            return Ok(Vec::new());
+        }
+        if self.state.copying.is_some() {
+            return Ok(Vec::new());
+        }
+        let commands: Vec<Command> = self.active_tab_mut().handle_mouse(mouse, parent_state)?;
+        Ok(self.process_tab_commands(commands))
+    }
+    fn handle_event(
+        &mut self,
+        event: &AppEvent,
+        mut parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        // Every glyph keeps saving in the background, not only the active one, even when another
+        // one fails to.
+        let mut commands: Vec<Command> = Vec::new();
+        let mut reports: Vec<String> = Vec::new();
+        for tab in self.tabs.iter_mut() {
+            match tab.handle_event(event, parent_state.as_deref_mut()) {
+                Ok(mut tab_commands) => commands.append(&mut tab_commands),
+                Err(report) => reports.push(format!("{}: {}", tab.name(), report)),
+            }
+        }
+        if !reports.is_empty() {
+            return Err(Report::msg(reports.join("\n")));
+        }
+        Ok(commands)
+    }
+    fn actions(&self) -> Vec<Action> {
+        if self.state.copying.is_some() {
+            return vec![
+                Action::NextTab,
+                Action::PreviousTab,
+                Action::NextItem,
+                Action::PreviousItem,
+                Action::Interact,
+                Action::Back,
+            ];
+        }
+        TAB_ACTIONS.to_vec()
+    }
+}
+
+impl Focusable for GlyphTabsPage {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        // The keys of the active glyph are not available while the tab to copy to is picked.
+        if self.state.copying.is_some() {
+            return None;
+        }
+        Some(self.active_tab_ref())
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        if self.state.copying.is_some() {
+            return None;
+        }
+        Some(self.active_tab_mut())
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        if self.state.copying.is_some() {
+            return None;
+        }
+        Some(self.state.active_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::page::glyph_page::AUTOSAVE_TIMER;
+    use crate::config::Config;
+    use crate::services::test_glyph::TempGlyph;
+
+    fn glyph_page(glyph: &TempGlyph) -> GlyphPage {
+        GlyphPage::new(glyph.connect(), &Config::default())
+    }
+
+    #[test]
+    fn test_glyph_tabs() {
+        let glyphs: Vec<TempGlyph> = ["tabs_first", "tabs_second", "tabs_third"]
+            .into_iter()
+            .map(TempGlyph::new)
+            .collect();
+        let mut page: GlyphTabsPage = GlyphTabsPage::new(glyph_page(&glyphs[0]));
+        page.open(glyph_page(&glyphs[1]));
+        page.open(glyph_page(&glyphs[2]));
+        assert_eq!(page.state.active_index, 2);
+        // Each glyph saves on its own schedule.
+        assert_ne!(
+            page.tabs[0].timer_name(AUTOSAVE_TIMER),
+            page.tabs[1].timer_name(AUTOSAVE_TIMER)
+        );
+
+        // Reopening a glyph switches to its tab.
+        page.open(glyph_page(&glyphs[0]));
+        assert_eq!(page.tabs.len(), 3);
+        assert_eq!(page.state.active_index, 0);
+
+        page.move_tab(-1);
+        assert_eq!(page.state.active_index, 0);
+        page.move_tab(1);
+        assert_eq!(
+            page.active_tab_ref().name(),
+            format!("tabs_first_{}", std::process::id())
+        );
+        assert_eq!(
+            page.tabs[0].name(),
+            format!("tabs_second_{}", std::process::id())
+        );
+        page.switch_tab(-1);
+        assert_eq!(page.state.active_index, 0);
+        page.switch_tab(-1);
+        assert_eq!(page.state.active_index, 2);
+
+        // Copy an entry of the third glyph to the second one, the active tab is skipped.
+        {
+            let mut local_entry_state =
+                page.active_tab_mut().state.local_entry_state_mut().unwrap();
+            let eid: i64 = local_entry_state.create_default_entry_db("notes").unwrap();
+            local_entry_state.active_entry_id = Some(eid);
+            local_entry_state
+                .create_section_to_active_entry_db("todo", "content")
+                .unwrap();
+        }
+        let entry: Entry = page.active_tab_ref().active_entry().unwrap();
+        page.start_copying(Clipping::Entry(entry)).unwrap();
+        page.cycle_copy_target(-1);
+        assert_eq!(page.state.copying.as_ref().unwrap().1, 1);
+        page.copy().unwrap();
+        assert_eq!(page.state.active_index, 1);
+        {
+            let local_entry_state = page.active_tab_ref().state.local_entry_state_ref().unwrap();
+            let copied_entry: &Entry = local_entry_state.get_active_entry_ref().unwrap();
+            assert_eq!(copied_entry.entry_name, "notes");
+            assert_eq!(copied_entry.sections[0].1.content, "content");
+        }
+
+        // Closing the last tab leaves the page.
+        assert!(!page
+            .close_active_tab()
+            .iter()
+            .any(|command| matches!(command, AppCommand(PopPage))));
+        assert_eq!(page.state.active_index, 1);
+        page.close_active_tab();
+        assert!(page
+            .close_active_tab()
+            .iter()
+            .any(|command| matches!(command, AppCommand(PopPage))));
+    }
+}
@@ -1,26 +1,33 @@
+use crate::app::keymap::{self, keymap, Action};
 use crate::app::page::glyph_edit_view::GlyphEditView;
-use crate::app::page::glyph_layout_view::{GlyphLayoutEditView, GlyphLayoutView};
+use crate::app::page::glyph_layout_view::{
+    GlyphLayoutEditView, GlyphLayoutOverview, GlyphLayoutView,
+};
 use crate::app::page::glyph_read_view::GlyphReadView;
 use crate::app::Command::GlyphCommand;
 use crate::app::GlyphCommand::SetEntryUnsavedState;
-use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::app::{focus_chain, Command, Container, DrawFlag, Drawable, Focusable, Interactable};
 use crate::block;
+use crate::config::Config;
 use crate::models::layout::LayoutOrientation;
 use crate::services::LocalEntryState;
 use crate::theme::Theme;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
 use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::prelude::Line;
 use ratatui::prelude::Stylize;
 use ratatui::widgets::BorderType;
 use ratatui::widgets::{Block, Paragraph, Widget};
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::cell::{Ref, RefCell, RefMut};
 use std::rc::Rc;
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GlyphMode {
+    #[default]
     Read,
     Layout,
     Edit,
@@ -54,12 +61,18 @@ impl From<GlyphView> for Box<dyn Container> {
 }
 
 impl GlyphView {
-    pub fn new(entry_state: Rc<RefCell<LocalEntryState>>) -> Self {
+    pub fn new(entry_state: Rc<RefCell<LocalEntryState>>, config: &Config) -> Self {
         let shared_focus: Rc<RefCell<bool>> = Rc::new(RefCell::new(false));
         Self {
             containers: [
-                GlyphReadView::new(shared_focus.clone(), entry_state.clone()).into(),
-                GlyphEditView::new(shared_focus.clone(), entry_state.clone()).into(),
+                GlyphReadView::new(
+                    shared_focus.clone(),
+                    entry_state.clone(),
+                    config.print_width,
+                )
+                .into(),
+                GlyphEditView::new(shared_focus.clone(), entry_state.clone(), config.tab_width)
+                    .into(),
                 GlyphLayoutView::new(shared_focus.clone(), entry_state.clone()).into(),
             ],
             state: GlyphViewState {
@@ -69,10 +82,120 @@ impl GlyphView {
             },
         }
     }
+    pub fn set_mode(&mut self, mode: GlyphMode) {
+        self.state.mode = mode;
+        if let GlyphMode::Layout = mode {
+            // Dangerous Cheating here
+            (*(*self.containers[2])
+                .as_any_mut()
+                .downcast_mut::<GlyphLayoutView>()
+                .unwrap()
+                .containers[1])
+                .as_any_mut()
+                .downcast_mut::<GlyphLayoutEditView>()
+                .unwrap()
+                .refresh_layout_edit_panel();
+        }
+    }
+    pub(crate) fn read_view_ref(&self) -> &GlyphReadView {
+        (*self.containers[0])
+            .as_any()
+            .downcast_ref::<GlyphReadView>()
+            .unwrap()
+    }
+    pub(crate) fn read_view_mut(&mut self) -> &mut GlyphReadView {
+        (*self.containers[0])
+            .as_any_mut()
+            .downcast_mut::<GlyphReadView>()
+            .unwrap()
+    }
+    pub(crate) fn edit_view_ref(&self) -> &GlyphEditView {
+        (*self.containers[1])
+            .as_any()
+            .downcast_ref::<GlyphEditView>()
+            .unwrap()
+    }
+    /// Whether a section or the layout is open in the text editor, which keeps its keys to itself.
+    pub fn is_editing_text(&self) -> bool {
+        match self.state.mode {
+            GlyphMode::Read => false,
+            GlyphMode::Edit => self.edit_view_ref().state.is_editing,
+            GlyphMode::Layout => {
+                (*self.containers[2])
+                    .as_any()
+                    .downcast_ref::<GlyphLayoutView>()
+                    .unwrap()
+                    .state
+                    .is_editing_text
+            }
+        }
+    }
+    /// Drop the selected section and layout, they may be gone once the entry is changed underneath.
+    pub fn reset_selection(&mut self) {
+        (*self.containers[1])
+            .as_any_mut()
+            .downcast_mut::<GlyphEditView>()
+            .unwrap()
+            .state
+            .active_sid
+            .replace(None);
+        let layout_view: &mut GlyphLayoutView = (*self.containers[2])
+            .as_any_mut()
+            .downcast_mut::<GlyphLayoutView>()
+            .unwrap();
+        layout_view.state.selected_coordinate.borrow_mut().clear();
+        let overview: &mut GlyphLayoutOverview = (*layout_view.containers[0])
+            .as_any_mut()
+            .downcast_mut::<GlyphLayoutOverview>()
+            .unwrap();
+        overview.state.hovered_index = None;
+        overview.state.marked_layout = None;
+        if self.state.mode == GlyphMode::Layout
+            && self
+                .state
+                .local_entry_state_ref()
+                .unwrap()
+                .active_entry_id
+                .is_some()
+        {
+            // Setting the layout mode again refreshes the edit panel.
+            self.set_mode(GlyphMode::Layout);
+        }
+    }
+    /// Apply the unsaved state of entries, the other commands are bubbled up in order.
+    fn process_glyph_commands(&mut self, mut commands: Vec<Command>) -> Result<Vec<Command>> {
+        let mut processed_commands: Vec<Command> = Vec::new();
+        while let Some(command) = commands.pop() {
+            match command {
+                GlyphCommand(page_command) => match page_command {
+                    SetEntryUnsavedState(eid, is_changed) => {
+                        self.state
+                            .local_entry_state_mut()
+                            .unwrap()
+                            .set_entry_unsaved(eid, is_changed)?;
+                    }
+                    _ => {
+                        processed_commands.insert(0, GlyphCommand(page_command));
+                    }
+                },
+                _ => {
+                    processed_commands.insert(0, command);
+                }
+            }
+        }
+        Ok(processed_commands)
+    }
+    fn current_view_ref(&self) -> &dyn Container {
+        match self.state.mode {
+            GlyphMode::Read => self.containers[0].as_ref(),
+            GlyphMode::Edit => self.containers[1].as_ref(),
+            GlyphMode::Layout => self.containers[2].as_ref(),
+        }
+    }
 }
 
 impl Drawable for GlyphView {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -154,42 +277,23 @@ impl Interactable for GlyphView {
                Switch Mode Key
             */
             if key.kind == KeyEventKind::Press
-                && let KeyCode::Char(c) = key.code
+                && let Some(action) =
+                    keymap().action(key, &[Action::CycleMode, Action::SaveEntry])
             {
-                match c {
-                    '\\' => {
-                        match self.state.mode {
-                            GlyphMode::Read => {
-                                self.state.mode = GlyphMode::Edit;
-                            }
-                            GlyphMode::Edit => {
-                                self.state.mode = GlyphMode::Layout;
-
-                                // Dangerous Cheating here
-                                (*(*self.containers[2])
-                                    .as_any_mut()
-                                    .downcast_mut::<GlyphLayoutView>()
-                                    .unwrap()
-                                    .containers[1])
-                                    .as_any_mut()
-                                    .downcast_mut::<GlyphLayoutEditView>()
-                                    .unwrap()
-                                    .refresh_layout_edit_panel();
-                            }
-                            GlyphMode::Layout => {
-                                self.state.mode = GlyphMode::Read;
-                            }
-                        }
+                match action {
+                    Action::CycleMode => {
+                        self.set_mode(match self.state.mode {
+                            GlyphMode::Read => GlyphMode::Edit,
+                            GlyphMode::Edit => GlyphMode::Layout,
+                            GlyphMode::Layout => GlyphMode::Read,
+                        });
                     }
-                    's' => {
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            let mut state: RefMut<LocalEntryState> =
-                                self.state.local_entry_state_mut().unwrap();
-                            let eid = state.active_entry_id.unwrap();
-                            state.updated_entries.remove(&eid);
-
-                            state.save_entry_db(&eid)?;
-                        }
+                    Action::SaveEntry => {
+                        let mut state: RefMut<LocalEntryState> =
+                            self.state.local_entry_state_mut().unwrap();
+                        let eid = state.active_entry_id.unwrap();
+                        state.save_entry_db(&eid)?;
+                        state.set_entry_unsaved(eid, false)?;
                     }
                     _ => {}
                 }
@@ -198,89 +302,61 @@ impl Interactable for GlyphView {
                 GlyphMode::Read => self.containers[0].as_mut().handle(key, parent_state),
                 GlyphMode::Edit => {
                     let result = self.containers[1].as_mut().handle(key, parent_state);
-                    if result.is_err() {
-                        result
-                    } else {
-                        let mut processed_commands: Vec<Command> = Vec::new();
-                        let mut commands = result?;
-                        while let Some(command) = commands.pop() {
-                            match command {
-                                GlyphCommand(page_command) => match page_command {
-                                    SetEntryUnsavedState(eid, is_changed) => {
-                                        let mut state = self.state.local_entry_state_mut().unwrap();
-                                        if is_changed {
-                                            state.updated_entries.insert(eid);
-                                        } else {
-                                            state.updated_entries.remove(&eid);
-                                        }
-                                    }
-                                    _ => {
-                                        processed_commands.insert(0, GlyphCommand(page_command));
-                                    }
-                                },
-                                _ => {
-                                    processed_commands.insert(0, command);
-                                }
-                            }
-                        }
-                        Ok(processed_commands)
-                    }
+                    self.process_glyph_commands(result?)
                 }
                 GlyphMode::Layout => {
                     let result = self.containers[2].as_mut().handle(key, parent_state);
-                    if result.is_err() {
-                        result
-                    } else {
-                        let mut processed_commands: Vec<Command> = Vec::new();
-                        let mut commands = result?;
-                        while let Some(command) = commands.pop() {
-                            match command {
-                                GlyphCommand(page_command) => match page_command {
-                                    SetEntryUnsavedState(eid, is_changed) => {
-                                        let mut state = self.state.local_entry_state_mut().unwrap();
-                                        if is_changed {
-                                            state.updated_entries.insert(eid);
-                                        } else {
-                                            state.updated_entries.remove(&eid);
-                                        }
-                                    }
-                                    _ => {
-                                        processed_commands.insert(0, GlyphCommand(page_command));
-                                    }
-                                },
-                                _ => {
-                                    processed_commands.insert(0, command);
-                                }
-                            }
-                        }
-                        Ok(processed_commands)
-                    }
+                    self.process_glyph_commands(result?)
                 }
             }
         }
     }
-    fn keymap(&self) -> Vec<(&str, &str)> {
-        match self.state.mode {
-            GlyphMode::Read => {
-                [("up/down/pageup/pagedown", "Scroll"), ("P", "Print to txt")].into()
-            }
-            GlyphMode::Edit => [
-                ("pageup/pagedown", "Scroll"),
-                ("e", "Edit Active Section"),
-                ("R", "Rename Active Section"),
-                ("A", "Create Default Section"),
-                ("+/-", "Change Section Position"),
-            ]
-            .into(),
-            GlyphMode::Layout => [
-                ("up/down/pageup/pagedown", "Scroll"),
-                ("e", "Edit (With Active Layout)"),
-                ("D", "Clone Layout to other Entry"),
-                ("t", "Change Layout Orientation"),
-                ("+/-", "Change Targeted Section Position"),
-            ]
-            .into(),
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        if self
+            .state
+            .local_entry_state_ref()
+            .unwrap()
+            .active_entry_id
+            .is_none()
+        {
+            return Ok(Vec::new());
+        }
+        let index: usize = match self.state.mode {
+            GlyphMode::Read => 0,
+            GlyphMode::Edit => 1,
+            GlyphMode::Layout => 2,
+        };
+        let result = self.containers[index].handle_mouse(mouse, parent_state);
+        self.process_glyph_commands(result?)
+    }
+    fn actions(&self) -> Vec<Action> {
+        let mut actions: Vec<Action> = match self.state.mode {
+            GlyphMode::Read => self.containers[0].actions(),
+            GlyphMode::Edit => self.containers[1].actions(),
+            GlyphMode::Layout => self.containers[2].actions(),
+        };
+        actions.extend([Action::CycleMode, Action::SaveEntry]);
+        actions
+    }
+    // The current view is not exposed as a focused child, hence going through it here.
+    fn help(&self) -> Vec<(String, Vec<(&str, &str)>)> {
+        let mut groups: Vec<(String, Vec<(&str, &str)>)> = vec![(
+            String::from("Glyph View"),
+            keymap::describe(&[Action::CycleMode, Action::SaveEntry]),
+        )];
+        for container in focus_chain(self.current_view_ref()) {
+            groups.extend(container.help());
         }
+        groups
+    }
+    fn is_typing(&self) -> bool {
+        focus_chain(self.current_view_ref())
+            .last()
+            .is_some_and(|container| container.is_typing())
     }
 }
 impl Focusable for GlyphView {
@@ -1,2 +1,7 @@
+pub mod command_palette;
 pub mod confirm_popup;
+pub mod help_popup;
+pub mod log_popup;
 pub mod message_popup;
+pub mod quit_popup;
+pub mod theme_popup;
@@ -0,0 +1,179 @@
+use crate::app::keymap::{keymap, Action};
+use crate::app::widget::text_field::TextField;
+use crate::app::AppCommand::{DispatchAction, PopPopup};
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::block;
+use crate::theme::Theme;
+use crate::utils::{cycle_offset, fuzzy_score};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::widgets::{Block, BorderType, Clear, Widget};
+use ratatui::Frame;
+use std::any::Any;
+
+pub struct CommandPaletteState {
+    pub is_focused: bool,
+    pub actions: Vec<Action>,
+    /// Indexes of the actions matching the query, best match first.
+    pub matched_indexes: Vec<usize>,
+    pub hovered_index: usize,
+}
+/// Fuzzy search through the actions available in the current context, and execute one of them.
+pub struct CommandPalette {
+    pub state: CommandPaletteState,
+    text_field: TextField,
+}
+impl CommandPalette {
+    pub fn new(actions: Vec<Action>) -> Self {
+        let mut text_field = TextField::new("", "", Box::new(|_value| true));
+        text_field.set_focus(true);
+        let mut palette = Self {
+            state: CommandPaletteState {
+                is_focused: true,
+                actions,
+                matched_indexes: Vec::new(),
+                hovered_index: 0,
+            },
+            text_field,
+        };
+        palette.filter();
+        palette
+    }
+    pub fn get_hovered_action(&self) -> Option<Action> {
+        self.state
+            .matched_indexes
+            .get(self.state.hovered_index)
+            .map(|index| self.state.actions[*index])
+    }
+    pub(crate) fn cycle_hover(&mut self, offset: i16) {
+        let max: u16 = self.state.matched_indexes.len() as u16;
+        self.state.hovered_index =
+            cycle_offset(self.state.hovered_index as u16, offset, max) as usize;
+    }
+    /// Match the actions against the query, ordered by their score.
+    fn filter(&mut self) {
+        let query: String = self.text_field.state.chars.iter().collect();
+        let mut scores: Vec<(usize, i32)> = self
+            .state
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, action)| {
+                fuzzy_score(query.as_str(), action.label()).map(|score| (index, score))
+            })
+            .collect();
+        // Stable, so that equally scored actions keep the order of the context.
+        scores.sort_by_key(|(_index, score)| -score);
+        self.state.matched_indexes = scores.into_iter().map(|(index, _)| index).collect();
+        self.state.hovered_index = 0;
+    }
+}
+impl From<CommandPalette> for Box<dyn Container> {
+    fn from(popup: CommandPalette) -> Self {
+        Box::new(popup)
+    }
+}
+impl Drawable for CommandPalette {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        let popup_frame: Block =
+            block!(" Command Palette ", DrawFlag::FOCUSED, theme).bg(theme.surface_low());
+        let popup_area: Rect = area.centered(Constraint::Length(64), Constraint::Percentage(50));
+        let popup_inner_area: Rect = popup_frame.inner(popup_area);
+        let layout: Layout = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]);
+        let [text_field_area, list_area] = layout.areas(popup_inner_area);
+        Clear.render(popup_area, frame.buffer_mut());
+        popup_frame.render(popup_area, frame.buffer_mut());
+
+        self.text_field
+            .render(frame, text_field_area, DrawFlag::DEFAULT, theme);
+
+        // List Area, scrolled to keep the hovered action visible.
+        let rows = list_area.rows().collect::<Vec<Rect>>();
+        let scroll_offset: usize = (self.state.hovered_index + 1).saturating_sub(rows.len());
+        for (row, (index, action_index)) in rows.iter().zip(
+            self.state
+                .matched_indexes
+                .iter()
+                .enumerate()
+                .skip(scroll_offset),
+        ) {
+            let action: Action = self.state.actions[*action_index];
+            let is_hovered: bool = index == self.state.hovered_index;
+            let prefix = if is_hovered { "> " } else { "  " };
+            let mut line: Line = Line::from([prefix, action.label()].concat()).dim();
+            let mut keys: Line = Line::from(keymap().describe(action)).right_aligned().dim();
+            if is_hovered {
+                line = line.bold().not_dim();
+                keys = keys.not_dim();
+            }
+            line.render(*row, frame.buffer_mut());
+            keys.render(*row, frame.buffer_mut());
+        }
+    }
+}
+impl Interactable for CommandPalette {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                match key.code {
+                    KeyCode::Esc => return Ok(vec![AppCommand(PopPopup)]),
+                    KeyCode::Enter => {
+                        // Commands are processed from the last one, close the palette first.
+                        return Ok(match self.get_hovered_action() {
+                            Some(action) => {
+                                vec![AppCommand(DispatchAction(action)), AppCommand(PopPopup)]
+                            }
+                            None => vec![AppCommand(PopPopup)],
+                        });
+                    }
+                    KeyCode::Down | KeyCode::Tab => self.cycle_hover(1),
+                    KeyCode::Up | KeyCode::BackTab => self.cycle_hover(-1),
+                    KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.cycle_hover(1)
+                    }
+                    _ => {
+                        self.text_field.handle(key, None)?;
+                        self.filter();
+                    }
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn keymap(&self) -> Vec<(&str, &str)> {
+        [
+            ("up/down/tab/backtab/c-n", "Navigate"),
+            ("Enter", "Execute"),
+            ("Esc", "Close"),
+        ]
+        .into()
+    }
+    fn is_typing(&self) -> bool {
+        true
+    }
+}
+impl Focusable for CommandPalette {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
@@ -42,7 +42,7 @@ impl From<ConfirmPopup> for Box<dyn Container> {
     }
 }
 impl Drawable for ConfirmPopup {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
         let area: Rect = area.centered(Constraint::Length(42), Constraint::Length(6));
         let paragraph_message: Paragraph = Paragraph::new(self.message.clone())
             .wrap(Wrap { trim: true })
@@ -0,0 +1,153 @@
+use crate::app::keymap::{keymap, Action};
+use crate::app::AppCommand::PopPopup;
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::block;
+use crate::theme::Theme;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Rect, Size};
+use ratatui::prelude::{Line, Span, Stylize};
+use ratatui::widgets::{Block, BorderType, Clear, StatefulWidget, Widget};
+use ratatui::Frame;
+use std::any::Any;
+use std::cell::RefCell;
+use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
+
+pub struct HelpPopupState {
+    pub is_focused: bool,
+    /// (title, [(keys, description)])
+    pub groups: Vec<(String, Vec<(String, String)>)>,
+    pub scroll_state: RefCell<ScrollViewState>,
+}
+/// List every key binding available from where it is opened, grouped by the container handling them.
+pub struct HelpPopup {
+    pub state: HelpPopupState,
+}
+impl HelpPopup {
+    pub fn new(groups: Vec<(String, Vec<(String, String)>)>) -> Self {
+        Self {
+            state: HelpPopupState {
+                is_focused: true,
+                groups,
+                scroll_state: RefCell::new(ScrollViewState::default()),
+            },
+        }
+    }
+    /// Group titles followed by their bindings, with the keys padded into a column.
+    fn lines(&self) -> Vec<Line<'_>> {
+        let key_width: usize = self
+            .state
+            .groups
+            .iter()
+            .flat_map(|(_title, bindings)| bindings.iter())
+            .map(|(keys, _description)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines: Vec<Line> = Vec::new();
+        for (title, bindings) in &self.state.groups {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(title.as_str()).bold().underlined());
+            for (keys, description) in bindings {
+                lines.push(Line::from(vec![
+                    Span::from(format!("  {:<width$}  ", keys, width = key_width)).dim(),
+                    Span::from(description.as_str()),
+                ]));
+            }
+        }
+        lines
+    }
+}
+impl From<HelpPopup> for Box<dyn Container> {
+    fn from(popup: HelpPopup) -> Self {
+        Box::new(popup)
+    }
+}
+impl Drawable for HelpPopup {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        let popup_frame: Block = block!(" Help ", DrawFlag::FOCUSED, theme).bg(theme.surface_low());
+        let popup_area: Rect = area.centered(Constraint::Length(72), Constraint::Percentage(70));
+        let popup_inner_area: Rect = popup_frame.inner(popup_area);
+        Clear.render(popup_area, frame.buffer_mut());
+        popup_frame.render(popup_area, frame.buffer_mut());
+
+        // Leave a column to the scrollbar.
+        let lines: Vec<Line> = self.lines();
+        let mut scroll_view: ScrollView = ScrollView::new(Size {
+            width: popup_inner_area.width.saturating_sub(1),
+            height: lines.len() as u16,
+        })
+        .horizontal_scrollbar_visibility(ScrollbarVisibility::Never);
+        for (index, line) in lines.into_iter().enumerate() {
+            let mut row: Rect = scroll_view.area();
+            row.y = index as u16;
+            row.height = 1;
+            line.render(row, scroll_view.buf_mut());
+        }
+        scroll_view.render(
+            popup_inner_area,
+            frame.buffer_mut(),
+            &mut *self.state.scroll_state.borrow_mut(),
+        );
+    }
+}
+impl Interactable for HelpPopup {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                if keymap().matches(key, Action::Back) {
+                    return Ok(vec![AppCommand(PopPopup)]);
+                }
+                if keymap().matches(key, Action::ScrollPageUp) {
+                    self.state.scroll_state.borrow_mut().scroll_page_up();
+                }
+                if keymap().matches(key, Action::ScrollPageDown) {
+                    self.state.scroll_state.borrow_mut().scroll_page_down();
+                }
+                if keymap().matches(key, Action::ScrollUp)
+                    || keymap().matches(key, Action::PreviousItem)
+                {
+                    self.state.scroll_state.borrow_mut().scroll_up();
+                } else if keymap().matches(key, Action::ScrollDown)
+                    || keymap().matches(key, Action::NextItem)
+                {
+                    self.state.scroll_state.borrow_mut().scroll_down();
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::ScrollUp,
+            Action::ScrollDown,
+            Action::ScrollPageUp,
+            Action::ScrollPageDown,
+            Action::Back,
+        ]
+    }
+}
+impl Focusable for HelpPopup {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
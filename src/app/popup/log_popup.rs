@@ -0,0 +1,144 @@
+use crate::app::keymap::{keymap, Action};
+use crate::app::AppCommand::PopPopup;
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::block;
+use crate::logger;
+use crate::theme::Theme;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Rect, Size};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::widgets::{Block, BorderType, Clear, StatefulWidget, Widget};
+use ratatui::Frame;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
+
+pub struct LogPopupState {
+    pub is_focused: bool,
+    /// Whether the view sticks to the latest line, it stops once scrolled up.
+    pub is_following: Cell<bool>,
+    pub scroll_state: RefCell<ScrollViewState>,
+}
+/// Tail the lines logged during the session.
+pub struct LogPopup {
+    pub state: LogPopupState,
+}
+impl LogPopup {
+    pub fn new() -> Self {
+        Self {
+            state: LogPopupState {
+                is_focused: true,
+                is_following: Cell::new(true),
+                scroll_state: RefCell::new(ScrollViewState::default()),
+            },
+        }
+    }
+}
+impl From<LogPopup> for Box<dyn Container> {
+    fn from(popup: LogPopup) -> Self {
+        Box::new(popup)
+    }
+}
+impl Drawable for LogPopup {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        let popup_frame: Block = block!(" Log ", DrawFlag::FOCUSED, theme).bg(theme.surface_low());
+        let popup_area: Rect =
+            area.centered(Constraint::Percentage(90), Constraint::Percentage(80));
+        let popup_inner_area: Rect = popup_frame.inner(popup_area);
+        Clear.render(popup_area, frame.buffer_mut());
+        popup_frame.render(popup_area, frame.buffer_mut());
+
+        let lines: Vec<String> = logger::recent_lines();
+        let width: u16 = lines
+            .iter()
+            .map(|line| line.chars().count() as u16)
+            .max()
+            .unwrap_or(0)
+            .max(popup_inner_area.width.saturating_sub(1));
+        let mut scroll_view: ScrollView = ScrollView::new(Size {
+            width,
+            height: lines.len() as u16,
+        });
+        if lines.is_empty() {
+            scroll_view = scroll_view.vertical_scrollbar_visibility(ScrollbarVisibility::Never);
+        }
+        for (index, line) in lines.iter().enumerate() {
+            let mut row: Rect = scroll_view.area();
+            row.y = index as u16;
+            row.height = 1;
+            Line::from(line.as_str()).render(row, scroll_view.buf_mut());
+        }
+        let mut scroll_state = self.state.scroll_state.borrow_mut();
+        if self.state.is_following.get() {
+            scroll_state.scroll_to_bottom();
+        }
+        scroll_view.render(popup_inner_area, frame.buffer_mut(), &mut scroll_state);
+    }
+}
+impl Interactable for LogPopup {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                if keymap().matches(key, Action::Back) {
+                    return Ok(vec![AppCommand(PopPopup)]);
+                }
+                let mut scroll_state = self.state.scroll_state.borrow_mut();
+                if keymap().matches(key, Action::Interact) {
+                    self.state.is_following.set(true);
+                }
+                if keymap().matches(key, Action::ScrollPageUp) {
+                    self.state.is_following.set(false);
+                    scroll_state.scroll_page_up();
+                }
+                if keymap().matches(key, Action::ScrollPageDown) {
+                    scroll_state.scroll_page_down();
+                }
+                if keymap().matches(key, Action::ScrollUp)
+                    || keymap().matches(key, Action::PreviousItem)
+                {
+                    self.state.is_following.set(false);
+                    scroll_state.scroll_up();
+                } else if keymap().matches(key, Action::ScrollDown)
+                    || keymap().matches(key, Action::NextItem)
+                {
+                    scroll_state.scroll_down();
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn actions(&self) -> Vec<Action> {
+        vec![
+            Action::ScrollUp,
+            Action::ScrollDown,
+            Action::ScrollPageUp,
+            Action::ScrollPageDown,
+            Action::Interact,
+            Action::Back,
+        ]
+    }
+}
+impl Focusable for LogPopup {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
@@ -41,7 +41,7 @@ impl From<MessagePopup> for Box<dyn Container> {
     }
 }
 impl Drawable for MessagePopup {
-    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
         let ratio: f64 = 1.0 / 2.0;
         let width: u16 =
             (((self.message.len().isqrt() as f64 + 1f64) / ratio) as u16).clamp(42, area.width) + 6;
@@ -0,0 +1,135 @@
+use crate::app::AppCommand::{DiscardUnsavedEntries, PopPopup, Quit, SaveUnsavedEntries};
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::theme::Theme;
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::{Line, Span};
+use ratatui::style::Stylize;
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget, Wrap};
+use ratatui::Frame;
+use std::any::Any;
+
+const CHOICES: [&str; 3] = ["Cancel", "Discard", "Save"];
+
+/// Guard the exit while entries are unsaved, offering to save or discard them first.
+pub struct QuitPopup {
+    pub is_focused: bool,
+    pub focus_index: usize,
+    pub unsaved_entry_names: Vec<String>,
+}
+impl QuitPopup {
+    pub fn new(unsaved_entry_names: Vec<String>) -> Self {
+        Self {
+            is_focused: true,
+            focus_index: 0,
+            unsaved_entry_names,
+        }
+    }
+}
+impl From<QuitPopup> for Box<dyn Container> {
+    fn from(container: QuitPopup) -> Self {
+        Box::new(container)
+    }
+}
+impl Drawable for QuitPopup {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        let mut lines: Vec<Line> = vec![Line::from("Unsaved entries:").bold()];
+        for name in &self.unsaved_entry_names {
+            lines.push(Line::from(format!("- {}", name)));
+        }
+        let choices: Vec<Span> = CHOICES
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| {
+                if index == self.focus_index {
+                    Span::from(format!("[{}]", choice)).bold()
+                } else {
+                    Span::from(format!(" {} ", choice))
+                }
+            })
+            .collect();
+        let area: Rect = area.centered(
+            Constraint::Length(42),
+            Constraint::Length(lines.len() as u16 + 4),
+        );
+        let paragraph: Paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::bordered()
+                .padding(Padding::horizontal(1))
+                .title_top(Line::from("Quit").centered())
+                .title_bottom(Line::from(choices).centered())
+                .border_type(BorderType::Double)
+                .border_style(theme.on_surface())
+                .style(theme.on_surface())
+                .bg(theme.surface_low()),
+        );
+
+        Clear.render(area, frame.buffer_mut());
+        paragraph.render(area, frame.buffer_mut());
+    }
+}
+impl Interactable for QuitPopup {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                if let KeyCode::Tab | KeyCode::Right = key.code {
+                    self.focus_index = (self.focus_index + 1) % CHOICES.len();
+                }
+                if let KeyCode::BackTab | KeyCode::Left = key.code {
+                    self.focus_index = (self.focus_index + CHOICES.len() - 1) % CHOICES.len();
+                }
+                if let KeyCode::Esc = key.code {
+                    return Ok(vec![AppCommand(PopPopup)]);
+                }
+                if let KeyCode::Enter = key.code {
+                    // Commands are executed from the last one.
+                    return Ok(match self.focus_index {
+                        1 => vec![
+                            AppCommand(Quit),
+                            AppCommand(DiscardUnsavedEntries),
+                            AppCommand(PopPopup),
+                        ],
+                        2 => vec![
+                            AppCommand(Quit),
+                            AppCommand(SaveUnsavedEntries),
+                            AppCommand(PopPopup),
+                        ],
+                        _ => vec![AppCommand(PopPopup)],
+                    });
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn keymap(&self) -> Vec<(&str, &str)> {
+        [
+            ("left/right/tab/backtab", "Navigate"),
+            ("Enter", "Interact"),
+            ("Esc", "Cancel"),
+        ]
+        .into()
+    }
+}
+impl Focusable for QuitPopup {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
@@ -0,0 +1,136 @@
+use crate::app::AppCommand::PopPopup;
+use crate::app::AppState;
+use crate::app::Command::AppCommand;
+use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::block;
+use crate::theme::Theme;
+use crate::utils::cycle_offset;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::widgets::{Block, BorderType, Clear, Widget};
+use ratatui::Frame;
+use std::any::Any;
+
+pub struct ThemePopupState {
+    pub is_focused: bool,
+    pub themes: Vec<Theme>,
+    pub hovered_index: usize,
+    /// Theme in use when the popup was opened, restored on cancel.
+    pub initial_theme: Theme,
+}
+/// Switch between the available themes, previewing the hovered one.
+pub struct ThemePopup {
+    pub state: ThemePopupState,
+}
+impl ThemePopup {
+    pub fn new(themes: Vec<Theme>, initial_theme: Theme) -> Self {
+        let hovered_index: usize = themes
+            .iter()
+            .position(|theme| theme.name == initial_theme.name)
+            .unwrap_or(0);
+        Self {
+            state: ThemePopupState {
+                is_focused: true,
+                themes,
+                hovered_index,
+                initial_theme,
+            },
+        }
+    }
+    pub(crate) fn cycle_hover(&mut self, offset: i16) {
+        let max: u16 = self.state.themes.len() as u16;
+        self.state.hovered_index =
+            cycle_offset(self.state.hovered_index as u16, offset, max) as usize;
+    }
+}
+impl From<ThemePopup> for Box<dyn Container> {
+    fn from(popup: ThemePopup) -> Self {
+        Box::new(popup)
+    }
+}
+impl Drawable for ThemePopup {
+    fn render(&self, frame: &mut Frame, area: Rect, _draw_flag: DrawFlag, theme: &Theme) {
+        let popup_frame: Block =
+            block!(" Theme ", DrawFlag::FOCUSED, theme).bg(theme.surface_low());
+        let popup_area: Rect = area.centered(
+            Constraint::Length(40),
+            Constraint::Length(self.state.themes.len() as u16 + 2),
+        );
+        let popup_inner_area: Rect = popup_frame.inner(popup_area);
+        Clear.render(popup_area, frame.buffer_mut());
+        popup_frame.render(popup_area, frame.buffer_mut());
+
+        for (row, (index, theme)) in popup_inner_area
+            .rows()
+            .zip(self.state.themes.iter().enumerate())
+        {
+            let is_hovered: bool = index == self.state.hovered_index;
+            let prefix = if is_hovered { "> " } else { "  " };
+            let mut line: Line = Line::from([prefix, theme.name.as_str()].concat()).dim();
+            if is_hovered {
+                line = line.bold().not_dim();
+            }
+            line.render(row, frame.buffer_mut());
+        }
+    }
+}
+impl Interactable for ThemePopup {
+    fn handle(
+        &mut self,
+        key: &KeyEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match key.kind {
+            KeyEventKind::Press => {
+                let app_state = parent_state.and_then(|state| state.downcast_mut::<AppState>());
+                match key.code {
+                    KeyCode::Esc => {
+                        if let Some(app_state) = app_state {
+                            app_state.theme = self.state.initial_theme.clone();
+                        }
+                        return Ok(vec![AppCommand(PopPopup)]);
+                    }
+                    KeyCode::Enter => return Ok(vec![AppCommand(PopPopup)]),
+                    KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => self.cycle_hover(1),
+                    KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => self.cycle_hover(-1),
+                    _ => return Ok(Vec::new()),
+                }
+                // Preview the hovered theme.
+                if let Some(app_state) = app_state
+                    && let Some(theme) = self.state.themes.get(self.state.hovered_index)
+                {
+                    app_state.theme = theme.clone();
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    fn keymap(&self) -> Vec<(&str, &str)> {
+        [
+            ("up/down/j/k/tab/backtab", "Preview"),
+            ("Enter", "Apply"),
+            ("Esc", "Cancel"),
+        ]
+        .into()
+    }
+}
+impl Focusable for ThemePopup {
+    fn is_focused(&self) -> bool {
+        self.state.is_focused
+    }
+    fn set_focus(&mut self, value: bool) {
+        self.state.is_focused = value;
+    }
+    fn focused_child_ref(&self) -> Option<&dyn Container> {
+        None
+    }
+    fn focused_child_mut(&mut self) -> Option<&mut dyn Container> {
+        None
+    }
+    fn focused_child_index(&self) -> Option<usize> {
+        None
+    }
+}
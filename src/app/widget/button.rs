@@ -36,7 +36,7 @@ impl From<Button> for Box<dyn Component> {
 }
 
 impl Drawable for Button {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &Theme) {
         match draw_flag {
             DrawFlag::HIGHLIGHTING => {
                 Line::from(["> ", self.label.as_str(), "  "].concat())
@@ -1,19 +1,22 @@
 use crate::app::{
     is_cycle_backward_hover_key, is_cycle_forward_hover_key, Command, Container, DrawFlag, Drawable, Focusable,
-    Interactable,
+    Interactable, MOUSE_SCROLL_LINES,
 };
 use crate::block;
 use crate::theme::Theme;
 use crate::utils::{cycle_offset, get_dir_names, get_file_names};
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use ratatui::layout::{Offset, Rect};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Offset, Position, Rect};
 use ratatui::prelude::Stylize;
 use ratatui::prelude::{Line, Span};
 use ratatui::widgets::Block;
 use ratatui::widgets::{BorderType, Widget};
 use ratatui::Frame;
 use std::any::Any;
+use std::cell::Cell;
 use std::path::PathBuf;
 
 pub struct DirectoryListState {
@@ -27,6 +30,8 @@ pub struct DirectoryListState {
     pub offset: usize,
     pub show_files: bool,
     pub select_dir: bool,
+    /// Where the entries were last drawn, to resolve mouse clicks.
+    pub list_area: Cell<Rect>,
 }
 pub struct DirectoryList {
     pub state: DirectoryListState,
@@ -47,6 +52,7 @@ impl DirectoryList {
                 offset: 0,
                 show_files,
                 select_dir,
+                list_area: Cell::new(Rect::default()),
             },
             on_exit: None,
         }
@@ -58,6 +64,11 @@ impl DirectoryList {
         self.on_exit = Some(on_exit);
         self
     }
+    /// Start listing from another directory than the working one.
+    pub fn directory(mut self, path: PathBuf) -> Self {
+        self.state.current_path = path;
+        self
+    }
 
     /// Retrieve the num of files in the current directory.
     pub fn get_num_files(&self) -> usize {
@@ -123,7 +134,7 @@ impl From<DirectoryList> for Box<dyn Container> {
     }
 }
 impl Drawable for DirectoryList {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         /*
            Container Frame
         */
@@ -143,6 +154,7 @@ impl Drawable for DirectoryList {
         */
         let inner_area: Rect = widget_frame.inner(area);
         widget_frame.render(area, frame.buffer_mut());
+        self.state.list_area.set(inner_area);
         let list_items: Vec<Line> = self.get_entries()
             .iter()
             .enumerate()
@@ -166,7 +178,7 @@ impl Drawable for DirectoryList {
                     line = line.bold();
                 }
                 if is_hovered {
-                    line = line.bg(theme.surface_low_highlight());
+                    line = line.patch_style(theme.highlight());
                 }
                 line
             })
@@ -287,6 +299,43 @@ impl Interactable for DirectoryList {
             }
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let list_area: Rect = self.state.list_area.get();
+        if !list_area.contains(Position::new(mouse.column, mouse.row)) {
+            return Ok(Vec::new());
+        }
+        let num_entries: usize = self.get_num_entries();
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                self.state.offset = self
+                    .state
+                    .offset
+                    .saturating_sub(MOUSE_SCROLL_LINES as usize);
+            }
+            MouseEventKind::ScrollDown => {
+                self.state.offset = (self.state.offset + MOUSE_SCROLL_LINES as usize)
+                    .min(num_entries.saturating_sub(1));
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index: usize =
+                    self.state.offset + (mouse.row - list_area.y) as usize / self.state.line_height;
+                if index >= num_entries {
+                    return Ok(Vec::new());
+                }
+                // A second click on the hovered entry behaves like Enter.
+                if self.state.hovered_index == Some(index) {
+                    return self.handle(&KeyEvent::from(KeyCode::Enter), parent_state);
+                }
+                self.state.hovered_index = Some(index);
+            }
+            _ => {}
+        }
+        Ok(Vec::new())
+    }
     fn keymap(&self) -> Vec<(&str, &str)> {
         [
             ("Enter", "Change Directory"),
@@ -42,7 +42,7 @@ impl From<LineButton> for Box<dyn Component> {
 }
 
 impl Drawable for LineButton {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &Theme) {
         let text = self.label.clone().to_string();
         match draw_flag {
             DrawFlag::HIGHLIGHTING => {
@@ -70,7 +70,7 @@
 // }
 //
 // impl Drawable for List {
-//     fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+//     fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
 //         let border: Block = block!("",draw_flag,theme);
 //         let inner_area: Rect = border.inner(area);
 //
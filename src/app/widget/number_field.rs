@@ -5,7 +5,7 @@ use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::layout::{Constraint, Offset, Position, Rect};
 use ratatui::prelude::Line;
-use ratatui::style::Stylize;
+use ratatui::style::{Color, Modifier, Stylize};
 use ratatui::widgets::BorderType;
 use ratatui::widgets::{Block, Widget};
 use ratatui::Frame;
@@ -77,13 +77,15 @@ impl From<NumberField> for Box<dyn Container> {
 }
 
 impl Drawable for NumberField {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let text_field_area = area.centered(Constraint::Min(18), Constraint::Min(3));
         let text = self.state.chars.iter().collect::<String>();
         let text_line: Line = Line::from(text);
         let mut number_field_block: Block = block!(self.state.label.as_str(), draw_flag, theme);
         if !self.state.is_valid {
-            number_field_block = number_field_block.red().title_bottom("Invalid Input");
+            number_field_block = theme
+                .accent(number_field_block, Color::Red, Modifier::UNDERLINED)
+                .title_bottom("Invalid Input");
         }
         let text_line_area: Rect = number_field_block.inner(text_field_area);
         if self.is_focused() {
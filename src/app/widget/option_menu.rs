@@ -48,7 +48,7 @@ impl From<OptionMenu> for Box<dyn Component> {
     }
 }
 impl Drawable for OptionMenu {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, _theme: &Theme) {
         let current_index: usize = self.state.current_index as usize;
         let current_text: String = self.state.options.get(current_index).unwrap().0.clone();
         match draw_flag {
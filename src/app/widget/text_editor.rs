@@ -1,15 +1,25 @@
-use crate::app::{Command, Container, DrawFlag, Drawable, Focusable, Interactable};
+use crate::app::{
+    Command, Container, DrawFlag, Drawable, Focusable, Interactable, MOUSE_SCROLL_LINES,
+};
 use crate::block;
 use crate::theme::Theme;
-use color_eyre::eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use color_eyre::eyre::{Report, Result};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::layout::{Offset, Position, Rect, Rows};
 use ratatui::prelude::{Line, Span, Widget};
-use ratatui::style::{Color, Stylize};
+use ratatui::style::{Color, Modifier, Stylize};
 use ratatui::widgets::BorderType;
 use ratatui::widgets::{Block, Clear};
 use ratatui::Frame;
 use std::any::Any;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use text_buffer::TextBuffer;
+
+pub mod text_buffer;
 
 pub enum EditMode {
     Normal,
@@ -19,16 +29,28 @@ pub enum EditMode {
 }
 pub enum Operation {
     Delete,
+    /// Waiting for the register to record a macro into, after `q`.
+    Record,
+    /// Waiting for the register to replay a macro from, after `@`.
+    Replay,
+}
+/// Keys of a change made from NORMAL mode, so that it can be repeated with `.`.
+#[derive(Clone)]
+pub struct Change {
+    pub multiple: Option<u8>,
+    pub keys: Vec<KeyEvent>,
 }
 pub struct TextEditorState {
     pub is_focused: bool,
     pub label: String,
 
     pub mode: EditMode,
-    pub lines: Vec<Vec<char>>,
+    pub buffer: TextBuffer,
     pub scroll_offset: usize,
     pub cursor_index: usize,
     pub cursor_line_index: usize,
+    /// Area of the text as last drawn, used to locate mouse clicks.
+    pub text_area: Cell<Rect>,
     /// Typical Vim Operations.
     pub operation: Option<Operation>,
     /// Determine how many times an operation should be executed. At least 1.
@@ -37,6 +59,19 @@ pub struct TextEditorState {
     pub anchor: (usize, usize),
 
     pub copy_buffer: Vec<Vec<char>>, // First line insert char, the rest directly insert line.
+
+    /// The register being recorded into, along with the keys recorded so far.
+    pub recording: Option<(char, Vec<KeyEvent>)>,
+    pub registers: HashMap<char, Vec<KeyEvent>>,
+    /// The register replayed last, used by `@@`.
+    pub last_register: Option<char>,
+    pub last_change: Option<Change>,
+    /// The change still being typed in INSERT mode, it becomes the last change upon `Esc`.
+    pub pending_change: Option<Change>,
+    /// How many replays are nested, guards against macros calling themselves forever.
+    pub replay_depth: usize,
+    /// Number of spaces inserted by Tab.
+    pub tab_width: u16,
 }
 pub struct TextEditor {
     pub state: TextEditorState,
@@ -52,16 +87,25 @@ impl TextEditor {
                 label: label.to_string(),
 
                 mode: EditMode::Normal,
-                lines: Vec::new(),
+                buffer: TextBuffer::new(),
                 scroll_offset: 0,
                 cursor_index: 0,
                 cursor_line_index: 0,
+                text_area: Cell::new(Rect::default()),
                 operation: None,
                 multiple: None,
 
                 anchor: (0, 0),
 
                 copy_buffer: Vec::new(), // First line insert char, the rest directly insert line.
+
+                recording: None,
+                registers: HashMap::new(),
+                last_register: None,
+                last_change: None,
+                pending_change: None,
+                replay_depth: 0,
+                tab_width: 4,
             },
             on_exit: None,
         }
@@ -74,22 +118,13 @@ impl TextEditor {
         self.on_exit = Some(on_exit);
         self
     }
-    /// Convert the content of the text editor to String.
-    pub fn to_string(&self) -> String {
-        let mut lines = self.state.lines.clone();
-        for line in &mut lines[0..self.state.lines.len() - 1] {
-            line.push('\n');
-        }
-        self.state.lines.concat().iter().collect::<String>()
+    pub fn tab_width(mut self, tab_width: u16) -> Self {
+        self.state.tab_width = tab_width;
+        self
     }
     /// Replace the whole content of the text editor.
     pub fn replace(&mut self, content: String) {
-        let parsed_content_0: Vec<&str> = content.split('\n').collect::<Vec<&str>>();
-        let parsed_content_1: Vec<Vec<char>> = parsed_content_0
-            .iter()
-            .map(|line| line.chars().collect::<Vec<char>>())
-            .collect::<Vec<Vec<char>>>();
-        self.state.lines = parsed_content_1;
+        self.state.buffer.replace(&content);
         self.state.cursor_index = 0;
     }
     // Getters
@@ -98,10 +133,7 @@ impl TextEditor {
         (self.state.cursor_index, self.state.cursor_line_index)
     }
     pub fn get_line_len_at(&self, row: usize) -> usize {
-        if self.state.lines.get(row).is_some() {
-            return self.state.lines.get(row).unwrap().len();
-        }
-        0
+        self.state.buffer.line_len(row)
     }
     /// Retrieve relevant information about the current state of the editor
     pub fn get_info(&self) -> String {
@@ -112,12 +144,24 @@ impl TextEditor {
             },
             match self.state.operation {
                 Some(Operation::Delete) => "[delete]".to_string(),
+                Some(Operation::Record) => "[record]".to_string(),
+                Some(Operation::Replay) => "[replay]".to_string(),
                 _ => "".to_string(),
             },
+            match &self.state.recording {
+                Some((register, _)) => format!("recording @{}", register),
+                None => "".to_string(),
+            },
         ]
         .join(" ")
     }
 
+    /// Number of chars scrolled out on the left, so that the cursor stays visible in the width.
+    pub fn get_horizontal_offset(&self, width: u16) -> usize {
+        self.state
+            .cursor_index
+            .saturating_sub(width.saturating_sub(7) as usize)
+    }
     pub fn has_multiple(&self) -> bool {
         self.state.multiple.is_some()
     }
@@ -149,7 +193,7 @@ impl TextEditor {
 
     pub fn scroll_vertical_offset(&mut self, offset: i16) {
         if offset.is_positive() {
-            self.state.cursor_line_index = self.state.cursor_line_index.saturating_add(offset.unsigned_abs() as usize).clamp(0, self.state.lines.len()-1);
+            self.state.cursor_line_index = self.state.cursor_line_index.saturating_add(offset.unsigned_abs() as usize).clamp(0, self.state.buffer.line_count() - 1);
             self.state.scroll_offset = self.state .scroll_offset .saturating_add(offset.unsigned_abs() as usize);
         } else {
             self.state.cursor_line_index = self.state.cursor_line_index.saturating_sub(offset.unsigned_abs() as usize);
@@ -164,7 +208,7 @@ impl TextEditor {
                 .state
                 .cursor_line_index
                 .saturating_add(1)
-                .clamp(0, self.state.lines.len().saturating_sub(1));
+                .clamp(0, self.state.buffer.line_count().saturating_sub(1));
         }
         self.reset_multiple();
     }
@@ -174,31 +218,29 @@ impl TextEditor {
                 .state
                 .cursor_line_index
                 .saturating_sub(1)
-                .clamp(0, self.state.lines.len().saturating_sub(1));
+                .clamp(0, self.state.buffer.line_count().saturating_sub(1));
         }
         self.reset_multiple();
     }
     pub fn move_to_next_char(&mut self) {
         for i in 0..self.state.multiple.unwrap_or(1) {
-            if let Some(current_line) = self.state.lines.get(self.state.cursor_line_index) {
-                self.state.cursor_index = self
-                    .state
-                    .cursor_index
-                    .saturating_add(1)
-                    .clamp(0, current_line.len());
-            }
+            let line_len = self.get_line_len_at(self.state.cursor_line_index);
+            self.state.cursor_index = self
+                .state
+                .cursor_index
+                .saturating_add(1)
+                .clamp(0, line_len);
         }
         self.reset_multiple();
     }
     pub fn move_to_previous_char(&mut self) {
         for i in 0..self.state.multiple.unwrap_or(1) {
-            if let Some(current_line) = self.state.lines.get(self.state.cursor_line_index) {
-                self.state.cursor_index = self
-                    .state
-                    .cursor_index
-                    .clamp(0, current_line.len())
-                    .saturating_sub(1);
-            }
+            let line_len = self.get_line_len_at(self.state.cursor_line_index);
+            self.state.cursor_index = self
+                .state
+                .cursor_index
+                .clamp(0, line_len)
+                .saturating_sub(1);
         }
         self.reset_multiple();
     }
@@ -209,7 +251,7 @@ impl TextEditor {
             let current_x: usize = self
                 .state
                 .cursor_index
-                .clamp(0, self.state.lines[current_y].len().saturating_sub(1));
+                .clamp(0, self.get_line_len_at(current_y).saturating_sub(1));
             if let Some((x, _y)) = self.find_next(current_x, current_y, ' ') {
                 self.state.cursor_index = x;
             } else {
@@ -226,7 +268,7 @@ impl TextEditor {
             let current_x: usize = self
                 .state
                 .cursor_index
-                .clamp(0, self.state.lines[current_y].len().saturating_sub(1));
+                .clamp(0, self.get_line_len_at(current_y).saturating_sub(1));
             if let Some((x, _y)) = self.find_previous(current_x.saturating_sub(1), current_y, ' ') {
                 self.state.cursor_index = x;
                 self.move_to_next_char();
@@ -239,142 +281,222 @@ impl TextEditor {
     }
 
     pub fn move_to_end_of_line(&mut self) {
-        if let Some(current_line) = self.state.lines.get(self.state.cursor_line_index) {
-            self.state.cursor_index = current_line.len();
-        }
+        self.state.cursor_index = self.get_line_len_at(self.state.cursor_line_index);
     }
     pub fn move_to_start_of_line(&mut self) {
-        if let Some(_current_line) = self.state.lines.get(self.state.cursor_line_index) {
-            self.state.cursor_index = 0;
-        }
+        self.state.cursor_index = 0;
     }
 
     pub fn insert_char(&mut self, char: char) {
-        if let Some(current_line) = self.state.lines.get_mut(self.state.cursor_line_index) {
-            self.state.cursor_index = self.state.cursor_index.clamp(0, current_line.len());
-            current_line.insert(self.state.cursor_index, char);
-        }
+        let (x, y) = self.get_cursor_position();
+        self.state.cursor_index = x.clamp(0, self.get_line_len_at(y));
+        self.state.buffer.insert_char(self.state.cursor_index, y, char);
         self.move_to_next_char();
     }
 
     /// Delete the char under the cursor without moving it.
     pub fn delete_char(&mut self) {
         for i in 0..self.state.multiple.unwrap_or(1) {
-            if let Some(current_line) = self.state.lines.get_mut(self.state.cursor_line_index) {
-                if current_line.is_empty() {
-                    return;
-                }
-                if self.state.cursor_index >= current_line.len() {
-                    return;
-                }
-                self.state.cursor_index = self.state.cursor_index.clamp(0, current_line.len());
-                current_line.remove(self.state.cursor_index);
+            let (x, y) = self.get_cursor_position();
+            if self.state.buffer.remove_char(x, y).is_none() {
+                return;
             }
         }
         self.reset_multiple();
     }
     pub fn insert_new_line_below(&mut self) {
         self.state
-            .lines
-            .insert(self.state.cursor_line_index + 1, Vec::new());
+            .buffer
+            .insert_line(self.state.cursor_line_index + 1, "");
     }
     pub fn insert_new_line_above(&mut self) {
-        self.state
-            .lines
-            .insert(self.state.cursor_line_index, Vec::new());
+        self.state.buffer.insert_line(self.state.cursor_line_index, "");
     }
     pub fn merge_with_next_line(&mut self) {
-        if self.state.lines.get(self.state.cursor_line_index).is_none() {
+        self.state.buffer.join_lines(self.state.cursor_line_index);
+    }
+    pub fn cut_into_next_newline(&mut self) {
+        let (x, y) = self.get_cursor_position();
+        if y >= self.state.buffer.line_count() {
             return;
         }
-        if self
-            .state
-            .lines
-            .get(self.state.cursor_line_index + 1)
-            .is_none()
-        {
-            return;
+        self.state.buffer.split_line(x, y);
+        self.move_to_next_line();
+        self.move_to_start_of_line();
+    }
+    /// Start recording keys into the register, `q` stops it.
+    pub fn start_recording(&mut self, register: char) {
+        self.state.recording = Some((register, Vec::new()));
+    }
+    pub fn stop_recording(&mut self) {
+        if let Some((register, keys)) = self.state.recording.take() {
+            self.state.registers.insert(register, keys);
         }
-        self.merge_line(
-            self.state.cursor_line_index + 1,
-            self.state.cursor_line_index,
-        );
     }
-    pub fn cut_into_next_newline(&mut self) {
-        let line_index = self.state.cursor_line_index;
-        if let Some(current_line) = self.state.lines.get_mut(line_index) {
-            let from = self.state.cursor_index;
-            let to = current_line.len().saturating_sub(1);
-            let mut portion = self.remove_line_portion(from, to);
-            self.insert_new_line_below();
-            if let Some(next_line) = self.state.lines.get_mut(line_index + 1) {
-                next_line.append(&mut portion);
-            }
-            self.move_to_next_line();
-            self.move_to_start_of_line();
+    /// Replay the macro stored in the register, `@` replays the last replayed register.
+    pub fn replay_register(
+        &mut self,
+        register: char,
+        mut parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let register = match register {
+            '@' => match self.state.last_register {
+                Some(register) => register,
+                None => return Ok(Vec::new()),
+            },
+            _ => register,
+        };
+        let Some(keys) = self.state.registers.get(&register).cloned() else {
+            return Ok(Vec::new());
+        };
+        self.state.last_register = Some(register);
+        let times = self.state.multiple.unwrap_or(1);
+        self.reset_multiple();
+        let mut commands: Vec<Command> = Vec::new();
+        for _ in 0..times {
+            commands.append(&mut self.replay(&keys, parent_state.as_deref_mut())?);
         }
+        Ok(commands)
+    }
+    /// Repeat the last change, a given multiple replaces the one of the change.
+    pub fn repeat_last_change(
+        &mut self,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        let Some(change) = self.state.last_change.clone() else {
+            self.reset_multiple();
+            return Ok(Vec::new());
+        };
+        self.state.multiple = self.state.multiple.or(change.multiple);
+        self.replay(&change.keys, parent_state)
     }
     // Helper functions
-    fn remove_line_portion(&mut self, from: usize, to: usize) -> Vec<char> {
-        if from == to {
-            return vec![];
+    /// Feed the keys through the usual key handling, as if the user typed them.
+    fn replay(
+        &mut self,
+        keys: &[KeyEvent],
+        mut parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        if self.state.replay_depth >= 100 {
+            return Err(Report::msg("Macro calls itself too many times."));
         }
-        if let Some(current_line) = self.state.lines.get_mut(self.state.cursor_line_index) {
-            let captured: Vec<char> = current_line[from..=to].to_vec();
-            for _i in from..=to {
-                current_line.remove(from);
+        self.state.replay_depth += 1;
+        let mut commands: Vec<Command> = Vec::new();
+        let mut result: Result<()> = Ok(());
+        for key in keys {
+            // Stop once the keys lead out of the editor.
+            if !self.is_focused() {
+                break;
+            }
+            match self.handle(key, parent_state.as_deref_mut()) {
+                Ok(mut new_commands) => commands.append(&mut new_commands),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
             }
-            return captured;
         }
-        Vec::new()
+        self.state.replay_depth -= 1;
+        result.map(|_| commands)
     }
-    fn merge_line(&mut self, from: usize, to: usize) {
-        let mut from_line = self.state.lines.get_mut(from).unwrap().to_vec();
-        self.delete_line(from);
-        let to_line = self.state.lines.get_mut(to).unwrap();
-        to_line.append(&mut from_line);
+    /// Keep track of the keys making up a change, for `.` to repeat it.
+    fn track_change(&mut self, key: &KeyEvent) {
+        match self.state.mode {
+            EditMode::Normal => {
+                let KeyCode::Char(c) = key.code else {
+                    return;
+                };
+                if key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return;
+                }
+                let change = Change {
+                    multiple: self.state.multiple,
+                    keys: vec![*key],
+                };
+                match c {
+                    'x' | 'J' => self.state.last_change = Some(change),
+                    'i' | 'a' | 'A' | 'I' | 'o' | 'O' => self.state.pending_change = Some(change),
+                    _ => {}
+                }
+            }
+            EditMode::Insert => {
+                if let Some(change) = self.state.pending_change.as_mut() {
+                    change.keys.push(*key);
+                }
+                if let KeyCode::Esc = key.code {
+                    self.state.last_change = self.state.pending_change.take();
+                }
+            }
+            _ => {}
+        }
     }
-    /// Delete specific line, it does not change cursor location.
-    fn delete_line(&mut self, at: usize) {
-        if let Some(_current_line) = self.state.lines.get(at) {
-            self.state.lines.remove(at);
+    /// Handle `q`, `@` and `.`, which work on recorded keys rather than the text itself.
+    fn handle_replay_keys(
+        &mut self,
+        key: &KeyEvent,
+        parent_state: Option<&mut dyn Any>,
+    ) -> Option<Result<Vec<Command>>> {
+        if !matches!(self.state.mode, EditMode::Normal) {
+            return None;
+        }
+        match (&self.state.operation, key.code) {
+            (Some(Operation::Record), KeyCode::Char(register)) => {
+                self.state.operation = None;
+                self.start_recording(register);
+                Some(Ok(Vec::new()))
+            }
+            (Some(Operation::Replay), KeyCode::Char(register)) => {
+                self.state.operation = None;
+                Some(self.replay_register(register, parent_state))
+            }
+            (Some(Operation::Record | Operation::Replay), _) => {
+                self.state.operation = None;
+                self.reset_multiple();
+                Some(Ok(Vec::new()))
+            }
+            (None, KeyCode::Char('q')) => {
+                if self.state.recording.is_some() {
+                    self.stop_recording();
+                } else {
+                    self.state.operation = Some(Operation::Record);
+                }
+                Some(Ok(Vec::new()))
+            }
+            (None, KeyCode::Char('@')) => {
+                self.state.operation = Some(Operation::Replay);
+                Some(Ok(Vec::new()))
+            }
+            (None, KeyCode::Char('.')) => Some(self.repeat_last_change(parent_state)),
+            _ => None,
         }
     }
-
     /// Find the location of target character in a forward direction.
     fn find_next(&mut self, x: usize, y: usize, character: char) -> Option<(usize, usize)> {
-        if let Some(current_line) = self.state.lines.get(y) {
-            // If x exceed the len of the line, or the line is empty.
-            let len = current_line.len();
-            if current_line.is_empty() || current_line.get(x).is_none() {
-                return None;
-            }
-            for (i, c) in (*current_line)[x..len].iter().enumerate() {
-                if (*c) == character {
-                    return Some((i + x, y));
-                }
-            }
-            None
-        } else {
-            None // Such line does not exist.
+        // If x exceed the len of the line, or the line is empty.
+        if x >= self.get_line_len_at(y) {
+            return None;
         }
+        self.state
+            .buffer
+            .line_chars(y)
+            .skip(x)
+            .position(|c| c == character)
+            .map(|i| (i + x, y))
     }
     /// Find the location of target character in a backward direction.
     fn find_previous(&mut self, x: usize, y: usize, character: char) -> Option<(usize, usize)> {
-        if let Some(current_line) = self.state.lines.get(y) {
-            // If x exceed the len of the line, or the line is empty.
-            if current_line.is_empty() || current_line.get(x).is_none() {
-                return None;
-            }
-            for (i, c) in (*current_line)[0..=x].iter().enumerate().rev() {
-                if *c == character {
-                    return Some((i, y));
-                }
-            }
-            None
-        } else {
-            None // Such line does not exist.
+        // If x exceed the len of the line, or the line is empty.
+        if x >= self.get_line_len_at(y) {
+            return None;
         }
+        let line: Vec<char> = self.state.buffer.line_chars(y).take(x + 1).collect();
+        line.iter().rposition(|c| *c == character).map(|i| (i, y))
+    }
+}
+impl Display for TextEditor {
+    /// Convert the content of the text editor to String.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.state.buffer.fmt(f)
     }
 }
 impl From<TextEditor> for Box<dyn Container> {
@@ -383,23 +505,28 @@ impl From<TextEditor> for Box<dyn Container> {
     }
 }
 impl Drawable for TextEditor {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let mut border: Block =
             block!(self.state.label.as_str(), draw_flag, theme).bg(theme.surface_low());
         match self.state.mode {
             EditMode::Normal => border = border.title(Line::from("NORMAL").bold()),
-            EditMode::Insert => border = border.title(Line::from("INSERT").bold()).yellow(),
-            EditMode::Visual => border = border.title(Line::from("VISUAL").bold()).blue(),
-            EditMode::VisualLine => border = border.title(Line::from("VISUAL LINE").bold()).cyan(),
+            EditMode::Insert => {
+                border = border.title(Line::from("INSERT").bold());
+                border = theme.accent(border, Color::Yellow, Modifier::BOLD);
+            }
+            EditMode::Visual => {
+                border = border.title(Line::from("VISUAL").bold());
+                border = theme.accent(border, Color::Blue, Modifier::UNDERLINED);
+            }
+            EditMode::VisualLine => {
+                border = border.title(Line::from("VISUAL LINE").bold());
+                border = theme.accent(border, Color::Cyan, Modifier::UNDERLINED);
+            }
         }
         let inner_area = border.inner(area);
+        self.state.text_area.set(inner_area);
         let line_rows: Rows = inner_area.rows();
-        let horizontal_offset =
-            if self.state.cursor_index > inner_area.width.saturating_sub(7) as usize {
-                self.state.cursor_index - inner_area.width.saturating_sub(7) as usize
-            } else {
-                0
-            };
+        let horizontal_offset = self.get_horizontal_offset(inner_area.width);
         // Set Cursor Position
         if self.is_focused() {
             let (_x, _y) = self.get_cursor_position();
@@ -422,27 +549,30 @@ impl Drawable for TextEditor {
         }
 
         border.render(area, frame.buffer_mut());
-        let lines: Vec<Line> = self
-            .state
-            .lines
-            .iter()
-            .enumerate()
-            .skip_while(|(line_number, _line)| *line_number < self.state.scroll_offset)
-            .map(|(line_number, line)| {
-                let mut line = Line::from(vec![
-                    Span::from(format!("{:<4}", line_number.to_string())).dim(),
-                    Span::from(line.iter().skip(horizontal_offset).collect::<String>()),
-                ]);
-                if line_number == self.state.cursor_line_index {
-                    line = line.bg(theme.surface_low_highlight());
-                } else {
-                    line = line.bg(theme.surface_low());
-                }
-                line
-            })
-            .collect();
-        for (line_number, line_row) in line_rows.into_iter().enumerate() {
-            lines.get(line_number).render(line_row, frame.buffer_mut());
+        // Only the visible lines are materialized, the rest stay in the buffer.
+        let line_count = self.state.buffer.line_count();
+        for (row, line_row) in line_rows.into_iter().enumerate() {
+            let line_number = self.state.scroll_offset + row;
+            if line_number >= line_count {
+                break;
+            }
+            let content: String = self
+                .state
+                .buffer
+                .line_chars(line_number)
+                .skip(horizontal_offset)
+                .take(line_row.width as usize)
+                .collect();
+            let mut line = Line::from(vec![
+                Span::from(format!("{:<4}", line_number.to_string())).dim(),
+                Span::from(content),
+            ]);
+            if line_number == self.state.cursor_line_index {
+                line = line.patch_style(theme.highlight());
+            } else {
+                line = line.bg(theme.surface_low());
+            }
+            line.render(line_row, frame.buffer_mut());
         }
         // Bottom status bar
         if self.is_focused() {
@@ -451,7 +581,7 @@ impl Drawable for TextEditor {
             Line::from(self.get_info())
                 .bg(match self.state.mode {
                     EditMode::Normal => theme.on_surface(),
-                    EditMode::Insert => Color::Yellow,
+                    EditMode::Insert => theme.color(Color::Yellow),
                     _ => theme.on_surface(),
                 })
                 .render(status_bar_area, frame.buffer_mut());
@@ -463,8 +593,25 @@ impl Interactable for TextEditor {
     fn handle(
         &mut self,
         key: &KeyEvent,
-        parent_state: Option<&mut dyn Any>,
+        mut parent_state: Option<&mut dyn Any>,
     ) -> Result<Vec<Command>> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(Vec::new());
+        }
+        // Keys replayed from a register are already part of the recording as `@{register}`.
+        let is_stop_recording = matches!(self.state.mode, EditMode::Normal)
+            && self.state.operation.is_none()
+            && key.code == KeyCode::Char('q');
+        if self.state.replay_depth == 0
+            && !is_stop_recording
+            && let Some((_, keys)) = self.state.recording.as_mut()
+        {
+            keys.push(*key);
+        }
+        if let Some(result) = self.handle_replay_keys(key, parent_state.as_deref_mut()) {
+            return result;
+        }
+        self.track_change(key);
         match self.state.mode {
             EditMode::Normal => handle_normal_mode(self, key, parent_state),
             EditMode::Insert => handle_insert_mode(self, key),
@@ -472,7 +619,79 @@ impl Interactable for TextEditor {
             EditMode::VisualLine => handle_visual_line_mode(self, key),
         }
     }
+    fn handle_mouse(
+        &mut self,
+        mouse: &MouseEvent,
+        _parent_state: Option<&mut dyn Any>,
+    ) -> Result<Vec<Command>> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_vertical_offset(-(MOUSE_SCROLL_LINES as i16)),
+            MouseEventKind::ScrollDown => self.scroll_vertical_offset(MOUSE_SCROLL_LINES as i16),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let text_area: Rect = self.state.text_area.get();
+                if !text_area.contains(Position::new(mouse.column, mouse.row)) {
+                    return Ok(Vec::new());
+                }
+                let y: usize = (self.state.scroll_offset + (mouse.row - text_area.y) as usize)
+                    .min(self.state.buffer.line_count() - 1);
+                // The line number takes the first 4 columns.
+                let x: usize = (mouse.column - text_area.x).saturating_sub(4) as usize
+                    + self.get_horizontal_offset(text_area.width);
+                let max_x: usize = match self.state.mode {
+                    EditMode::Insert => self.get_line_len_at(y),
+                    _ => self.get_line_len_at(y).saturating_sub(1),
+                };
+                self.state.cursor_line_index = y;
+                self.state.cursor_index = x.min(max_x);
+            }
+            _ => {}
+        }
+        Ok(Vec::new())
+    }
+    fn keymap(&self) -> Vec<(&str, &str)> {
+        match self.state.mode {
+            EditMode::Normal => NORMAL_MODE_KEYS.into(),
+            EditMode::Insert => INSERT_MODE_KEYS.into(),
+            EditMode::Visual | EditMode::VisualLine => VISUAL_MODE_KEYS.into(),
+        }
+    }
+    fn help(&self) -> Vec<(String, Vec<(&str, &str)>)> {
+        vec![
+            (String::from("Editor: NORMAL"), NORMAL_MODE_KEYS.into()),
+            (String::from("Editor: INSERT"), INSERT_MODE_KEYS.into()),
+        ]
+    }
+    fn is_typing(&self) -> bool {
+        !matches!(self.state.mode, EditMode::Normal) || self.state.operation.is_some()
+    }
 }
+const NORMAL_MODE_KEYS: [(&str, &str); 13] = [
+    ("h/j/k/l", "Move"),
+    ("w/b/e", "Next Word/Previous Word/End of Word"),
+    ("0/$", "Start/End of Line"),
+    ("i/a/I/A", "Insert Before/After/At Start/At End"),
+    ("o/O", "Insert Line Below/Above"),
+    ("x", "Delete Char"),
+    ("J", "Join with Next Line"),
+    ("c-d/c-u", "Scroll Down/Up"),
+    ("1-9", "Count for the Next Key"),
+    (".", "Repeat Last Change"),
+    ("q{register}/q", "Record Macro/Stop Recording"),
+    ("@{register}/@@", "Replay Macro/Replay Last Macro"),
+    ("Esc", "Exit Editor"),
+];
+const INSERT_MODE_KEYS: [(&str, &str); 5] = [
+    ("Esc", "NORMAL Mode"),
+    ("up/down/left/right", "Move"),
+    ("Enter", "Break Line"),
+    ("tab", "Insert tab_width Spaces"),
+    ("backspace", "Delete Previous Char"),
+];
+const VISUAL_MODE_KEYS: [(&str, &str); 3] = [
+    ("h/j/k/l", "Move"),
+    ("i", "INSERT Mode"),
+    ("Esc", "NORMAL Mode"),
+];
 fn handle_normal_mode(
     me: &mut TextEditor,
     key: &KeyEvent,
@@ -623,10 +842,9 @@ fn handle_insert_mode(me: &mut TextEditor, key: &KeyEvent) -> Result<Vec<Command
                 me.move_to_next_line();
             }
             if let KeyCode::Tab = key.code {
-                me.insert_char(' ');
-                me.insert_char(' ');
-                me.insert_char(' ');
-                me.insert_char(' ');
+                for _ in 0..me.state.tab_width {
+                    me.insert_char(' ');
+                }
             }
             if let KeyCode::Backspace = key.code {
                 if me.get_cursor_position().0 == 0 {
@@ -755,3 +973,63 @@ impl Focusable for TextEditor {
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn type_keys(editor: &mut TextEditor, keys: &str) -> Result<Vec<Command>> {
+        let mut commands = Vec::new();
+        for c in keys.chars() {
+            let code = match c {
+                '\x1b' => KeyCode::Esc,
+                _ => KeyCode::Char(c),
+            };
+            commands.append(&mut editor.handle(&KeyEvent::from(code), None)?);
+        }
+        Ok(commands)
+    }
+    fn editor_with(content: &str) -> TextEditor {
+        let mut editor = TextEditor::new("Editor");
+        editor.replace(content.to_string());
+        editor.set_focus(true);
+        editor
+    }
+
+    #[test]
+    fn dot_repeats_last_change() {
+        let mut editor = editor_with("abcdefgh");
+        type_keys(&mut editor, "x.").unwrap();
+        assert_eq!(editor.to_string(), "cdefgh");
+        type_keys(&mut editor, "2.").unwrap();
+        assert_eq!(editor.to_string(), "efgh");
+        // The new multiple sticks with the change.
+        type_keys(&mut editor, ".").unwrap();
+        assert_eq!(editor.to_string(), "gh");
+
+        let mut editor = editor_with("a\nb");
+        type_keys(&mut editor, "A;\x1bj.").unwrap();
+        assert_eq!(editor.to_string(), "a;\nb;");
+    }
+
+    #[test]
+    fn macros_record_and_replay() {
+        let mut editor = editor_with("one\ntwo\nthree\nfour");
+        type_keys(&mut editor, "qaI- \x1bjq").unwrap();
+        assert_eq!(editor.to_string(), "- one\ntwo\nthree\nfour");
+        type_keys(&mut editor, "@a@@").unwrap();
+        assert_eq!(editor.to_string(), "- one\n- two\n- three\nfour");
+        type_keys(&mut editor, "@b").unwrap();
+        assert_eq!(editor.to_string(), "- one\n- two\n- three\nfour");
+        type_keys(&mut editor, "1@a").unwrap();
+        assert_eq!(editor.to_string(), "- one\n- two\n- three\n- four");
+    }
+
+    #[test]
+    fn macros_calling_themselves_are_stopped() {
+        let mut editor = editor_with("text");
+        type_keys(&mut editor, "qa@aq").unwrap();
+        assert!(type_keys(&mut editor, "@a").is_err());
+        assert_eq!(editor.state.replay_depth, 0);
+    }
+}
@@ -0,0 +1,199 @@
+use ropey::Rope;
+use std::fmt::{Display, Formatter};
+
+/// Line oriented text storage of the text editor.
+///
+/// Backed by a rope, so inserting and deleting stays cheap regardless of the size of the content.
+/// Only `\n` is treated as a line break, every position is expressed as (line index, char index).
+/// There is always at least one (possibly empty) line.
+#[derive(Clone, Default)]
+pub struct TextBuffer {
+    rope: Rope,
+}
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self { rope: Rope::new() }
+    }
+    // Getters
+    pub fn line_count(&self) -> usize {
+        self.rope.len_lines()
+    }
+    /// Number of chars in the line, excluding the line break. 0 if such line does not exist.
+    pub fn line_len(&self, y: usize) -> usize {
+        if y >= self.line_count() {
+            return 0;
+        }
+        let line = self.rope.line(y);
+        let len = line.len_chars();
+        if len > 0 && line.char(len - 1) == '\n' {
+            len - 1
+        } else {
+            len
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.rope.len_chars() == 0
+    }
+    pub fn char_at(&self, x: usize, y: usize) -> Option<char> {
+        if x >= self.line_len(y) {
+            return None;
+        }
+        Some(self.rope.line(y).char(x))
+    }
+    /// Iterate through the chars of the line, excluding the line break.
+    pub fn line_chars(&self, y: usize) -> impl Iterator<Item = char> + '_ {
+        let len = self.line_len(y);
+        let line = (y < self.line_count()).then(|| self.rope.line(y));
+        line.into_iter()
+            .flat_map(move |line| line.chars().take(len))
+    }
+    pub fn line_to_string(&self, y: usize) -> String {
+        self.line_chars(y).collect()
+    }
+    // Setters
+    /// Replace the whole content.
+    pub fn replace(&mut self, content: &str) {
+        self.rope = Rope::from_str(content);
+    }
+    /// Insert a char in the line, x is clamped to the line length.
+    pub fn insert_char(&mut self, x: usize, y: usize, c: char) {
+        if y >= self.line_count() {
+            return;
+        }
+        let index = self.char_index(x, y);
+        self.rope.insert_char(index, c);
+    }
+    /// Insert a string in the line, x is clamped to the line length.
+    pub fn insert_str(&mut self, x: usize, y: usize, text: &str) {
+        if y >= self.line_count() {
+            return;
+        }
+        let index = self.char_index(x, y);
+        self.rope.insert(index, text);
+    }
+    /// Remove the char under (x, y), line breaks are never removed.
+    pub fn remove_char(&mut self, x: usize, y: usize) -> Option<char> {
+        let c = self.char_at(x, y)?;
+        let index = self.rope.line_to_char(y) + x;
+        self.rope.remove(index..index + 1);
+        Some(c)
+    }
+    /// Remove chars of the line in range [from, to), returning the removed portion.
+    pub fn remove_line_portion(&mut self, from: usize, to: usize, y: usize) -> String {
+        let len = self.line_len(y);
+        let (from, to) = (from.min(len), to.min(len));
+        if from >= to {
+            return String::new();
+        }
+        let start = self.rope.line_to_char(y);
+        let portion = self.rope.slice(start + from..start + to).to_string();
+        self.rope.remove(start + from..start + to);
+        portion
+    }
+    /// Insert a new line so that it becomes line `at`, `at` is clamped to the line count.
+    pub fn insert_line(&mut self, at: usize, content: &str) {
+        if at >= self.line_count() {
+            let end = self.rope.len_chars();
+            self.rope.insert(end, &format!("\n{}", content));
+        } else {
+            let index = self.rope.line_to_char(at);
+            self.rope.insert(index, &format!("{}\n", content));
+        }
+    }
+    /// Remove the line entirely. Removing the only line clears it instead.
+    pub fn remove_line(&mut self, at: usize) {
+        let count = self.line_count();
+        if at >= count {
+            return;
+        }
+        let start = self.rope.line_to_char(at);
+        if count == 1 {
+            self.rope.remove(..);
+        } else if at == count - 1 {
+            // Last line, remove the preceding line break instead.
+            self.rope.remove(start - 1..);
+        } else {
+            let end = self.rope.line_to_char(at + 1);
+            self.rope.remove(start..end);
+        }
+    }
+    /// Break the line at x, moving the rest of it to a new line below.
+    pub fn split_line(&mut self, x: usize, y: usize) {
+        self.insert_char(x, y, '\n');
+    }
+    /// Append the next line to the end of line y.
+    pub fn join_lines(&mut self, y: usize) {
+        if y + 1 >= self.line_count() {
+            return;
+        }
+        let index = self.rope.line_to_char(y) + self.line_len(y);
+        self.rope.remove(index..index + 1);
+    }
+    // Helper functions
+    fn char_index(&self, x: usize, y: usize) -> usize {
+        self.rope.line_to_char(y) + x.min(self.line_len(y))
+    }
+}
+impl From<&str> for TextBuffer {
+    fn from(content: &str) -> Self {
+        Self {
+            rope: Rope::from_str(content),
+        }
+    }
+}
+impl Display for TextBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for chunk in self.rope.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_follow_line_breaks() {
+        let buffer = TextBuffer::from("ab\n\ncd\n");
+        assert_eq!(buffer.line_count(), 4);
+        assert_eq!(buffer.line_len(0), 2);
+        assert_eq!(buffer.line_len(1), 0);
+        assert_eq!(buffer.line_to_string(2), "cd");
+        assert_eq!(buffer.line_len(3), 0);
+        assert_eq!(buffer.line_len(9), 0);
+        assert_eq!(TextBuffer::new().line_count(), 1);
+    }
+
+    #[test]
+    fn edits_within_line() {
+        let mut buffer = TextBuffer::from("hello\nworld");
+        buffer.insert_char(99, 0, '!');
+        buffer.insert_char(0, 1, '>');
+        assert_eq!(buffer.to_string(), "hello!\n>world");
+        assert_eq!(buffer.remove_char(5, 0), Some('!'));
+        assert_eq!(buffer.remove_char(5, 0), None);
+        assert_eq!(buffer.remove_line_portion(1, 3, 1), "wo");
+        assert_eq!(buffer.to_string(), "hello\n>rld");
+    }
+
+    #[test]
+    fn edits_across_lines() {
+        let mut buffer = TextBuffer::from("one\ntwo");
+        buffer.split_line(1, 0);
+        assert_eq!(buffer.to_string(), "o\nne\ntwo");
+        buffer.join_lines(1);
+        assert_eq!(buffer.to_string(), "o\nnetwo");
+        buffer.insert_line(0, "zero");
+        buffer.insert_line(9, "last");
+        assert_eq!(buffer.to_string(), "zero\no\nnetwo\nlast");
+        buffer.remove_line(3);
+        buffer.remove_line(0);
+        assert_eq!(buffer.to_string(), "o\nnetwo");
+        buffer.remove_line(1);
+        buffer.remove_line(0);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.line_count(), 1);
+    }
+}
@@ -6,6 +6,7 @@ use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::layout::{Offset, Position, Rect};
 use ratatui::prelude::Line;
 use ratatui::prelude::Stylize;
+use ratatui::style::{Color, Modifier};
 use ratatui::widgets::{Block, Paragraph, Wrap};
 use ratatui::widgets::{BorderType, Widget};
 use ratatui::Frame;
@@ -88,15 +89,15 @@ impl From<TextField> for Box<dyn Container> {
     }
 }
 impl Drawable for TextField {
-    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &dyn Theme) {
+    fn render(&self, frame: &mut Frame, area: Rect, draw_flag: DrawFlag, theme: &Theme) {
         let content = self.state.chars.iter().collect::<String>();
         let content_paragraph: Paragraph =
             Paragraph::new(Line::from(content)).wrap(Wrap { trim: true });
         let mut text_field_block: Block =
             block!(self.state.label.as_str(), draw_flag, theme).bg(theme.surface_low());
         if !self.state.is_valid {
-            text_field_block = text_field_block
-                .red()
+            text_field_block = theme
+                .accent(text_field_block, Color::Red, Modifier::UNDERLINED)
                 .title_bottom("Invalid Input")
                 .bg(theme.surface_low());
         }
@@ -167,6 +168,9 @@ impl Interactable for TextField {
             }
         }
     }
+    fn is_typing(&self) -> bool {
+        self.is_focused()
+    }
 }
 
 impl Focusable for TextField {
@@ -0,0 +1,136 @@
+use crate::theme::Theme;
+use crate::utils::config_dir;
+use color_eyre::eyre::{Report, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const PRINT_WIDTH_RANGE: (u16, u16) = (20, 1000);
+const TAB_WIDTH_RANGE: (u16, u16) = (1, 16);
+
+/// Settings of the application, read from `config.toml` in the config directory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Name of the theme in use.
+    pub theme: String,
+    /// Directory listed when opening or creating a glyph, default to the working directory.
+    pub default_directory: Option<PathBuf>,
+    /// Number of columns of a printed entry.
+    pub print_width: u16,
+    /// Seconds between a change and its autosave, `0` disables the autosave.
+    pub autosave_seconds: u64,
+    /// Number of spaces inserted by Tab in the editor.
+    pub tab_width: u16,
+    /// Reopen the latest glyph when launched without arguments.
+    pub restore_session: bool,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: Theme::iceberg().name,
+            default_directory: None,
+            print_width: 96,
+            autosave_seconds: 30,
+            tab_width: 4,
+            restore_session: false,
+        }
+    }
+}
+impl Config {
+    /// Path of the config file, whether it exists or not.
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+    /// Load the config file, the default config is used when there is none.
+    pub fn load(themes: &[Theme]) -> Result<Self> {
+        let Some(path) = Config::path().filter(|path| path.exists()) else {
+            return Ok(Config::default());
+        };
+        let config: Config = Config::parse(&fs::read_to_string(&path)?, themes)
+            .map_err(|report| report.wrap_err(format!("Invalid config {}", path.display())))?;
+        Ok(config)
+    }
+    /// Parse and validate a config, every problem is reported at once.
+    pub fn parse(content: &str, themes: &[Theme]) -> Result<Self> {
+        let config: Config = toml::from_str(content).map_err(Report::new)?;
+        let problems: Vec<String> = config.validate(themes);
+        if !problems.is_empty() {
+            return Err(Report::msg(problems.join("\n")));
+        }
+        Ok(config)
+    }
+    pub fn validate(&self, themes: &[Theme]) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+        if !themes.iter().any(|theme| theme.name == self.theme) {
+            problems.push(format!("theme: no theme named \"{}\"", self.theme));
+        }
+        if let Some(dir) = &self.default_directory
+            && !dir.is_dir()
+        {
+            problems.push(format!(
+                "default_directory: {} is not a directory",
+                dir.display()
+            ));
+        }
+        for (key, value, (min, max)) in [
+            ("print_width", self.print_width, PRINT_WIDTH_RANGE),
+            ("tab_width", self.tab_width, TAB_WIDTH_RANGE),
+        ] {
+            if !(min..=max).contains(&value) {
+                problems.push(format!(
+                    "{}: {} is not within {}..={}",
+                    key, value, min, max
+                ));
+            }
+        }
+        problems
+    }
+    /// The directory to start browsing from.
+    pub fn directory(&self) -> PathBuf {
+        self.default_directory
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+    }
+    /// Delay between a change and its autosave, none when disabled.
+    pub fn autosave_interval(&self) -> Option<Duration> {
+        (self.autosave_seconds > 0).then(|| Duration::from_secs(self.autosave_seconds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let themes: Vec<Theme> = Theme::builtins();
+        let config: Config = Config::parse("theme = \"Light\"\ntab_width = 2", &themes).unwrap();
+        assert_eq!(config.theme, "Light");
+        assert_eq!(config.tab_width, 2);
+        assert_eq!(config.print_width, Config::default().print_width);
+        assert_eq!(Config::parse("", &themes).unwrap(), Config::default());
+        assert_eq!(
+            Config::parse("autosave_seconds = 0", &themes)
+                .unwrap()
+                .autosave_interval(),
+            None
+        );
+
+        // Unknown keys and wrong types are rejected.
+        assert!(Config::parse("tab_widht = 2", &themes).is_err());
+        assert!(Config::parse("tab_width = \"2\"", &themes).is_err());
+
+        let report: Report = Config::parse(
+            "theme = \"Solarized\"\nprint_width = 0\ntab_width = 32",
+            &themes,
+        )
+        .unwrap_err();
+        let message: String = report.to_string();
+        assert!(message.contains("theme"));
+        assert!(message.contains("print_width"));
+        assert!(message.contains("tab_width"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::utils::state_dir;
+use color_eyre::config::HookBuilder;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableFocusChange, DisableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, LeaveAlternateScreen};
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of the latest commands kept for the crash report.
+const RECENT_COMMANDS_CAPACITY: usize = 32;
+static RECENT_COMMANDS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Remember a command processed by the application, only the latest ones are kept.
+pub fn record_command(description: String) {
+    if let Ok(mut commands) = RECENT_COMMANDS.lock() {
+        if commands.len() == RECENT_COMMANDS_CAPACITY {
+            commands.pop_front();
+        }
+        commands.push_back(description);
+    }
+}
+/// The latest commands processed, from the oldest one.
+pub fn recent_commands() -> Vec<String> {
+    RECENT_COMMANDS
+        .lock()
+        .map(|commands| commands.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Undo the terminal setup of `main`, leaving the terminal usable.
+pub fn restore_terminal<W: Write>(writer: &mut W) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        writer,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// Install the `color-eyre` hooks. A panic restores the terminal before anything is printed,
+/// and leaves a crash report in the state directory.
+pub fn install_hooks() -> color_eyre::Result<()> {
+    let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
+    eyre_hook.install()?;
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore_terminal(&mut io::stderr());
+        log::error!("{}", panic_info);
+        eprintln!("{}", panic_hook.panic_report(panic_info));
+        let report: String = crash_report(
+            &panic_info.to_string(),
+            &Backtrace::force_capture().to_string(),
+            &recent_commands(),
+        );
+        match write_crash_report(&report) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(error) => eprintln!("Crash report could not be written: {}", error),
+        }
+    }));
+    Ok(())
+}
+
+fn crash_report(message: &str, backtrace: &str, commands: &[String]) -> String {
+    let mut report: String = format!(
+        "Glyph v{}\n\n{}\n\nRecent commands:\n",
+        env!("CARGO_PKG_VERSION"),
+        message
+    );
+    for command in commands {
+        report.push_str(&format!("  {}\n", command));
+    }
+    report.push_str(&format!("\nBacktrace:\n{}\n", backtrace));
+    report
+}
+
+fn write_crash_report(report: &str) -> io::Result<PathBuf> {
+    let dir: PathBuf = state_dir().unwrap_or_else(std::env::temp_dir);
+    fs::create_dir_all(&dir)?;
+    let seconds: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path: PathBuf = dir.join(format!("crash-{}.log", seconds));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_restore_terminal() {
+        let mut output: Vec<u8> = Vec::new();
+        restore_terminal(&mut output).unwrap();
+        let output: String = String::from_utf8(output).unwrap();
+        // Leave the alternate screen, stop capturing the mouse and show the cursor.
+        assert!(output.contains("\x1b[?1049l"));
+        assert!(output.contains("\x1b[?1000l"));
+        assert!(output.contains("\x1b[?25h"));
+    }
+    #[test]
+    fn test_crash_report() {
+        for index in 0..RECENT_COMMANDS_CAPACITY + 2 {
+            record_command(format!("Command {}", index));
+        }
+        let commands: Vec<String> = recent_commands();
+        assert_eq!(commands.len(), RECENT_COMMANDS_CAPACITY);
+        assert_eq!(
+            commands.last().unwrap(),
+            &format!("Command {}", RECENT_COMMANDS_CAPACITY + 1)
+        );
+
+        let report: String = crash_report("panicked at src/main.rs", "0: main", &commands);
+        assert!(report.contains("panicked at src/main.rs"));
+        assert!(report.contains("  Command 2\n"));
+        assert!(report.ends_with("0: main\n"));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::models::entry::Entry;
 use crate::models::layout::Layout;
 use crate::models::section::Section;
+use crate::models::template::LayoutTemplate;
 use color_eyre::{Report, Result};
-use rusqlite::{params, Connection, Row, Rows, Statement};
+use log::{debug, info};
+use rusqlite::{params, Connection, OpenFlags, Row, Rows, Statement};
 use std::path::PathBuf;
 
 pub struct GlyphRepository {}
@@ -11,17 +13,25 @@ pub(crate) struct EntryRepository {}
 
 impl GlyphRepository {
     pub fn init_glyph_db(path_to_db: &PathBuf) -> Result<Connection> {
+        info!("Open glyph {}", path_to_db.display());
         let c = Connection::open(path_to_db)?;
         c.execute(
             "
         CREATE TABLE IF NOT EXISTS entries (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            entry_name  TEXT NOT NULL UNIQUE,
-            layout      TEXT NOT NULL DEFAULT ''
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_name          TEXT NOT NULL UNIQUE,
+            layout              TEXT NOT NULL DEFAULT '',
+            shared_layout_id    INTEGER
         )
-        ",
+        ", // shared_layout_id REFERENCES layout_templates(id)
             (),
         )?;
+        if !Self::has_shared_layouts(&c)? {
+            c.execute(
+                "ALTER TABLE entries ADD COLUMN shared_layout_id INTEGER",
+                (),
+            )?;
+        }
         c.execute(
             "
         CREATE TABLE IF NOT EXISTS sections (
@@ -34,15 +44,48 @@ impl GlyphRepository {
         ", // UNIQUE (entry_id, position)
             (),
         )?;
+        c.execute(
+            "
+        CREATE TABLE IF NOT EXISTS layout_templates (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            template_name   TEXT NOT NULL UNIQUE,
+            layout          TEXT NOT NULL DEFAULT ''
+        )
+        ",
+            (),
+        )?;
         Ok(c)
     }
+
+    /// Open the glyph without creating its tables or migrating it, to inspect it as it is.
+    pub fn open_read_only(path_to_db: &PathBuf) -> Result<Connection> {
+        info!("Open glyph {} read only", path_to_db.display());
+        Ok(Connection::open_with_flags(
+            path_to_db,
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?)
+    }
+
+    /// Glyphs created before the shared layouts lack the column, and the templates table.
+    fn has_shared_layouts(c: &Connection) -> Result<bool> {
+        Ok(c.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('entries') WHERE name = 'shared_layout_id'",
+            (),
+            |row| row.get::<usize, i64>(0),
+        )? > 0)
+    }
 }
 
 impl EntryRepository {
-    pub fn create_default_entry(c: &Connection, entry_name: &str) -> color_eyre::Result<i64> {
+    pub fn create_default_entry(
+        c: &Connection,
+        entry_name: &str,
+        layout: &Layout,
+    ) -> color_eyre::Result<i64> {
+        info!("Create entry {:?}", entry_name);
         c.execute(
             "INSERT INTO entries (entry_name, layout) VALUES (?1, ?2)",
-            params![entry_name, serde_json::to_string(&Layout::new("Root"))?],
+            params![entry_name, serde_json::to_string(layout)?],
         )?;
         let eid: i64 = c.last_insert_rowid();
         Ok(eid)
@@ -50,6 +93,7 @@ impl EntryRepository {
 
     /// Insert an Entry to db, does not perform duplicated name check.
     pub fn insert(c: &Connection, entry: &Entry) -> color_eyre::Result<i64> {
+        info!("Insert entry {:?}", entry.entry_name);
         c.execute(
             "INSERT INTO entries (entry_name, layout) VALUES (?1, ?2)",
             params![entry.entry_name, serde_json::to_string(&entry.layout)?],
@@ -57,7 +101,28 @@ impl EntryRepository {
         let eid: i64 = c.last_insert_rowid();
         Ok(eid)
     }
+    /// Insert the entry back under the id it had, its sections are inserted apart.
+    pub fn insert_with_id(c: &Connection, eid: &i64, entry: &Entry) -> color_eyre::Result<()> {
+        info!("Insert entry {:?} as {}", entry.entry_name, eid);
+        c.execute(
+            "
+                INSERT INTO entries (id, entry_name, layout, shared_layout_id) VALUES (?1, ?2, ?3, ?4)
+            ",
+            params![
+                eid,
+                entry.entry_name,
+                serde_json::to_string(&entry.layout)?,
+                entry.shared_layout.as_ref().map(|(id, _name)| *id)
+            ],
+        )?;
+        Ok(())
+    }
     pub fn update(c: &Connection, eid: &i64, entry: &Entry) -> color_eyre::Result<i64> {
+        info!(
+            "Update entry {} with {} sections",
+            eid,
+            entry.sections.len()
+        );
         c.execute(
             "
                 UPDATE entries
@@ -75,7 +140,36 @@ impl EntryRepository {
 
         Ok(id)
     }
+    /// Link the entry to the shared layout, or detach it with the layout embedded instead.
+    pub fn update_shared_layout(
+        c: &Connection,
+        eid: &i64,
+        shared_layout_id: Option<i64>,
+        layout: &Layout,
+    ) -> color_eyre::Result<()> {
+        info!(
+            "Set shared layout of entry {} to {:?}",
+            eid, shared_layout_id
+        );
+        if c.execute(
+            "
+                UPDATE entries
+                SET
+                    layout = ?2,
+                    shared_layout_id = ?3
+                WHERE id = ?1
+            ",
+            params![eid, serde_json::to_string(layout)?, shared_layout_id],
+        )? != 1
+        {
+            return Err(Report::msg(
+                "Tried to update shared layout but there is no entry",
+            ));
+        }
+        Ok(())
+    }
     pub fn update_name(c: &Connection, eid: &i64, new_name: &str) -> color_eyre::Result<()> {
+        info!("Rename entry {} to {:?}", eid, new_name);
         if c.execute(
             "
                 UPDATE entries
@@ -92,18 +186,35 @@ impl EntryRepository {
     }
 
     pub fn delete(c: &Connection, eid: &i64) -> color_eyre::Result<usize> {
+        info!("Delete entry {}", eid);
         let num_of_row_deleted = c.execute("DELETE FROM entries WHERE id = ?1", params![eid])?;
         Ok(num_of_row_deleted)
     }
 
     pub fn read_by_id(c: &Connection, id: &i64) -> color_eyre::Result<(i64, Entry)> {
-        let mut stmt = c.prepare("SELECT id, entry_name, layout FROM entries WHERE id = ?1")?;
+        debug!("Read entry {}", id);
+        let mut stmt = c.prepare(
+            "
+                SELECT e.id, e.entry_name, COALESCE(t.layout, e.layout), t.id, t.template_name
+                FROM entries e LEFT JOIN layout_templates t ON t.id = e.shared_layout_id
+                WHERE e.id = ?1
+            ",
+        )?;
         let mut rows: Rows = stmt.query(params![*id])?;
         Self::map_row(c, rows.next()?.unwrap())
     }
 
     pub fn read_all(c: &Connection) -> color_eyre::Result<Vec<(i64, Entry)>> {
-        let mut stmt: Statement = c.prepare("SELECT id, entry_name, layout FROM entries")?;
+        debug!("Read all entries");
+        let query: &str = if GlyphRepository::has_shared_layouts(c)? {
+            "
+                SELECT e.id, e.entry_name, COALESCE(t.layout, e.layout), t.id, t.template_name
+                FROM entries e LEFT JOIN layout_templates t ON t.id = e.shared_layout_id
+            "
+        } else {
+            "SELECT id, entry_name, layout, NULL, NULL FROM entries"
+        };
+        let mut stmt: Statement = c.prepare(query)?;
         let mut rows: Rows = stmt.query(params![])?;
         let mut entries: Vec<(i64, Entry)> = Vec::new();
         while let Some(row) = rows.next()? {
@@ -113,16 +224,21 @@ impl EntryRepository {
         Ok(entries)
     }
 
-    // Returning (eid, entry_name, layout)
+    // Returning (eid, entry_name, layout, shared_layout_id, shared_layout_name)
     fn map_row(c: &Connection, row: &Row) -> color_eyre::Result<(i64, Entry)> {
         let id: i64 = row.get(0)?;
         let layout_string: String = row.get(2)?;
+        let shared_layout_id: Option<i64> = row.get(3)?;
         Ok((
             id,
             Entry {
                 entry_name: row.get(1)?,
                 sections: SectionRepository::read_all_by_eid(c, &id)?,
                 layout: serde_json::from_str(layout_string.as_str()).unwrap_or(Layout::new("")),
+                shared_layout: match shared_layout_id {
+                    Some(shared_layout_id) => Some((shared_layout_id, row.get(4)?)),
+                    None => None,
+                },
             },
         ))
     }
@@ -130,6 +246,7 @@ impl EntryRepository {
 pub(crate) struct SectionRepository {}
 impl SectionRepository {
     pub fn insert(c: &Connection, eid: &i64, section: &Section) -> color_eyre::Result<i64> {
+        info!("Insert section {:?} to entry {}", section.title, eid);
         c.execute(
             "
                 INSERT INTO sections (entry_id, position, title, content) VALUES (?1, ?2, ?3, ?4)
@@ -139,7 +256,28 @@ impl SectionRepository {
         let id = c.last_insert_rowid();
         Ok(id)
     }
+    /// Insert the section back under the id it had.
+    /// The sections of a deleted entry are left behind, they are replaced.
+    pub fn insert_with_id(
+        c: &Connection,
+        eid: &i64,
+        sid: &i64,
+        section: &Section,
+    ) -> color_eyre::Result<()> {
+        info!(
+            "Insert section {:?} to entry {} as {}",
+            section.title, eid, sid
+        );
+        c.execute(
+            "
+                INSERT OR REPLACE INTO sections (id, entry_id, position, title, content) VALUES (?1, ?2, ?3, ?4, ?5)
+            ",
+            params![sid, eid, section.position, section.title, section.content],
+        )?;
+        Ok(())
+    }
     pub fn update_name(c: &Connection, sid: &i64, new_name: &str) -> color_eyre::Result<()> {
+        info!("Rename section {} to {:?}", sid, new_name);
         if c.execute(
             "
                 UPDATE sections
@@ -155,6 +293,7 @@ impl SectionRepository {
         Ok(())
     }
     pub fn update(c: &Connection, sid: &i64, section: &Section) -> color_eyre::Result<i64> {
+        debug!("Update section {}", sid);
         c.execute(
             "
                 UPDATE sections
@@ -171,6 +310,7 @@ impl SectionRepository {
     }
 
     pub fn delete(c: &Connection, sid: &i64) -> color_eyre::Result<usize> {
+        info!("Delete section {}", sid);
         let num_of_row_deleted = c.execute("DELETE FROM sections WHERE id = ?1", params![sid])?;
 
         Ok(num_of_row_deleted)
@@ -180,6 +320,7 @@ impl SectionRepository {
        Return (eid, sid, section)
     */
     pub fn read_by_id(c: &Connection, id: &i64) -> color_eyre::Result<Option<(i64, i64, Section)>> {
+        debug!("Read section {}", id);
         let mut stmt =
             c.prepare("SELECT id, entry_id, position, title, content FROM sections WHERE id = ?1")?;
         let mut rows: Rows = stmt.query(params![*id])?;
@@ -190,6 +331,7 @@ impl SectionRepository {
         c: &Connection,
         entry_id: &i64,
     ) -> color_eyre::Result<Vec<(i64, Section)>> {
+        debug!("Read sections of entry {}", entry_id);
         let mut stmt = c.prepare("SELECT id, entry_id, position, title, content FROM sections WHERE entry_id = ?1 ORDER BY position ASC")?;
         let mut rows: Rows = stmt.query(params![*entry_id])?;
         let mut sections: Vec<(i64, Section)> = Vec::new();
@@ -215,3 +357,73 @@ impl SectionRepository {
         ))
     }
 }
+pub(crate) struct LayoutTemplateRepository {}
+impl LayoutTemplateRepository {
+    /// Save the layout under the name, replacing the template already saved under it.
+    /// Return the id of the template.
+    pub fn upsert(c: &Connection, template_name: &str, layout: &Layout) -> color_eyre::Result<i64> {
+        info!("Save layout template {:?}", template_name);
+        c.execute(
+            "
+                INSERT INTO layout_templates (template_name, layout) VALUES (?1, ?2)
+                ON CONFLICT (template_name) DO UPDATE SET layout = excluded.layout
+            ",
+            params![template_name, serde_json::to_string(layout)?],
+        )?;
+        let id: i64 = c.query_row(
+            "SELECT id FROM layout_templates WHERE template_name = ?1",
+            params![template_name],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+    pub fn update_layout(c: &Connection, id: &i64, layout: &Layout) -> color_eyre::Result<()> {
+        debug!("Update layout template {}", id);
+        if c.execute(
+            "UPDATE layout_templates SET layout = ?2 WHERE id = ?1",
+            params![id, serde_json::to_string(layout)?],
+        )? != 1
+        {
+            return Err(Report::msg(
+                "Tried to update layout but there is no template",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Delete the template, the entries linked to it keep a copy of its layout.
+    pub fn delete(c: &Connection, id: &i64) -> color_eyre::Result<usize> {
+        info!("Delete layout template {}", id);
+        c.execute(
+            "
+                UPDATE entries
+                SET
+                    layout = (SELECT layout FROM layout_templates WHERE id = ?1),
+                    shared_layout_id = NULL
+                WHERE shared_layout_id = ?1
+            ",
+            params![id],
+        )?;
+        let num_of_row_deleted =
+            c.execute("DELETE FROM layout_templates WHERE id = ?1", params![id])?;
+        Ok(num_of_row_deleted)
+    }
+
+    pub fn read_all(c: &Connection) -> color_eyre::Result<Vec<LayoutTemplate>> {
+        debug!("Read all layout templates");
+        let mut stmt: Statement = c.prepare(
+            "SELECT id, template_name, layout FROM layout_templates ORDER BY template_name ASC",
+        )?;
+        let mut rows: Rows = stmt.query(params![])?;
+        let mut templates: Vec<LayoutTemplate> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let layout_string: String = row.get(2)?;
+            templates.push(LayoutTemplate {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                layout: serde_json::from_str(layout_string.as_str()).unwrap_or(Layout::new("")),
+            });
+        }
+        Ok(templates)
+    }
+}
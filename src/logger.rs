@@ -0,0 +1,115 @@
+use crate::utils::state_dir;
+use color_eyre::eyre::{Report, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of the latest lines kept in memory for the log popup.
+pub const RECENT_LINES_CAPACITY: usize = 500;
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Append the records to a file, keeping the latest lines around.
+struct FileLogger {
+    level: LevelFilter,
+    file: Mutex<File>,
+}
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line: String = format_record(record);
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", line);
+        }
+        if let Ok(mut lines) = RECENT_LINES.lock() {
+            if lines.len() == RECENT_LINES_CAPACITY {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Install the file logger. The level is read from `GLYPH_LOG` (`off`, `error` ... `trace`),
+/// the file from `GLYPH_LOG_FILE`, default to `glyph.log` in the state directory.
+/// Return the path of the log file.
+pub fn init() -> Result<PathBuf> {
+    let level: LevelFilter = match std::env::var("GLYPH_LOG") {
+        Ok(value) => LevelFilter::from_str(value.trim())
+            .map_err(|_| Report::msg(format!("Invalid GLYPH_LOG level: {}", value)))?,
+        Err(_) => DEFAULT_LEVEL,
+    };
+    let path: PathBuf = match std::env::var_os("GLYPH_LOG_FILE") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => state_dir()
+            .ok_or(Report::msg("No directory to write the log in"))?
+            .join("glyph.log"),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file: File = OpenOptions::new().create(true).append(true).open(&path)?;
+    let logger: &'static FileLogger = Box::leak(Box::new(FileLogger {
+        level,
+        file: Mutex::new(file),
+    }));
+    log::set_logger(logger).map_err(|error| Report::msg(error.to_string()))?;
+    log::set_max_level(level);
+    Ok(path)
+}
+
+/// The latest lines logged, from the oldest one.
+pub fn recent_lines() -> Vec<String> {
+    RECENT_LINES
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn format_record(record: &Record) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!(
+        "{}.{:03} {:<5} {}: {}",
+        timestamp.as_secs(),
+        timestamp.subsec_millis(),
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn test_format_record() {
+        let line: String = format_record(
+            &Record::builder()
+                .level(Level::Warn)
+                .target("glyph::db")
+                .args(format_args!("Delete entry {}", 1))
+                .build(),
+        );
+        assert!(line.ends_with(" WARN  glyph::db: Delete entry 1"));
+    }
+}
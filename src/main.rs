@@ -1,29 +1,43 @@
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture, Event};
+use crossterm::event::{EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event};
 use crossterm::execute;
-use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
-};
+use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use std::{fs, io};
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Report, Result};
 use ratatui::backend::Backend;
 use ratatui::prelude::CrosstermBackend;
+use ratatui::style::Color;
 use ratatui::Terminal;
 use rusqlite::Connection;
 
 mod app;
+mod config;
+mod crash;
 mod db;
+mod logger;
 mod models;
+mod recent;
 mod services;
 mod theme;
 mod utils;
 
-use crate::app::{draw, handle_key_events};
-use crate::db::GlyphRepository;
-use app::Application;
+use crate::app::event::{AppEvent, TICK_RATE};
+use crate::app::keymap;
+use crate::app::popup::message_popup::MessagePopup;
+use crate::app::{
+    draw, handle_app_events, handle_key_events, handle_mouse_events, handle_paste_events,
+    handle_timers, record_recent_glyphs,
+};
+use crate::config::Config;
+use crate::db::{EntryRepository, GlyphRepository};
+use crate::theme::Theme;
+use app::{AppState, Application};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    crash::install_hooks()?;
+    let logger_result = logger::init();
     let args: Vec<String> = std::env::args().collect();
     let cli_result: (bool, Option<Connection>) = handle_cli(&args)?;
     if cli_result.0 {
@@ -33,23 +47,72 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Init
     enable_raw_mode()?;
     let mut stderr = io::stderr();
-    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stderr,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stderr);
     let mut terminal = Terminal::new(backend)?;
     // Main
-    let mut app: Application = Application::new();
-    if cli_result.1.is_some() {
-        app = Application::from(cli_result.1.unwrap());
+    let (user_themes, theme_reports) = theme::load_user_themes();
+    let config_result: Result<Config> = Config::load(&available_themes(&user_themes));
+    let state: AppState = AppState::new(
+        config_result.as_ref().cloned().unwrap_or_default(),
+        user_themes,
+    );
+    let connection: Option<Connection> = match cli_result.1 {
+        Some(connection) => Some(connection),
+        None if state.config.restore_session => last_session(),
+        None => None,
+    };
+    let mut app: Application = match connection {
+        Some(connection) => Application::from(connection, state),
+        None => Application::new(state),
+    };
+    match logger_result {
+        Ok(path) => log::info!(
+            "Glyph v{} started, logging to {}",
+            env!("CARGO_PKG_VERSION"),
+            path.display()
+        ),
+        Err(report) => app.popup_states.push(
+            MessagePopup::new(
+                format!("Nothing is logged.\n{}", report).as_str(),
+                Color::Red,
+            )
+            .into(),
+        ),
+    }
+    if let Err(report) = config_result {
+        app.popup_states.push(
+            MessagePopup::new(
+                format!("Default config is used.\n{}", config_problems(&report)).as_str(),
+                Color::Red,
+            )
+            .into(),
+        );
+    }
+    for report in theme_reports {
+        app.popup_states.push(
+            MessagePopup::new(format!("Theme skipped.\n{:#}", report).as_str(), Color::Red).into(),
+        );
+    }
+    if let Err(report) = keymap::init() {
+        app.popup_states.push(
+            MessagePopup::new(
+                format!("Default key bindings are used.\n{}", report).as_str(),
+                Color::Red,
+            )
+            .into(),
+        );
     }
     let result = run(&mut terminal, &mut app);
+    record_recent_glyphs(&mut app);
     // Restore
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    crash::restore_terminal(terminal.backend_mut())?;
     // Process the result
     match result {
         Ok(_m) => println!("Glyph Exit"),
@@ -59,11 +122,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn run<B: Backend>(terminal: &mut Terminal<B>, app: &mut Application) -> io::Result<bool> {
+    let mut last_tick: Instant = Instant::now();
     loop {
         terminal.draw(|frame| draw(frame, app));
-        if let Event::Key(key) = crossterm::event::read()? {
-            handle_key_events(&key, app);
+        // Wake up for the next tick, or earlier if a timer is due.
+        let mut deadline: Instant = last_tick + TICK_RATE;
+        if let Some(timer_deadline) = app.timers.next_deadline() {
+            deadline = deadline.min(timer_deadline);
+        }
+        let timeout: Duration = deadline.saturating_duration_since(Instant::now());
+        if crossterm::event::poll(timeout)? {
+            match crossterm::event::read()? {
+                Event::Key(key) => handle_key_events(&key, app),
+                Event::Mouse(mouse) => handle_mouse_events(&mouse, app),
+                Event::Resize(width, height) => {
+                    handle_app_events(&AppEvent::Resize(width, height), app)
+                }
+                Event::FocusGained => handle_app_events(&AppEvent::FocusGained, app),
+                Event::FocusLost => handle_app_events(&AppEvent::FocusLost, app),
+                Event::Paste(text) => handle_paste_events(&text, app),
+            }
+        }
+        if last_tick.elapsed() >= TICK_RATE {
+            handle_app_events(&AppEvent::Tick, app);
+            last_tick = Instant::now();
         }
+        handle_timers(app);
         if app.state.should_quit {
             break;
         }
@@ -99,10 +183,113 @@ fn handle_cli(args: &Vec<String>) -> Result<(bool, Option<Connection>)> {
                 let connection = GlyphRepository::init_glyph_db(&PathBuf::from(glyph_path))?;
                 return Ok((false, Some(connection)));
             }
+            "config" => {
+                handle_config_cli(args.get(2).map(String::as_str))?;
+                return Ok((true, None));
+            }
+            "check" => {
+                let Some(glyph_path) = args.get(2) else {
+                    println!("Usage: glyph check <glyph-name>");
+                    return Ok((true, None));
+                };
+                if !fs::exists(glyph_path)? {
+                    println!("Glyph does not exist: {}", glyph_path);
+                    return Ok((true, None));
+                };
+                check_glyph(PathBuf::from(glyph_path))?;
+                return Ok((true, None));
+            }
             _ => {
-                println!("Invalid Command\nAvailable commands: \n - new\n - delete\n - open")
+                println!(
+                    "Invalid Command\nAvailable commands: \n - new\n - delete\n - open\n - config\n - check"
+                )
             }
         }
     }
     Ok((true, None))
 }
+
+/// `glyph config path|show|check`
+fn handle_config_cli(command: Option<&str>) -> Result<()> {
+    let path: PathBuf = Config::path().ok_or(Report::msg(
+        "No config directory, neither XDG_CONFIG_HOME nor HOME is set",
+    ))?;
+    match command {
+        Some("path") => println!("{}", path.display()),
+        Some("show") => {
+            let (user_themes, _theme_reports) = theme::load_user_themes();
+            let config: Config = Config::load(&available_themes(&user_themes))?;
+            print!("{}", toml::to_string(&config)?);
+        }
+        Some("check") => {
+            let (user_themes, theme_reports) = theme::load_user_themes();
+            let mut is_valid: bool = theme_reports.is_empty();
+            for report in theme_reports {
+                println!("{}", config_problems(&report));
+            }
+            if let Err(report) = Config::load(&available_themes(&user_themes)) {
+                is_valid = false;
+                println!("{}", config_problems(&report));
+            }
+            if !is_valid {
+                std::process::exit(1);
+            }
+            if path.exists() {
+                println!("{} is valid", path.display());
+            } else {
+                println!("{} does not exist, the defaults are used", path.display());
+            }
+        }
+        _ => println!("Available config commands: \n - path\n - show\n - check"),
+    }
+    Ok(())
+}
+
+/// `glyph check <path>`, list the layout problems of every entry.
+fn check_glyph(path: PathBuf) -> Result<()> {
+    let connection: Connection = GlyphRepository::open_read_only(&path)?;
+    let mut is_valid: bool = true;
+    for (_eid, entry) in EntryRepository::read_all(&connection)? {
+        let warnings: Vec<String> = entry.layout.check(&entry.sections);
+        if warnings.is_empty() {
+            continue;
+        }
+        is_valid = false;
+        println!("{}:", entry.entry_name);
+        for warning in warnings {
+            println!("  - {}", warning);
+        }
+    }
+    if !is_valid {
+        std::process::exit(1);
+    }
+    println!("{} has no layout problems", path.display());
+    Ok(())
+}
+
+/// Reopen the latest glyph, unless it has been removed since.
+fn last_session() -> Option<Connection> {
+    let path: PathBuf = recent::load().into_iter().next()?.path;
+    if !path.exists() {
+        return None;
+    }
+    GlyphRepository::init_glyph_db(&path)
+        .inspect_err(|report| log::warn!("Last session is not restored: {}", report))
+        .ok()
+}
+
+/// The built-in themes followed by the user ones, for the config to pick from.
+fn available_themes(user_themes: &[Theme]) -> Vec<Theme> {
+    let mut themes: Vec<Theme> = Theme::builtins();
+    themes.extend_from_slice(user_themes);
+    themes
+}
+
+/// Every message of the report on its own line, from the outermost one.
+fn config_problems(report: &Report) -> String {
+    report
+        .chain()
+        .map(|cause| cause.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,3 +1,7 @@
 pub mod entry;
+pub mod history;
 pub mod layout;
+pub mod layout_check;
+pub mod layout_dsl;
 pub mod section;
+pub mod template;
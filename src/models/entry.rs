@@ -9,6 +9,8 @@ pub struct Entry {
     pub entry_name: String,
     pub sections: Vec<(i64, Section)>,
     pub layout: Layout,
+    /// Id and name of the shared layout the entry is linked to, its layout follows the shared one.
+    pub shared_layout: Option<(i64, String)>,
 }
 
 impl Entry {
@@ -0,0 +1,115 @@
+/*
+   History
+*/
+use crate::models::entry::Entry;
+use crate::models::layout::Layout;
+use crate::models::section::Section;
+
+/// Changes kept at most, the oldest ones are forgotten first.
+const HISTORY_LIMIT: usize = 100;
+
+/// What is needed to take a change back, it holds the state before the change.
+#[derive(Clone)]
+pub enum Revision {
+    Layout {
+        eid: i64,
+        layout: Layout,
+    },
+    /// (sid, position) of the sections moved.
+    SectionPositions {
+        eid: i64,
+        positions: Vec<(i64, i64)>,
+    },
+    SectionCreated {
+        eid: i64,
+        sid: i64,
+    },
+    SectionDeleted {
+        eid: i64,
+        sid: i64,
+        section: Section,
+    },
+    SectionRenamed {
+        sid: i64,
+        title: String,
+    },
+    EntryRenamed {
+        eid: i64,
+        name: String,
+    },
+    EntryCreated {
+        eid: i64,
+    },
+    /// The entry as last saved, with its unsaved changes which are only brought back in memory.
+    EntryDeleted {
+        eid: i64,
+        saved: Entry,
+        unsaved: Option<Box<Entry>>,
+    },
+}
+
+/// Changes which could be undone, and the undone ones which could be redone.
+/// Each is described by the action which made it in the first place.
+#[derive(Default)]
+pub struct History {
+    undo_stack: Vec<(String, Revision)>,
+    redo_stack: Vec<(String, Revision)>,
+}
+
+impl History {
+    /// Keep a new change, the changes undone could not be redone anymore.
+    pub fn record(&mut self, description: &str, revision: Revision) {
+        self.undo_stack.push((description.to_string(), revision));
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+    pub fn pop_undo(&mut self) -> Option<(String, Revision)> {
+        self.undo_stack.pop()
+    }
+    pub fn pop_redo(&mut self) -> Option<(String, Revision)> {
+        self.redo_stack.pop()
+    }
+    /// Keep the change reverting an undone one, to redo it.
+    pub fn push_redo(&mut self, description: String, revision: Revision) {
+        self.redo_stack.push((description, revision));
+    }
+    /// Keep the change reverting a redone one, to undo it again.
+    pub fn push_undo(&mut self, description: String, revision: Revision) {
+        self.undo_stack.push((description, revision));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_history_limit() {
+        let mut history: History = History::default();
+        for eid in 0..=HISTORY_LIMIT as i64 {
+            history.record("Copy Entry", Revision::EntryCreated { eid });
+        }
+        let (description, revision) = history.pop_undo().unwrap();
+        assert_eq!(description, "Copy Entry");
+        history.push_redo(description, revision);
+        assert!(history.pop_redo().is_some());
+
+        // A new change drops the changes undone.
+        let (description, revision) = history.pop_undo().unwrap();
+        history.push_redo(description, revision);
+        history.record("Copy Entry", Revision::EntryCreated { eid: -1 });
+        assert!(history.pop_redo().is_none());
+
+        // Two changes undone and one recorded since the limit was reached.
+        let mut count: usize = 0;
+        while let Some((_description, revision)) = history.pop_undo() {
+            if let Revision::EntryCreated { eid } = revision {
+                assert!(eid != 0, "The oldest change is forgotten");
+            }
+            count += 1;
+        }
+        assert_eq!(count, HISTORY_LIMIT - 1);
+    }
+}
@@ -1,8 +1,9 @@
 use color_eyre::eyre::Result;
 use color_eyre::Report;
+use ratatui::layout::Constraint;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Layout {
     pub label: String,
     pub section_index: Option<u16>,
@@ -26,7 +27,7 @@ impl Layout {
         }
         let mut temp_layout: &Layout = self;
         while let Some(index) = coor.pop() {
-            temp_layout = &temp_layout.sub_layouts[index];
+            temp_layout = temp_layout.sub_layouts.get(index)?;
         }
         if coor.is_empty() {
             Some(temp_layout)
@@ -42,7 +43,7 @@ impl Layout {
         }
         let mut temp_layout: &mut Layout = self;
         while let Some(index) = coor.pop() {
-            temp_layout = &mut temp_layout.sub_layouts[index];
+            temp_layout = temp_layout.sub_layouts.get_mut(index)?;
         }
         if coor.is_empty() {
             Some(temp_layout)
@@ -93,17 +94,178 @@ impl Layout {
             )))
         }
     }
+    /// Swap the layout with the sibling `offset` away, return the new coordinates of the layout.
+    /// It stays in place when there is no such sibling.
+    pub fn swap_sublayout(
+        &mut self,
+        coordinates: &Vec<usize>,
+        offset: isize,
+    ) -> Result<Vec<usize>> {
+        let mut coor = coordinates.clone();
+        let index = coor
+            .pop()
+            .ok_or(Report::msg("The root layout has no sibling"))?;
+        let parent: &mut Layout = self.get_layout_at_mut(&coor).ok_or(Report::msg(format!(
+            "Could not find sub layout. At {:?}",
+            coordinates
+        )))?;
+        let Some(other_index) = index
+            .checked_add_signed(offset)
+            .filter(|other_index| *other_index < parent.sub_layouts.len())
+        else {
+            return Ok(coordinates.clone());
+        };
+        parent.sub_layouts.swap(index, other_index);
+        coor.push(other_index);
+        Ok(coor)
+    }
+    /// Move the layout to the end of another parent, return the new coordinates of the layout.
+    pub fn move_sublayout(
+        &mut self,
+        coordinates: &Vec<usize>,
+        parent_coordinates: &Vec<usize>,
+    ) -> Result<Vec<usize>> {
+        if coordinates.is_empty() || parent_coordinates.starts_with(coordinates) {
+            return Err(Report::msg("Could not move a layout into itself"));
+        }
+        if self.get_layout_at_ref(coordinates).is_none()
+            || self.get_layout_at_ref(parent_coordinates).is_none()
+        {
+            return Err(Report::msg(format!(
+                "Could not find sub layout. At {:?} or {:?}",
+                coordinates, parent_coordinates
+            )));
+        }
+        let mut coor = coordinates.clone();
+        let index = coor.pop().unwrap();
+        let layout: Layout = self
+            .get_layout_at_mut(&coor)
+            .unwrap()
+            .sub_layouts
+            .remove(index);
+        // The later siblings of the moved layout, and their children, are shifted back by one.
+        let mut new_parent_coordinates = parent_coordinates.clone();
+        if new_parent_coordinates.len() > coor.len()
+            && new_parent_coordinates.starts_with(&coor)
+            && new_parent_coordinates[coor.len()] > index
+        {
+            new_parent_coordinates[coor.len()] -= 1;
+        }
+        let parent: &mut Layout = self.get_layout_at_mut(&new_parent_coordinates).unwrap();
+        parent.sub_layouts.push(layout);
+        new_parent_coordinates.push(parent.sub_layouts.len() - 1);
+        Ok(new_parent_coordinates)
+    }
+    /// Wrap the layout in a new container, which takes over its size within the parent.
+    pub fn wrap_sublayout(&mut self, coordinates: &Vec<usize>) -> Result<()> {
+        let target: &mut Layout =
+            self.get_layout_at_mut(coordinates)
+                .ok_or(Report::msg(format!(
+                    "Could not find sub layout. At {:?}",
+                    coordinates
+                )))?;
+        let mut container: Layout = Layout::new("");
+        container.details.set_size(&target.details);
+        target.details.set_size(&LayoutDetails::new());
+        let layout: Layout = std::mem::replace(target, container);
+        target.sub_layouts.push(layout);
+        Ok(())
+    }
+    /// Replace the container by its children, a single child takes over its size within the parent.
+    pub fn unwrap_sublayout(&mut self, coordinates: &Vec<usize>) -> Result<()> {
+        let mut coor = coordinates.clone();
+        let index = coor
+            .pop()
+            .ok_or(Report::msg("Could not unwrap the root layout"))?;
+        let parent: &mut Layout = self.get_layout_at_mut(&coor).ok_or(Report::msg(format!(
+            "Could not find sub layout. At {:?}",
+            coordinates
+        )))?;
+        if index >= parent.sub_layouts.len() {
+            return Err(Report::msg(format!(
+                "Could not find sub layout. At {:?}",
+                coordinates
+            )));
+        }
+        if parent.sub_layouts[index].sub_layouts.is_empty() {
+            return Err(Report::msg("Could not unwrap a layout without children"));
+        }
+        let container: Layout = parent.sub_layouts.remove(index);
+        let mut children: Vec<Layout> = container.sub_layouts;
+        if children.len() == 1 {
+            children[0].details.set_size(&container.details);
+        }
+        parent.sub_layouts.splice(index..index, children);
+        Ok(())
+    }
+    /// Insert a copy of the layout and its children right after it, return the coordinates of the copy.
+    pub fn duplicate_sublayout(&mut self, coordinates: &Vec<usize>) -> Result<Vec<usize>> {
+        let mut coor = coordinates.clone();
+        let index = coor
+            .pop()
+            .ok_or(Report::msg("Could not duplicate the root layout"))?;
+        let parent: &mut Layout = self.get_layout_at_mut(&coor).ok_or(Report::msg(format!(
+            "Could not find sub layout. At {:?}",
+            coordinates
+        )))?;
+        let layout: Layout = parent
+            .sub_layouts
+            .get(index)
+            .ok_or(Report::msg(format!(
+                "Could not find sub layout. At {:?}",
+                coordinates
+            )))?
+            .clone();
+        parent.sub_layouts.insert(index + 1, layout);
+        coor.push(index + 1);
+        Ok(coor)
+    }
+    /// The widths just below every breakpoint of the layout and its children, widest first.
+    pub fn breakpoint_widths(&self) -> Vec<u16> {
+        let mut widths: Vec<u16> = self
+            .details
+            .breakpoints
+            .iter()
+            .map(|breakpoint| breakpoint.below.saturating_sub(1))
+            .chain(self.sub_layouts.iter().flat_map(Layout::breakpoint_widths))
+            .collect();
+        widths.sort_unstable_by(|a, b| b.cmp(a));
+        widths.dedup();
+        widths
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub enum LayoutOrientation {
     Horizontal,
     Vertical,
+    /// The children share the whole area, one at a time, and are switched between like tabs.
+    Stack,
+}
+/// A rule of a layout applied while the view is narrower than `below` columns.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub below: u16,
+    pub rule: BreakpointRule,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum BreakpointRule {
+    /// Lay the children out along another axis.
+    Orientation(LayoutOrientation),
+    /// Leave the layout out, its siblings share the space.
+    Hide,
+}
+/// How a layout is sized along the main axis of its parent.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub enum SizeMode {
     Length,
     Flex,
+    Percentage,
+    Ratio,
+    /// At least `length` cells, growing with the space left.
+    Min,
+    /// At most `length` cells.
+    Max,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub enum BorderMode {
@@ -112,16 +274,26 @@ pub enum BorderMode {
     Dashed,
     Rounded,
 }
-#[derive(Serialize, Deserialize, Clone)]
+/// The keys missing from a layout saved by an older version take their default value.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct LayoutDetails {
     pub size_mode: SizeMode,
     pub border_mode: BorderMode,
+    /// Cells of the `Length`, `Min` and `Max` modes.
     pub length: u16,
+    /// Size a `Length` panel to the height of its section instead, when laid vertically.
+    pub fit_content: bool,
     pub flex: u16,
+    pub percentage: u16,
+    /// Numerator and denominator of the `Ratio` mode.
+    pub ratio: (u32, u32),
     pub padding: u16,
     pub margin: u16,
 
     pub orientation: LayoutOrientation, // Describing orientation main axis for the children
+    /// Rules applied in place of the above while the view is narrower than their width.
+    pub breakpoints: Vec<Breakpoint>,
 }
 
 impl LayoutDetails {
@@ -130,11 +302,218 @@ impl LayoutDetails {
             size_mode: SizeMode::Flex,
             border_mode: BorderMode::None,
             length: 42,
+            fit_content: false,
             flex: 1,
+            percentage: 50,
+            ratio: (1, 2),
             padding: 0,
             margin: 0,
 
             orientation: LayoutOrientation::Vertical,
+            breakpoints: Vec::new(),
         }
     }
+    /// The orientation of the children in a view `width` columns wide, the narrowest breakpoint
+    /// applying wins.
+    pub fn orientation_at(&self, width: u16) -> &LayoutOrientation {
+        self.breakpoints
+            .iter()
+            .filter(|breakpoint| width < breakpoint.below)
+            .filter_map(|breakpoint| match &breakpoint.rule {
+                BreakpointRule::Orientation(orientation) => Some((breakpoint.below, orientation)),
+                BreakpointRule::Hide => None,
+            })
+            .min_by_key(|(below, _orientation)| *below)
+            .map(|(_below, orientation)| orientation)
+            .unwrap_or(&self.orientation)
+    }
+    /// Whether the layout is left out of a view `width` columns wide.
+    pub fn is_hidden_at(&self, width: u16) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|breakpoint| width < breakpoint.below && breakpoint.rule == BreakpointRule::Hide)
+    }
+    /// The constraint of the layout within its parent.
+    pub fn constraint(&self) -> Constraint {
+        match self.size_mode {
+            SizeMode::Length => Constraint::Length(self.length),
+            SizeMode::Flex => Constraint::Fill(self.flex),
+            SizeMode::Percentage => Constraint::Percentage(self.percentage),
+            SizeMode::Ratio => Constraint::Ratio(self.ratio.0, self.ratio.1),
+            SizeMode::Min => Constraint::Min(self.length),
+            SizeMode::Max => Constraint::Max(self.length),
+        }
+    }
+    /// Take the size of the other layout within its parent, leaving the rest untouched.
+    fn set_size(&mut self, other: &LayoutDetails) {
+        self.size_mode = other.size_mode.clone();
+        self.length = other.length;
+        self.fit_content = other.fit_content;
+        self.flex = other.flex;
+        self.percentage = other.percentage;
+        self.ratio = other.ratio;
+    }
+}
+/// Parse a ratio written like `1/3`, the denominator must not be zero.
+pub fn parse_ratio(value: &str) -> Option<(u32, u32)> {
+    let (numerator, denominator) = value.split_once('/')?;
+    let numerator: u32 = numerator.trim().parse().ok()?;
+    let denominator: u32 = denominator.trim().parse().ok()?;
+    (denominator > 0).then_some((numerator, denominator))
+}
+impl Default for LayoutDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_legacy_layout() {
+        // A layout saved before the percentage and ratio were introduced.
+        let legacy: &str = r#"{"label":"root","section_index":null,"sub_layouts":[{"label":"side","section_index":0,"sub_layouts":[],"details":{"size_mode":"Length","border_mode":"Rounded","length":24,"flex":1,"padding":1,"margin":0,"orientation":"Vertical"}}],"details":{"size_mode":"Flex","border_mode":"None","length":42,"flex":2,"padding":0,"margin":1,"orientation":"Horizontal"}}"#;
+        let layout: Layout = serde_json::from_str(legacy).unwrap();
+        assert_eq!(layout.label, "root");
+        assert!(layout.details.size_mode == SizeMode::Flex);
+        assert_eq!(layout.details.flex, 2);
+        assert_eq!(layout.details.margin, 1);
+        assert!(layout.details.orientation == LayoutOrientation::Horizontal);
+        assert_eq!(layout.details.percentage, 50);
+        assert_eq!(layout.details.ratio, (1, 2));
+        assert!(!layout.details.fit_content);
+        let side: &Layout = &layout.sub_layouts[0];
+        assert_eq!(side.section_index, Some(0));
+        assert!(side.details.border_mode == BorderMode::Rounded);
+        assert_eq!(side.details.constraint(), Constraint::Length(24));
+
+        // The new modes survive a round trip.
+        let mut details: LayoutDetails = LayoutDetails::new();
+        details.size_mode = SizeMode::Ratio;
+        details.ratio = (1, 3);
+        let mut details: LayoutDetails =
+            serde_json::from_str(&serde_json::to_string(&details).unwrap()).unwrap();
+        assert_eq!(details.constraint(), Constraint::Ratio(1, 3));
+        details.size_mode = SizeMode::Max;
+        details.length = 10;
+        assert_eq!(details.constraint(), Constraint::Max(10));
+
+        assert_eq!(parse_ratio("1 / 3"), Some((1, 3)));
+        assert_eq!(parse_ratio("1/0"), None);
+        assert_eq!(parse_ratio("1"), None);
+    }
+
+    /// Labels of the layout and its children, like `root(a,b(c))`.
+    fn outline(layout: &Layout) -> String {
+        if layout.sub_layouts.is_empty() {
+            return layout.label.clone();
+        }
+        let children: Vec<String> = layout.sub_layouts.iter().map(outline).collect();
+        format!("{}({})", layout.label, children.join(","))
+    }
+
+    #[test]
+    fn test_restructure_layout() {
+        let mut root: Layout = Layout::new("root");
+        root.sub_layouts = vec![Layout::new("a"), Layout::new("b"), Layout::new("c")];
+        root.insert_sublayout_under(Layout::new("d"), &vec![1])
+            .unwrap();
+        assert_eq!(outline(&root), "root(a,b(d),c)");
+        assert_eq!(root.get_layout_at_mut(&vec![1, 0]).unwrap().label, "d");
+        assert!(root.get_layout_at_mut(&vec![1, 1]).is_none());
+        assert!(root.get_layout_at_ref(&vec![3]).is_none());
+
+        // Swap with the siblings, staying in place at either end.
+        assert_eq!(root.swap_sublayout(&vec![0], 1).unwrap(), vec![1]);
+        assert_eq!(outline(&root), "root(b(d),a,c)");
+        assert_eq!(root.swap_sublayout(&vec![2], 1).unwrap(), vec![2]);
+        assert_eq!(root.swap_sublayout(&vec![0], -1).unwrap(), vec![0]);
+        assert!(root.swap_sublayout(&vec![], 1).is_err());
+
+        // Move to a later parent, whose coordinates shift once the layout is taken out.
+        assert_eq!(root.move_sublayout(&vec![1], &vec![2]).unwrap(), vec![1, 0]);
+        assert_eq!(outline(&root), "root(b(d),c(a))");
+        assert_eq!(
+            root.move_sublayout(&vec![1, 0], &vec![0]).unwrap(),
+            vec![0, 1]
+        );
+        assert_eq!(outline(&root), "root(b(d,a),c)");
+        assert!(root.move_sublayout(&vec![0], &vec![0, 1]).is_err());
+        assert!(root.move_sublayout(&vec![0], &vec![5]).is_err());
+        assert_eq!(outline(&root), "root(b(d,a),c)");
+
+        // Wrapping hands the size over to the container, unwrapping gives it back.
+        let c: &mut Layout = root.get_layout_at_mut(&vec![1]).unwrap();
+        c.details.size_mode = SizeMode::Length;
+        c.details.length = 7;
+        c.details.border_mode = BorderMode::Plain;
+        root.wrap_sublayout(&vec![1]).unwrap();
+        assert_eq!(outline(&root), "root(b(d,a),(c))");
+        let container: &Layout = root.get_layout_at_ref(&vec![1]).unwrap();
+        assert_eq!(container.details.constraint(), Constraint::Length(7));
+        assert!(container.details.border_mode == BorderMode::None);
+        let c: &Layout = root.get_layout_at_ref(&vec![1, 0]).unwrap();
+        assert_eq!(c.details.constraint(), Constraint::Fill(1));
+        assert!(c.details.border_mode == BorderMode::Plain);
+        root.unwrap_sublayout(&vec![1]).unwrap();
+        assert_eq!(outline(&root), "root(b(d,a),c)");
+        assert_eq!(
+            root.get_layout_at_ref(&vec![1])
+                .unwrap()
+                .details
+                .constraint(),
+            Constraint::Length(7)
+        );
+        root.unwrap_sublayout(&vec![0]).unwrap();
+        assert_eq!(outline(&root), "root(d,a,c)");
+        assert!(root.unwrap_sublayout(&vec![0]).is_err());
+        assert!(root.unwrap_sublayout(&vec![]).is_err());
+        root.wrap_sublayout(&vec![]).unwrap();
+        assert_eq!(outline(&root), "(root(d,a,c))");
+
+        // Duplicate a whole subtree right after itself.
+        assert_eq!(root.duplicate_sublayout(&vec![0]).unwrap(), vec![1]);
+        assert_eq!(outline(&root), "(root(d,a,c),root(d,a,c))");
+        assert!(root.duplicate_sublayout(&vec![]).is_err());
+        assert!(root.duplicate_sublayout(&vec![2]).is_err());
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut root: Layout = Layout::new("root");
+        root.details.orientation = LayoutOrientation::Horizontal;
+        root.details.breakpoints = vec![
+            Breakpoint {
+                below: 100,
+                rule: BreakpointRule::Orientation(LayoutOrientation::Vertical),
+            },
+            Breakpoint {
+                below: 40,
+                rule: BreakpointRule::Orientation(LayoutOrientation::Stack),
+            },
+        ];
+        let mut side: Layout = Layout::new("side");
+        side.details.breakpoints = vec![Breakpoint {
+            below: 60,
+            rule: BreakpointRule::Hide,
+        }];
+        root.sub_layouts = vec![side, Layout::new("main")];
+
+        // The narrowest breakpoint applying wins, whatever their order.
+        assert!(*root.details.orientation_at(100) == LayoutOrientation::Horizontal);
+        assert!(*root.details.orientation_at(99) == LayoutOrientation::Vertical);
+        assert!(*root.details.orientation_at(39) == LayoutOrientation::Stack);
+        root.details.breakpoints.reverse();
+        assert!(*root.details.orientation_at(39) == LayoutOrientation::Stack);
+        assert!(!root.sub_layouts[0].details.is_hidden_at(60));
+        assert!(root.sub_layouts[0].details.is_hidden_at(59));
+        assert!(!root.details.is_hidden_at(0));
+        assert_eq!(root.breakpoint_widths(), vec![99, 59, 39]);
+
+        // Layouts saved before the breakpoints have none.
+        let details: LayoutDetails = serde_json::from_str(r#"{"orientation":"Stack"}"#).unwrap();
+        assert!(details.breakpoints.is_empty());
+    }
 }
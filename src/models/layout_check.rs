@@ -0,0 +1,183 @@
+/*
+   Layout Check
+
+   Problems of a layout against the sections of its entry, which would otherwise go unnoticed in
+   the read mode: a panel targeting a position no section has, two panels targeting the same
+   position, a section no panel targets, a panel of zero size and layouts nested too deep.
+*/
+use crate::models::layout::{Layout, SizeMode};
+use crate::models::section::Section;
+use std::collections::HashMap;
+
+/// Layouts nested deeper than this are reported, their panels get too small to be read.
+pub const MAX_LAYOUT_DEPTH: usize = 6;
+
+impl Layout {
+    /// Describe every problem of the layout drawing the sections, in the order of the panels then
+    /// of the sections left out.
+    pub fn check(&self, sections: &[(i64, Section)]) -> Vec<String> {
+        let mut warnings: Vec<String> = Vec::new();
+        // (position, name) of every panel drawing a section.
+        let mut targets: Vec<(u16, String)> = Vec::new();
+        check_layout(self, Vec::new(), &mut targets, &mut warnings);
+
+        // Only the first panel targeting a position draws the section.
+        let mut first_targets: HashMap<u16, &String> = HashMap::new();
+        for (position, name) in &targets {
+            if let Some(first) = first_targets.get(position) {
+                warnings.push(format!(
+                    "{} targets position {} already drawn by {}, it stays empty",
+                    capitalize(name),
+                    position,
+                    first
+                ));
+                continue;
+            }
+            first_targets.insert(*position, name);
+            if !sections
+                .iter()
+                .any(|(_sid, section)| section.position == *position as i64)
+            {
+                warnings.push(format!(
+                    "{} targets position {}, which no section has",
+                    capitalize(name),
+                    position
+                ));
+            }
+        }
+        for (_sid, section) in sections {
+            if !targets
+                .iter()
+                .any(|(position, _name)| *position as i64 == section.position)
+            {
+                warnings.push(format!(
+                    "Section \"{}\" at position {} is in no panel, it is never drawn",
+                    section.title, section.position
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+fn check_layout(
+    layout: &Layout,
+    at: Vec<usize>,
+    targets: &mut Vec<(u16, String)>,
+    warnings: &mut Vec<String>,
+) {
+    // Only the top layout too deep is reported, not each of its children.
+    if at.len() == MAX_LAYOUT_DEPTH + 1 {
+        warnings.push(format!(
+            "{} is nested {} levels deep, more than {}",
+            capitalize(&describe(layout, &at)),
+            at.len(),
+            MAX_LAYOUT_DEPTH
+        ));
+    }
+    let details = &layout.details;
+    let is_zero_size: bool = match details.size_mode {
+        SizeMode::Length => details.length == 0 && !details.fit_content,
+        SizeMode::Max => details.length == 0,
+        SizeMode::Percentage => details.percentage == 0,
+        SizeMode::Ratio => details.ratio.0 == 0,
+        SizeMode::Flex | SizeMode::Min => false,
+    };
+    // The root fills the view whatever its size.
+    if is_zero_size && !at.is_empty() {
+        warnings.push(format!(
+            "{} has a size of zero, it is never drawn",
+            capitalize(&describe(layout, &at))
+        ));
+    }
+    // A container does not draw the section it targets, only a panel without children does.
+    if layout.sub_layouts.is_empty() {
+        if let Some(position) = layout.section_index {
+            targets.push((position, describe(layout, &at)));
+        }
+        return;
+    }
+    for (i, sub_layout) in layout.sub_layouts.iter().enumerate() {
+        let mut sub_at: Vec<usize> = at.clone();
+        sub_at.push(i);
+        check_layout(sub_layout, sub_at, targets, warnings);
+    }
+}
+
+/// Name the layout by its label when it has one, and by its place otherwise, counted from 1.
+fn describe(layout: &Layout, coordinate: &[usize]) -> String {
+    let place: String = coordinate
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<String>>()
+        .join(".");
+    match (layout.label.is_empty(), coordinate.is_empty()) {
+        (true, true) => String::from("the root layout"),
+        (true, false) => format!("the layout at {}", place),
+        (false, true) => format!("layout \"{}\"", layout.label),
+        (false, false) => format!("layout \"{}\" at {}", layout.label, place),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::layout::LayoutOrientation;
+
+    fn panel(label: &str, position: u16) -> Layout {
+        let mut layout: Layout = Layout::new(label);
+        layout.section_index = Some(position);
+        layout
+    }
+
+    #[test]
+    fn test_layout_check() {
+        let sections: Vec<(i64, Section)> = vec![
+            (1, Section::new("Notes", "", 0)),
+            (2, Section::new("Summary", "", 1)),
+            (3, Section::new("Cues", "", 2)),
+        ];
+        let mut root: Layout = Layout::new("");
+        root.details.orientation = LayoutOrientation::Horizontal;
+        root.sub_layouts = vec![panel("Main", 0), panel("", 1)];
+        assert!(root.check(&sections[..2]).is_empty());
+
+        // The container does not place its section, the third one targets a position twice.
+        let mut side: Layout = panel("Side", 2);
+        side.sub_layouts = vec![panel("", 0), panel("", 4)];
+        side.details.size_mode = SizeMode::Length;
+        side.details.length = 0;
+        root.sub_layouts.push(side);
+        assert_eq!(
+            root.check(&sections),
+            vec![
+                "Layout \"Side\" at 3 has a size of zero, it is never drawn",
+                "The layout at 3.1 targets position 0 already drawn by layout \"Main\" at 1, it stays empty",
+                "The layout at 3.2 targets position 4, which no section has",
+                "Section \"Cues\" at position 2 is in no panel, it is never drawn",
+            ]
+        );
+
+        // A panel fitted to its section is sized by it, the nesting is reported once per path.
+        let side: &mut Layout = root.get_layout_at_mut(&vec![2]).unwrap();
+        side.details.fit_content = true;
+        side.sub_layouts = vec![panel("", 2)];
+        for _ in 0..MAX_LAYOUT_DEPTH {
+            let mut container: Layout = Layout::new("");
+            container.sub_layouts = vec![root.sub_layouts.pop().unwrap()];
+            root.sub_layouts.push(container);
+        }
+        assert_eq!(
+            root.check(&sections),
+            vec!["Layout \"Side\" at 3.1.1.1.1.1.1 is nested 7 levels deep, more than 6"]
+        );
+    }
+}
@@ -0,0 +1,547 @@
+/*
+   Layout DSL
+
+   A layout written as text, for example:
+
+       row[
+         col(flex=2)[
+           @1
+           @2
+         ]
+         @3(len=20, border=rounded)
+       ]
+
+   `row`, `col` and `stack` lay their children out horizontally, vertically or stacked,
+   `@N` is a panel showing the section at position N and `_` an empty panel.
+   Attributes: `label="..."`, `section=N`, one size among `flex=N`, `len=N`, `min=N`, `max=N`,
+   `pct=N` and `ratio=N/M`, `fit`, `border=none|plain|dashed|rounded`, `pad=N` and `margin=N`.
+   `below=N:row|col|stack` lays the children out otherwise and `below=N:hide` hides the layout
+   while the view is narrower than N columns, it may be given several times.
+   `#` starts a comment running to the end of the line.
+*/
+use crate::models::layout::{
+    BorderMode, Breakpoint, BreakpointRule, Layout, LayoutDetails, LayoutOrientation, SizeMode,
+};
+use color_eyre::eyre::Result;
+use color_eyre::Report;
+
+const ATTRIBUTES: &str =
+    "label, section, flex, len, min, max, pct, ratio, fit, border, pad, margin, below";
+
+impl Layout {
+    /// Write the layout as DSL, a child per line.
+    /// Only the size of the mode in use is written, the values of the other modes are left out.
+    pub fn to_dsl(&self) -> String {
+        let mut text: String = String::new();
+        write_layout(self, 0, &mut text);
+        text
+    }
+    /// Read a layout written as DSL, the error tells the line and column where it went wrong.
+    pub fn from_dsl(text: &str) -> Result<Layout> {
+        let mut parser: Parser = Parser::new(text);
+        let layout: Layout = parser.parse_layout()?;
+        parser.skip_blank();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!(
+                "expected the end of the layout, found `{}`, the root layout must be the only one",
+                c
+            )));
+        }
+        Ok(layout)
+    }
+}
+
+fn write_layout(layout: &Layout, depth: usize, text: &mut String) {
+    let is_panel: bool =
+        layout.sub_layouts.is_empty() && layout.details.orientation == LayoutOrientation::Vertical;
+    let mut attributes: Vec<String> = Vec::new();
+    if !layout.label.is_empty() {
+        attributes.push(format!("label={}", quote(&layout.label)));
+    }
+    let head: String = match (is_panel, layout.section_index) {
+        (true, Some(position)) => format!("@{}", position),
+        (true, None) => String::from("_"),
+        (false, section_index) => {
+            if let Some(position) = section_index {
+                attributes.push(format!("section={}", position));
+            }
+            String::from(orientation_word(&layout.details.orientation))
+        }
+    };
+    let details: &LayoutDetails = &layout.details;
+    match details.size_mode {
+        SizeMode::Flex if details.flex == 1 => {}
+        SizeMode::Flex => attributes.push(format!("flex={}", details.flex)),
+        SizeMode::Length => attributes.push(format!("len={}", details.length)),
+        SizeMode::Min => attributes.push(format!("min={}", details.length)),
+        SizeMode::Max => attributes.push(format!("max={}", details.length)),
+        SizeMode::Percentage => attributes.push(format!("pct={}", details.percentage)),
+        SizeMode::Ratio => {
+            attributes.push(format!("ratio={}/{}", details.ratio.0, details.ratio.1))
+        }
+    }
+    if details.fit_content {
+        attributes.push(String::from("fit"));
+    }
+    match details.border_mode {
+        BorderMode::None => {}
+        BorderMode::Plain => attributes.push(String::from("border=plain")),
+        BorderMode::Dashed => attributes.push(String::from("border=dashed")),
+        BorderMode::Rounded => attributes.push(String::from("border=rounded")),
+    }
+    if details.padding != 0 {
+        attributes.push(format!("pad={}", details.padding));
+    }
+    if details.margin != 0 {
+        attributes.push(format!("margin={}", details.margin));
+    }
+    for breakpoint in &details.breakpoints {
+        let rule: &str = match &breakpoint.rule {
+            BreakpointRule::Orientation(orientation) => orientation_word(orientation),
+            BreakpointRule::Hide => "hide",
+        };
+        attributes.push(format!("below={}:{}", breakpoint.below, rule));
+    }
+
+    text.push_str(&"  ".repeat(depth));
+    text.push_str(&head);
+    if !attributes.is_empty() {
+        text.push_str(&format!("({})", attributes.join(", ")));
+    }
+    if !layout.sub_layouts.is_empty() {
+        text.push_str("[\n");
+        for sub_layout in &layout.sub_layouts {
+            write_layout(sub_layout, depth + 1, text);
+            text.push('\n');
+        }
+        text.push_str(&"  ".repeat(depth));
+        text.push(']');
+    }
+}
+
+fn orientation_word(orientation: &LayoutOrientation) -> &'static str {
+    match orientation {
+        LayoutOrientation::Horizontal => "row",
+        LayoutOrientation::Vertical => "col",
+        LayoutOrientation::Stack => "stack",
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Reads the DSL a char at a time, keeping track of the line and column for the errors.
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+    fn next(&mut self) -> Option<char> {
+        let c: char = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+    fn error(&self, message: String) -> Report {
+        Report::msg(format!(
+            "Line {}, column {}: {}",
+            self.line, self.column, message
+        ))
+    }
+    /// Describe the next char for an error, the end of the text included.
+    fn found(&self) -> String {
+        match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => String::from("the end of the layout"),
+        }
+    }
+    /// Skip the whitespaces and the comments.
+    fn skip_blank(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.next();
+                }
+            } else if c.is_whitespace() {
+                self.next();
+            } else {
+                break;
+            }
+        }
+    }
+    fn expect(&mut self, expected: char, context: &str) -> Result<()> {
+        self.skip_blank();
+        if self.peek() == Some(expected) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(format!(
+                "expected `{}` {}, found {}",
+                expected,
+                context,
+                self.found()
+            )))
+        }
+    }
+    fn word(&mut self) -> String {
+        let mut word: String = String::new();
+        while let Some(c) = self.peek()
+            && (c.is_alphanumeric() || c == '_')
+        {
+            word.push(c);
+            self.next();
+        }
+        word
+    }
+    fn number<T: std::str::FromStr>(&mut self, name: &str) -> Result<T> {
+        self.skip_blank();
+        let mut digits: String = String::new();
+        while let Some(c) = self.peek()
+            && c.is_ascii_digit()
+        {
+            digits.push(c);
+            self.next();
+        }
+        if digits.is_empty() {
+            return Err(self.error(format!(
+                "expected a number for `{}`, found {}",
+                name,
+                self.found()
+            )));
+        }
+        digits
+            .parse::<T>()
+            .map_err(|_| self.error(format!("`{}` is too large for `{}`", digits, name)))
+    }
+    fn string(&mut self, name: &str) -> Result<String> {
+        self.skip_blank();
+        if self.peek() != Some('"') {
+            return Err(self.error(format!(
+                "expected a quoted text for `{}`, found {}",
+                name,
+                self.found()
+            )));
+        }
+        self.next();
+        let mut value: String = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+        Err(self.error(format!("the text of `{}` is never closed by `\"`", name)))
+    }
+
+    fn parse_layout(&mut self) -> Result<Layout> {
+        self.skip_blank();
+        let mut layout: Layout = Layout::new("");
+        if self.peek() == Some('@') {
+            self.next();
+            layout.section_index = Some(self.number("@")?);
+        } else {
+            match self.word().as_str() {
+                "row" => layout.details.orientation = LayoutOrientation::Horizontal,
+                "col" | "_" => layout.details.orientation = LayoutOrientation::Vertical,
+                "stack" => layout.details.orientation = LayoutOrientation::Stack,
+                "" => {
+                    return Err(self.error(format!(
+                        "expected a layout, `row`, `col`, `stack`, `@N` or `_`, found {}",
+                        self.found()
+                    )));
+                }
+                word => {
+                    return Err(self.error(format!(
+                        "unknown layout `{}`, expected `row`, `col`, `stack`, `@N` or `_`",
+                        word
+                    )));
+                }
+            }
+        }
+        self.skip_blank();
+        if self.peek() == Some('(') {
+            self.next();
+            self.parse_attributes(&mut layout)?;
+        }
+        self.skip_blank();
+        if self.peek() == Some('[') {
+            let (line, column) = (self.line, self.column);
+            self.next();
+            loop {
+                self.skip_blank();
+                match self.peek() {
+                    Some(']') => {
+                        self.next();
+                        break;
+                    }
+                    None => {
+                        return Err(self.error(format!(
+                            "expected `]` to close the `[` at line {}, column {}",
+                            line, column
+                        )));
+                    }
+                    Some(_) => layout.sub_layouts.push(self.parse_layout()?),
+                }
+            }
+        }
+        Ok(layout)
+    }
+
+    fn parse_attributes(&mut self, layout: &mut Layout) -> Result<()> {
+        // Name of the attribute setting the size, a layout has only one.
+        let mut size: Option<String> = None;
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(')') {
+                self.next();
+                return Ok(());
+            }
+            let name: String = self.word();
+            if name.is_empty() {
+                return Err(self.error(format!(
+                    "expected an attribute or `)`, found {}",
+                    self.found()
+                )));
+            }
+            if matches!(
+                name.as_str(),
+                "flex" | "len" | "min" | "max" | "pct" | "ratio"
+            ) {
+                if let Some(other) = &size {
+                    return Err(self.error(format!(
+                        "`{}` conflicts with `{}`, a layout has only one size",
+                        name, other
+                    )));
+                }
+                size = Some(name.clone());
+            }
+            let details: &mut LayoutDetails = &mut layout.details;
+            if name == "fit" {
+                details.fit_content = true;
+            } else {
+                self.expect('=', &format!("after `{}`", name))?;
+                match name.as_str() {
+                    "label" => layout.label = self.string(&name)?,
+                    "section" => layout.section_index = Some(self.number(&name)?),
+                    "flex" => {
+                        details.size_mode = SizeMode::Flex;
+                        details.flex = self.number(&name)?;
+                    }
+                    "len" => {
+                        details.size_mode = SizeMode::Length;
+                        details.length = self.number(&name)?;
+                    }
+                    "min" => {
+                        details.size_mode = SizeMode::Min;
+                        details.length = self.number(&name)?;
+                    }
+                    "max" => {
+                        details.size_mode = SizeMode::Max;
+                        details.length = self.number(&name)?;
+                    }
+                    "pct" => {
+                        details.size_mode = SizeMode::Percentage;
+                        details.percentage = self.number(&name)?;
+                    }
+                    "ratio" => {
+                        details.size_mode = SizeMode::Ratio;
+                        let numerator: u32 = self.number(&name)?;
+                        self.expect('/', "between the numerator and the denominator")?;
+                        let denominator: u32 = self.number(&name)?;
+                        if denominator == 0 {
+                            return Err(self.error(String::from(
+                                "the denominator of `ratio` must not be zero",
+                            )));
+                        }
+                        details.ratio = (numerator, denominator);
+                    }
+                    "border" => {
+                        self.skip_blank();
+                        details.border_mode = match self.word().as_str() {
+                            "none" => BorderMode::None,
+                            "plain" => BorderMode::Plain,
+                            "dashed" => BorderMode::Dashed,
+                            "rounded" => BorderMode::Rounded,
+                            word => {
+                                return Err(self.error(format!(
+                                    "unknown border `{}`, expected `none`, `plain`, `dashed` or `rounded`",
+                                    word
+                                )));
+                            }
+                        };
+                    }
+                    "pad" => details.padding = self.number(&name)?,
+                    "margin" => details.margin = self.number(&name)?,
+                    "below" => {
+                        let below: u16 = self.number(&name)?;
+                        self.expect(':', "between the width and the rule")?;
+                        self.skip_blank();
+                        let rule: BreakpointRule = match self.word().as_str() {
+                            "row" => BreakpointRule::Orientation(LayoutOrientation::Horizontal),
+                            "col" => BreakpointRule::Orientation(LayoutOrientation::Vertical),
+                            "stack" => BreakpointRule::Orientation(LayoutOrientation::Stack),
+                            "hide" => BreakpointRule::Hide,
+                            word => {
+                                return Err(self.error(format!(
+                                    "unknown rule `{}`, expected `row`, `col`, `stack` or `hide`",
+                                    word
+                                )));
+                            }
+                        };
+                        details.breakpoints.push(Breakpoint { below, rule });
+                    }
+                    _ => {
+                        return Err(self.error(format!(
+                            "unknown attribute `{}`, expected one of {}",
+                            name, ATTRIBUTES
+                        )));
+                    }
+                }
+            }
+            self.skip_blank();
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some(')') => {}
+                _ => {
+                    return Err(self.error(format!(
+                        "expected `,` or `)` after `{}`, found {}",
+                        name,
+                        self.found()
+                    )));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::template::LayoutTemplate;
+
+    #[test]
+    fn test_layout_dsl_round_trip() {
+        let layout: Layout =
+            Layout::from_dsl("row[ col(flex=2)[@1 @2] @3(len=20, border=rounded) ] # the example")
+                .unwrap();
+        let mut expected: Layout = Layout::new("");
+        expected.details.orientation = LayoutOrientation::Horizontal;
+        let mut column: Layout = Layout::new("");
+        column.details.flex = 2;
+        let mut first: Layout = Layout::new("");
+        first.section_index = Some(1);
+        let mut second: Layout = Layout::new("");
+        second.section_index = Some(2);
+        column.sub_layouts = vec![first, second];
+        let mut third: Layout = Layout::new("");
+        third.section_index = Some(3);
+        third.details.size_mode = SizeMode::Length;
+        third.details.length = 20;
+        third.details.border_mode = BorderMode::Rounded;
+        expected.sub_layouts = vec![column, third];
+        assert_eq!(
+            serde_json::to_string(&layout).unwrap(),
+            serde_json::to_string(&expected).unwrap()
+        );
+
+        let mut layouts: Vec<Layout> = LayoutTemplate::builtins()
+            .into_iter()
+            .map(|template| template.layout)
+            .collect();
+        let mut odd: Layout = Layout::new("a \"quoted\" \\ label");
+        odd.section_index = Some(7);
+        odd.details.orientation = LayoutOrientation::Stack;
+        odd.details.size_mode = SizeMode::Ratio;
+        odd.details.ratio = (2, 3);
+        odd.details.padding = 2;
+        odd.details.margin = 1;
+        let mut fit: Layout = Layout::new("");
+        fit.details.size_mode = SizeMode::Length;
+        fit.details.fit_content = true;
+        fit.details.border_mode = BorderMode::Dashed;
+        let mut row: Layout = Layout::new("");
+        row.details.orientation = LayoutOrientation::Horizontal;
+        row.details.size_mode = SizeMode::Max;
+        row.details.length = 10;
+        row.details.breakpoints = vec![
+            Breakpoint {
+                below: 100,
+                rule: BreakpointRule::Orientation(LayoutOrientation::Vertical),
+            },
+            Breakpoint {
+                below: 60,
+                rule: BreakpointRule::Hide,
+            },
+        ];
+        odd.sub_layouts = vec![fit, row, Layout::new("")];
+        layouts.push(odd);
+        for layout in layouts {
+            let dsl: String = layout.to_dsl();
+            let parsed: Layout = Layout::from_dsl(&dsl).unwrap();
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                serde_json::to_string(&layout).unwrap(),
+                "{}",
+                dsl
+            );
+            assert_eq!(parsed.to_dsl(), dsl);
+        }
+    }
+
+    #[test]
+    fn test_layout_dsl_errors() {
+        let error = |text: &str| Layout::from_dsl(text).err().unwrap().to_string();
+        assert_eq!(
+            error("row[\n  @1\n"),
+            "Line 3, column 1: expected `]` to close the `[` at line 1, column 4"
+        );
+        assert_eq!(
+            error("row(flex=2, len=3)"),
+            "Line 1, column 16: `len` conflicts with `flex`, a layout has only one size"
+        );
+        assert_eq!(
+            error("col(border=thick)"),
+            "Line 1, column 17: unknown border `thick`, expected `none`, `plain`, `dashed` or `rounded`"
+        );
+        assert_eq!(
+            error("@1 @2"),
+            "Line 1, column 4: expected the end of the layout, found `@`, the root layout must be the only one"
+        );
+        assert!(error("col(len=99999)").contains("too large"));
+        assert!(error("col(ratio=1/0)").contains("must not be zero"));
+        assert!(error("grid[]").contains("unknown layout `grid`"));
+        assert!(error("col(label=\"open").contains("never closed"));
+        assert_eq!(
+            error("row(below=80:grid)"),
+            "Line 1, column 18: unknown rule `grid`, expected `row`, `col`, `stack` or `hide`"
+        );
+        assert!(error("row(below=80)").contains("expected `:` between the width and the rule"));
+    }
+}
@@ -1,7 +1,9 @@
 /*
    Section
 */
-#[derive(Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Section {
     // pub entry_id: i64,
     pub position: i64,
@@ -0,0 +1,128 @@
+/*
+   Layout Template
+*/
+use crate::models::layout::{BorderMode, Layout, LayoutOrientation, SizeMode};
+use crate::models::section::Section;
+
+/// A layout kept under a name, to lay out entries the same way.
+#[derive(Clone)]
+pub struct LayoutTemplate {
+    /// Id of the template saved in the glyph, none for the built-in ones.
+    pub id: Option<i64>,
+    pub name: String,
+    pub layout: Layout,
+}
+
+impl LayoutTemplate {
+    pub fn new(name: &str, layout: Layout) -> Self {
+        Self {
+            id: None,
+            name: name.to_string(),
+            layout,
+        }
+    }
+    /// Presets available in every glyph.
+    pub fn builtins() -> Vec<LayoutTemplate> {
+        let mut two_column: Layout = Layout::new("Root");
+        two_column.details.orientation = LayoutOrientation::Horizontal;
+        two_column.sub_layouts = vec![panel("Left", 1), panel("Right", 2)];
+
+        let mut sidebar: Layout = Layout::new("Root");
+        sidebar.details.orientation = LayoutOrientation::Horizontal;
+        let mut side: Layout = panel("Sidebar", 2);
+        side.details.size_mode = SizeMode::Length;
+        side.details.length = 28;
+        sidebar.sub_layouts = vec![side, panel("Main", 1)];
+
+        let mut dashboard: Layout = Layout::new("Root");
+        let mut top: Layout = Layout::new("Top");
+        top.details.orientation = LayoutOrientation::Horizontal;
+        top.sub_layouts = vec![panel("Top Left", 1), panel("Top Right", 2)];
+        let mut bottom: Layout = Layout::new("Bottom");
+        bottom.details.orientation = LayoutOrientation::Horizontal;
+        bottom.sub_layouts = vec![panel("Bottom Left", 3), panel("Bottom Right", 4)];
+        dashboard.sub_layouts = vec![top, bottom];
+
+        let mut cornell: Layout = Layout::new("Root");
+        let mut body: Layout = Layout::new("Body");
+        body.details.orientation = LayoutOrientation::Horizontal;
+        let mut cues: Layout = panel("Cues", 1);
+        cues.details.size_mode = SizeMode::Percentage;
+        cues.details.percentage = 30;
+        body.sub_layouts = vec![cues, panel("Notes", 2)];
+        let mut summary: Layout = panel("Summary", 3);
+        summary.details.size_mode = SizeMode::Length;
+        summary.details.length = 8;
+        cornell.sub_layouts = vec![body, summary];
+
+        vec![
+            LayoutTemplate::new("Two Column", two_column),
+            LayoutTemplate::new("Sidebar", sidebar),
+            LayoutTemplate::new("Dashboard Grid", dashboard),
+            LayoutTemplate::new("Cornell Notes", cornell),
+        ]
+    }
+    /// An empty section for each panel of the layout, titled after the panel.
+    pub fn sections(&self) -> Vec<Section> {
+        let mut sections: Vec<Section> = Vec::new();
+        let mut layouts: Vec<&Layout> = vec![&self.layout];
+        while let Some(layout) = layouts.pop() {
+            if let Some(position) = layout.section_index
+                && layout.sub_layouts.is_empty()
+                && !sections
+                    .iter()
+                    .any(|section| section.position == position as i64)
+            {
+                sections.push(Section::new(&layout.label, "", position as i64));
+            }
+            layouts.extend(layout.sub_layouts.iter().rev());
+        }
+        sections.sort_by_key(|section| section.position);
+        sections
+    }
+}
+
+/// A bordered panel showing the section at the position.
+fn panel(label: &str, position: u16) -> Layout {
+    let mut layout: Layout = Layout::new(label);
+    layout.section_index = Some(position);
+    layout.details.border_mode = BorderMode::Rounded;
+    layout.details.padding = 1;
+    layout
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builtin_sections() {
+        let templates: Vec<LayoutTemplate> = LayoutTemplate::builtins();
+        let cornell: &LayoutTemplate = templates
+            .iter()
+            .find(|template| template.name == "Cornell Notes")
+            .unwrap();
+        let titles: Vec<(i64, String)> = cornell
+            .sections()
+            .into_iter()
+            .map(|section| (section.position, section.title))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                (1, String::from("Cues")),
+                (2, String::from("Notes")),
+                (3, String::from("Summary")),
+            ]
+        );
+        // The templates survive being saved.
+        for template in templates {
+            let layout: Layout =
+                serde_json::from_str(&serde_json::to_string(&template.layout).unwrap()).unwrap();
+            assert_eq!(
+                LayoutTemplate::new(&template.name, layout).sections().len(),
+                template.sections().len()
+            );
+        }
+    }
+}
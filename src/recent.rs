@@ -0,0 +1,124 @@
+use crate::app::page::glyph_view::GlyphMode;
+use crate::utils::state_dir;
+use color_eyre::eyre::{Report, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of glyphs remembered, one per digit key of the entrance page.
+pub const RECENT_GLYPHS_CAPACITY: usize = 9;
+
+/// A glyph opened lately, along with where it was left.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecentGlyph {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub active_entry_id: Option<i64>,
+    #[serde(default)]
+    pub mode: GlyphMode,
+    /// Vertical scroll of the read view.
+    #[serde(default)]
+    pub scroll_offset: u16,
+}
+
+/// `recent.json` in the state directory.
+pub fn recent_glyphs_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("recent.json"))
+}
+
+/// The glyphs opened lately, from the latest one. Nothing is remembered when the file is unreadable.
+pub fn load() -> Vec<RecentGlyph> {
+    let Some(path) = recent_glyphs_path().filter(|path| path.exists()) else {
+        return Vec::new();
+    };
+    match fs::read_to_string(&path)
+        .map_err(Report::new)
+        .and_then(|content| serde_json::from_str(&content).map_err(Report::new))
+    {
+        Ok(recent_glyphs) => recent_glyphs,
+        Err(report) => {
+            log::warn!("Recent glyphs are ignored, {}: {}", path.display(), report);
+            Vec::new()
+        }
+    }
+}
+
+/// Where the glyph was left the last time, if it is remembered.
+pub fn find(path: &Path) -> Option<RecentGlyph> {
+    let path: PathBuf = normalize(path);
+    load()
+        .into_iter()
+        .find(|recent_glyph| recent_glyph.path == path)
+}
+
+/// Remember the glyph as the latest one, return the glyphs remembered.
+pub fn record(recent_glyph: RecentGlyph) -> Result<Vec<RecentGlyph>> {
+    let path: PathBuf = recent_glyphs_path().ok_or(Report::msg("No state directory"))?;
+    let mut recent_glyphs: Vec<RecentGlyph> = load();
+    push(&mut recent_glyphs, recent_glyph);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&recent_glyphs)?)?;
+    Ok(recent_glyphs)
+}
+
+/// Move the glyph to the front, dropping the oldest ones beyond the capacity.
+fn push(recent_glyphs: &mut Vec<RecentGlyph>, mut recent_glyph: RecentGlyph) {
+    recent_glyph.path = normalize(&recent_glyph.path);
+    recent_glyphs.retain(|other| other.path != recent_glyph.path);
+    recent_glyphs.insert(0, recent_glyph);
+    recent_glyphs.truncate(RECENT_GLYPHS_CAPACITY);
+}
+
+/// The same glyph could be opened from different working directories.
+pub fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn recent_glyph(path: &str) -> RecentGlyph {
+        RecentGlyph {
+            path: PathBuf::from(path),
+            active_entry_id: None,
+            mode: GlyphMode::Read,
+            scroll_offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_push_recent_glyph() {
+        let mut recent_glyphs: Vec<RecentGlyph> = Vec::new();
+        for index in 0..RECENT_GLYPHS_CAPACITY + 1 {
+            push(
+                &mut recent_glyphs,
+                recent_glyph(&format!("/nowhere/{}.glyph", index)),
+            );
+        }
+        assert_eq!(recent_glyphs.len(), RECENT_GLYPHS_CAPACITY);
+        assert_eq!(recent_glyphs[0].path, PathBuf::from("/nowhere/9.glyph"));
+
+        // Reopening a glyph moves it to the front with its new position.
+        let mut reopened: RecentGlyph = recent_glyph("/nowhere/5.glyph");
+        reopened.active_entry_id = Some(3);
+        reopened.mode = GlyphMode::Edit;
+        push(&mut recent_glyphs, reopened.clone());
+        assert_eq!(recent_glyphs.len(), RECENT_GLYPHS_CAPACITY);
+        assert_eq!(recent_glyphs[0], reopened);
+        assert_eq!(
+            recent_glyphs
+                .iter()
+                .filter(|other| other.path == reopened.path)
+                .count(),
+            1
+        );
+
+        // Only the path is required.
+        let recent_glyphs: Vec<RecentGlyph> =
+            serde_json::from_str(r#"[{ "path": "/nowhere/0.glyph" }]"#).unwrap();
+        assert_eq!(recent_glyphs[0], recent_glyph("/nowhere/0.glyph"));
+    }
+}
@@ -1,10 +1,24 @@
-use crate::db::{EntryRepository, SectionRepository};
+use crate::db::{EntryRepository, LayoutTemplateRepository, SectionRepository};
 use crate::models::entry::Entry;
+use crate::models::history::{History, Revision};
+use crate::models::layout::Layout;
 use crate::models::section::Section;
+use crate::models::template::LayoutTemplate;
 use crate::utils::auto_increment_name;
 use color_eyre::{Report, Result};
 use rusqlite::Connection;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Unsaved content of an entry, written to the recovery journal.
+#[derive(Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub eid: i64,
+    pub layout: Layout,
+    pub sections: Vec<(i64, Section)>,
+}
 
 pub struct LocalEntryState {
     /// All entries in the database.
@@ -16,6 +30,10 @@ pub struct LocalEntryState {
     pub updated_entries: HashSet<i64>,
     /// Holding entry id and entry name in specific order.
     pub ordered_entries: Vec<(i64, String)>,
+    /// Changes which could be undone and redone.
+    pub history: History,
+    /// Layout and section positions of each entry as of its last recorded change.
+    snapshots: HashMap<i64, (Layout, Vec<(i64, i64)>)>,
 }
 
 impl LocalEntryState {
@@ -28,8 +46,14 @@ impl LocalEntryState {
             active_entry_id: None,
             ordered_entries: Vec::new(),
             updated_entries: HashSet::new(),
+            history: History::default(),
+            snapshots: HashMap::new(),
         };
         me.reconstruct_entry_order();
+        let eids: Vec<i64> = me.entries.iter().map(|(eid, _entry)| *eid).collect();
+        for eid in &eids {
+            me.take_snapshot(eid);
+        }
         me
     }
 
@@ -55,19 +79,40 @@ impl LocalEntryState {
 
     /// Create a default entry in the database, this function interact and update database.
     pub fn create_default_entry_db(&mut self, entry_name: &str) -> Result<i64> {
+        self.create_entry_with_layout_db(entry_name, &Layout::new("Root"))
+    }
+    /// Create an entry laid out by the template, with an empty section for each of its panels.
+    pub fn create_entry_from_template_db(
+        &mut self,
+        entry_name: &str,
+        template: &LayoutTemplate,
+    ) -> Result<i64> {
+        let eid: i64 = self.create_entry_with_layout_db(entry_name, &template.layout)?;
+        for section in template.sections() {
+            self.insert_section(&eid, section)?;
+        }
+        Ok(eid)
+    }
+    fn create_entry_with_layout_db(&mut self, entry_name: &str, layout: &Layout) -> Result<i64> {
         let name_list: Vec<&str> = self
             .ordered_entries
             .iter()
             .map(|(_eid, name)| name.as_str())
             .collect::<Vec<&str>>();
         let new_entry_name: String = auto_increment_name(entry_name, name_list.as_slice());
-        let entry_id: i64 =
-            EntryRepository::create_default_entry(&self.connection, new_entry_name.as_str())?;
+        let entry_id: i64 = EntryRepository::create_default_entry(
+            &self.connection,
+            new_entry_name.as_str(),
+            layout,
+        )?;
         let entry_result = EntryRepository::read_by_id(&self.connection, &entry_id);
         match entry_result {
             Ok((eid, entry)) => {
                 self.entries.push((eid, entry));
                 self.reconstruct_entry_order();
+                self.take_snapshot(&eid);
+                self.history
+                    .record("Create Entry", Revision::EntryCreated { eid });
                 Ok(eid)
             }
             Err(e) => Err(e),
@@ -89,11 +134,24 @@ impl LocalEntryState {
             Ok((eid, entry)) => {
                 self.entries.push((eid, entry));
                 self.reconstruct_entry_order();
+                self.take_snapshot(&eid);
                 Ok(eid)
             }
             Err(e) => Err(e),
         }
     }
+    /// Insert a copy of the entry along with its sections, it could come from another glyph.
+    pub fn insert_entry_with_sections(&mut self, entry: Entry) -> Result<i64> {
+        let sections: Vec<(i64, Section)> = entry.sections.clone();
+        let eid: i64 = self.insert_entry(entry)?;
+        for (_sid, section) in sections {
+            self.insert_section(&eid, section)?;
+        }
+        self.take_snapshot(&eid);
+        self.history
+            .record("Copy Entry", Revision::EntryCreated { eid });
+        Ok(eid)
+    }
 
     /// Update entry's name by its id, this function interact and update database.
     pub fn update_entry_name_db(&mut self, eid: &i64, new_name: &str) -> Result<()> {
@@ -107,8 +165,11 @@ impl LocalEntryState {
         let (eid, entry) = EntryRepository::read_by_id(&self.connection, eid)?;
 
         let current_entry: &mut Entry = self.get_entry_mut(&eid).unwrap();
+        let name: String = current_entry.entry_name.clone();
         current_entry.update_name(&entry);
         self.reconstruct_entry_order();
+        self.history
+            .record("Rename Entry", Revision::EntryRenamed { eid, name });
         Ok(())
     }
 
@@ -116,31 +177,96 @@ impl LocalEntryState {
     pub fn save_entry_db(&mut self, eid: &i64) -> Result<()> {
         let entry: &Entry = self.get_entry_ref(eid).unwrap();
         EntryRepository::update(&self.connection, eid, entry)?;
+        if let Some((shared_layout_id, _name)) = &entry.shared_layout {
+            LayoutTemplateRepository::update_layout(
+                &self.connection,
+                shared_layout_id,
+                &entry.layout,
+            )?;
+        }
         Ok(())
     }
 
+    /// Save every unsaved entry to the database, return the number of entries saved.
+    pub fn save_updated_entries_db(&mut self) -> Result<usize> {
+        let mut eids: Vec<i64> = self.updated_entries.iter().copied().collect();
+        eids.sort();
+        for eid in &eids {
+            if self.get_entry_ref(eid).is_some() {
+                self.save_entry_db(eid)?;
+            }
+            self.updated_entries.remove(eid);
+        }
+        self.write_journal()?;
+        Ok(eids.len())
+    }
+
+    /// Mark the entry as changed or saved, the recovery journal is kept in sync.
+    /// Changes to the layout and to the order of the sections are recorded to be undone.
+    pub fn set_entry_unsaved(&mut self, eid: i64, is_unsaved: bool) -> Result<()> {
+        if is_unsaved {
+            self.updated_entries.insert(eid);
+            self.record_changes(&eid);
+            self.sync_shared_layout(&eid);
+        } else {
+            self.updated_entries.remove(&eid);
+        }
+        self.write_journal()
+    }
+
+    /// Names of the unsaved entries, in the order of the navigation bar.
+    pub fn get_unsaved_entry_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(eid, _entry)| self.updated_entries.contains(eid))
+            .map(|(_eid, entry)| entry.entry_name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Update the database by deleting corresponding Entry pointed by the eid parameter.
     pub fn delete_active_entry_db(&mut self) -> Result<usize> {
-        if let Some(id) = self.active_entry_id {
-            let result = EntryRepository::delete(&self.connection, &id);
-            let mut remove_index: i8 = -1;
-            for (index, entry) in self.entries.iter().enumerate() {
-                if entry.0 == id {
-                    remove_index = index as i8;
-                    break;
-                }
-            }
-            if remove_index == -1 {
-                return Err(Report::msg("Entry could not be found"));
-            }
-            self.entries.remove(remove_index as usize);
-            self.reconstruct_entry_order();
-            self.active_entry_id = None;
-            result
+        if let Some(eid) = self.active_entry_id {
+            let revision: Revision = self.deleted_entry_revision(&eid)?;
+            let result: usize = self.delete_entry_db(&eid)?;
+            self.history.record("Delete Entry", revision);
+            Ok(result)
         } else {
             Err(Report::msg("No active entry found"))
         }
     }
+    /// Keep the entry about to be deleted as it is saved, apart from its unsaved changes.
+    fn deleted_entry_revision(&self, eid: &i64) -> Result<Revision> {
+        let entry: &Entry = self
+            .get_entry_ref(eid)
+            .ok_or(Report::msg("Entry could not be found"))?;
+        let (_eid, saved) = EntryRepository::read_by_id(&self.connection, eid)?;
+        Ok(Revision::EntryDeleted {
+            eid: *eid,
+            saved,
+            unsaved: self
+                .updated_entries
+                .contains(eid)
+                .then(|| Box::new(entry.clone())),
+        })
+    }
+    fn delete_entry_db(&mut self, eid: &i64) -> Result<usize> {
+        let result = EntryRepository::delete(&self.connection, eid);
+        let remove_index: usize = self
+            .entries
+            .iter()
+            .position(|(_eid, _entry)| *_eid == *eid)
+            .ok_or(Report::msg("Entry could not be found"))?;
+        self.entries.remove(remove_index);
+        self.snapshots.remove(eid);
+        self.reconstruct_entry_order();
+        if self.active_entry_id == Some(*eid) {
+            self.active_entry_id = None;
+        }
+        result
+    }
 
     /// Update the current active entry eid.
     pub fn toggle_active_entry_id(&mut self, id: i64) {
@@ -214,7 +340,9 @@ impl LocalEntryState {
             SectionRepository::read_by_id(&self.connection, sid)?.unwrap();
 
         let current_section: &mut Section = self.get_section_mut(&eid, &sid).unwrap();
-        current_section.title = section.title;
+        let title: String = std::mem::replace(&mut current_section.title, section.title);
+        self.history
+            .record("Rename Section", Revision::SectionRenamed { sid, title });
         Ok(())
     }
 
@@ -226,6 +354,8 @@ impl LocalEntryState {
             let sid: i64 = SectionRepository::insert(&self.connection, &eid, &new_section)?;
             let active_entry = self.get_entry_mut(&eid).unwrap();
             active_entry.sections.push((sid, new_section));
+            self.history
+                .record("Create Section", Revision::SectionCreated { eid, sid });
             Ok(sid)
         } else {
             Err(Report::msg("No active entry found"))
@@ -267,7 +397,11 @@ impl LocalEntryState {
             if index_of_section == usize::MAX {
                 return Err(Report::msg("Section not found"));
             }
-            entry.sections.remove(index_of_section);
+            let (_sid, section) = entry.sections.remove(index_of_section);
+            self.history.record(
+                "Delete Section",
+                Revision::SectionDeleted { eid, sid, section },
+            );
             Ok(())
         } else {
             Err(Report::msg("Entry not found"))
@@ -318,10 +452,224 @@ impl LocalEntryState {
         self.ordered_entries = new_ordered_entries;
     }
 
+    /// The built-in templates followed by the ones saved in the glyph.
+    pub fn read_layout_templates(&self) -> Result<Vec<LayoutTemplate>> {
+        let mut templates: Vec<LayoutTemplate> = LayoutTemplate::builtins();
+        templates.extend(LayoutTemplateRepository::read_all(&self.connection)?);
+        Ok(templates)
+    }
+    /// Save the layout of the active entry as a template, replacing the one of the same name.
+    pub fn save_layout_template_db(&mut self, template_name: &str) -> Result<()> {
+        let layout: Layout = self
+            .get_active_entry_ref()
+            .ok_or(Report::msg("No active entry found"))?
+            .layout
+            .clone();
+        let id: i64 = LayoutTemplateRepository::upsert(&self.connection, template_name, &layout)?;
+        // The entries linked to a replaced template follow it.
+        let mut linked_eids: Vec<i64> = Vec::new();
+        for (eid, entry) in self.entries.iter_mut() {
+            if entry
+                .shared_layout
+                .as_ref()
+                .is_some_and(|(shared_layout_id, _name)| *shared_layout_id == id)
+            {
+                entry.layout = layout.clone();
+                linked_eids.push(*eid);
+            }
+        }
+        for eid in &linked_eids {
+            self.take_snapshot(eid);
+        }
+        Ok(())
+    }
+    pub fn delete_layout_template_db(&mut self, id: &i64) -> Result<()> {
+        if LayoutTemplateRepository::delete(&self.connection, id)? != 1 {
+            return Err(Report::msg(
+                "Tried to delete a template that does not exist",
+            ));
+        }
+        for (_eid, entry) in self.entries.iter_mut() {
+            if entry
+                .shared_layout
+                .as_ref()
+                .is_some_and(|(shared_layout_id, _name)| shared_layout_id == id)
+            {
+                entry.shared_layout = None;
+            }
+        }
+        Ok(())
+    }
+    /// Lay the active entry out by the template, its sections are kept as they are.
+    pub fn apply_layout_template(&mut self, template: &LayoutTemplate) -> Result<()> {
+        let eid: i64 = self
+            .active_entry_id
+            .ok_or(Report::msg("No active entry found"))?;
+        self.get_entry_mut(&eid).unwrap().layout = template.layout.clone();
+        self.set_entry_unsaved(eid, true)
+    }
+
+    /// Link the entry to a saved template, its layout is then shared with the other entries linked to it.
+    pub fn link_shared_layout_db(&mut self, eid: &i64, template: &LayoutTemplate) -> Result<()> {
+        let id: i64 = template
+            .id
+            .ok_or(Report::msg("Only a saved template could be shared"))?;
+        // A linked entry may hold changes to the layout not saved yet.
+        let layout: Layout = self
+            .entries
+            .iter()
+            .find(|(_eid, entry)| {
+                entry
+                    .shared_layout
+                    .as_ref()
+                    .is_some_and(|(shared_layout_id, _name)| *shared_layout_id == id)
+            })
+            .map_or(template.layout.clone(), |(_eid, entry)| {
+                entry.layout.clone()
+            });
+        EntryRepository::update_shared_layout(&self.connection, eid, Some(id), &layout)?;
+        let entry: &mut Entry = self
+            .get_entry_mut(eid)
+            .ok_or(Report::msg("No entry found"))?;
+        entry.layout = layout;
+        entry.shared_layout = Some((id, template.name.clone()));
+        self.take_snapshot(eid);
+        Ok(())
+    }
+    /// Turn the shared layout of the entry back into a copy of its own.
+    pub fn detach_shared_layout_db(&mut self, eid: &i64) -> Result<()> {
+        let entry: &mut Entry = self
+            .get_entry_mut(eid)
+            .ok_or(Report::msg("No entry found"))?;
+        if entry.shared_layout.is_none() {
+            return Ok(());
+        }
+        entry.shared_layout = None;
+        let layout: Layout = entry.layout.clone();
+        EntryRepository::update_shared_layout(&self.connection, eid, None, &layout)
+    }
+
     /// Reload layout
     pub fn reload_layout(&mut self, eid: &i64) {
         let item = EntryRepository::read_by_id(&self.connection, eid).unwrap();
         self.get_entry_mut(eid).unwrap().layout = item.1.layout;
+        self.take_snapshot(eid);
+    }
+
+    /*
+
+       History
+
+    */
+    /// Take back the last change, return its description.
+    /// The history is set aside meanwhile, taking a change back is not recorded as one.
+    pub fn undo(&mut self) -> Result<Option<String>> {
+        let mut history: History = std::mem::take(&mut self.history);
+        let result = match history.pop_undo() {
+            Some((description, revision)) => self.revert(revision).map(|revision| {
+                history.push_redo(description.clone(), revision);
+                Some(description)
+            }),
+            None => Ok(None),
+        };
+        self.history = history;
+        result
+    }
+    /// Make the last change taken back again, return its description.
+    pub fn redo(&mut self) -> Result<Option<String>> {
+        let mut history: History = std::mem::take(&mut self.history);
+        let result = match history.pop_redo() {
+            Some((description, revision)) => self.revert(revision).map(|revision| {
+                history.push_undo(description.clone(), revision);
+                Some(description)
+            }),
+            None => Ok(None),
+        };
+        self.history = history;
+        result
+    }
+
+    /*
+
+       Recovery Journal
+
+    */
+    /// The journal lives next to the glyph, an in-memory database has none.
+    pub fn journal_path(&self) -> Option<PathBuf> {
+        match self.connection.path() {
+            Some(path) if !path.is_empty() => Some(PathBuf::from(format!("{}.journal", path))),
+            _ => None,
+        }
+    }
+
+    /// Write the unsaved entries to the journal, remove it once there are none.
+    pub fn write_journal(&self) -> Result<()> {
+        let Some(path) = self.journal_path() else {
+            return Ok(());
+        };
+        if self.updated_entries.is_empty() {
+            return self.discard_journal();
+        }
+        let journal: Vec<JournalEntry> = self
+            .entries
+            .iter()
+            .filter(|(eid, _entry)| self.updated_entries.contains(eid))
+            .map(|(eid, entry)| JournalEntry {
+                eid: *eid,
+                layout: entry.layout.clone(),
+                sections: entry.sections.clone(),
+            })
+            .collect();
+        fs::write(path, serde_json::to_string(&journal)?)?;
+        Ok(())
+    }
+
+    /// Whether a journal left by an interrupted session exists.
+    pub fn has_journal(&self) -> bool {
+        self.journal_path().is_some_and(|path| path.exists())
+    }
+
+    /// Load the journal into the local state and mark the restored entries as unsaved.
+    /// Sections and entries deleted since the journal was written are skipped.
+    /// Return the number of entries restored.
+    pub fn restore_journal(&mut self) -> Result<usize> {
+        let Some(path) = self.journal_path() else {
+            return Ok(0);
+        };
+        let journal: Vec<JournalEntry> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let mut num_restored: usize = 0;
+        for journal_entry in journal {
+            let Some(entry) = self.get_entry_mut(&journal_entry.eid) else {
+                continue;
+            };
+            entry.layout = journal_entry.layout;
+            for (sid, journal_section) in journal_entry.sections {
+                if let Some((_sid, section)) = entry
+                    .sections
+                    .iter_mut()
+                    .find(|(_sid, _section)| *_sid == sid)
+                {
+                    *section = journal_section;
+                }
+            }
+            self.sort_sections_by_position(&journal_entry.eid);
+            self.take_snapshot(&journal_entry.eid);
+            self.sync_shared_layout(&journal_entry.eid);
+            self.updated_entries.insert(journal_entry.eid);
+            num_restored += 1;
+        }
+        self.write_journal()?;
+        Ok(num_restored)
+    }
+
+    /// Remove the journal, the unsaved changes in it are lost.
+    pub fn discard_journal(&self) -> Result<()> {
+        if let Some(path) = self.journal_path()
+            && path.exists()
+        {
+            fs::remove_file(path)?;
+        }
+        Ok(())
     }
 
     /*
@@ -330,6 +678,188 @@ impl LocalEntryState {
 
     */
 
+    /// Copy the layout of the entry to the other entries linked to the same shared layout.
+    fn sync_shared_layout(&mut self, eid: &i64) {
+        let Some(entry) = self.get_entry_ref(eid) else {
+            return;
+        };
+        let Some((shared_layout_id, _name)) = entry.shared_layout.clone() else {
+            return;
+        };
+        let layout: Layout = entry.layout.clone();
+        let mut linked_eids: Vec<i64> = Vec::new();
+        for (other_eid, entry) in self.entries.iter_mut() {
+            if *other_eid != *eid
+                && entry
+                    .shared_layout
+                    .as_ref()
+                    .is_some_and(|(id, _name)| *id == shared_layout_id)
+            {
+                entry.layout = layout.clone();
+                linked_eids.push(*other_eid);
+            }
+        }
+        // The change is recorded on the entry it was made to only.
+        for other_eid in &linked_eids {
+            self.take_snapshot(other_eid);
+        }
+    }
+
+    /// Keep the layout and section positions of the entry, the next changes are told apart from them.
+    fn take_snapshot(&mut self, eid: &i64) {
+        if let Some(entry) = self.get_entry_ref(eid) {
+            let positions: Vec<(i64, i64)> = entry
+                .sections
+                .iter()
+                .map(|(sid, section)| (*sid, section.position))
+                .collect();
+            self.snapshots
+                .insert(*eid, (entry.layout.clone(), positions));
+        }
+    }
+
+    /// Record the changes made to the layout and to the section positions of the entry since its snapshot.
+    fn record_changes(&mut self, eid: &i64) {
+        let mut revisions: Vec<(&str, Revision)> = Vec::new();
+        if let Some(entry) = self.get_entry_ref(eid)
+            && let Some((layout, positions)) = self.snapshots.get(eid)
+        {
+            if *layout != entry.layout {
+                revisions.push((
+                    "Edit Layout",
+                    Revision::Layout {
+                        eid: *eid,
+                        layout: layout.clone(),
+                    },
+                ));
+            }
+            let moved_positions: Vec<(i64, i64)> = positions
+                .iter()
+                .filter(|(sid, position)| {
+                    entry
+                        .sections
+                        .iter()
+                        .any(|(_sid, section)| *_sid == *sid && section.position != *position)
+                })
+                .copied()
+                .collect();
+            if !moved_positions.is_empty() {
+                revisions.push((
+                    "Reorder Sections",
+                    Revision::SectionPositions {
+                        eid: *eid,
+                        positions: moved_positions,
+                    },
+                ));
+            }
+        }
+        for (description, revision) in revisions {
+            self.history.record(description, revision);
+        }
+        self.take_snapshot(eid);
+    }
+
+    /// Take a recorded change back, return the change which takes it back in turn.
+    fn revert(&mut self, revision: Revision) -> Result<Revision> {
+        match revision {
+            Revision::Layout { eid, layout } => {
+                let entry: &mut Entry = self
+                    .get_entry_mut(&eid)
+                    .ok_or(Report::msg("Entry not found"))?;
+                let layout: Layout = std::mem::replace(&mut entry.layout, layout);
+                self.take_snapshot(&eid);
+                self.set_entry_unsaved(eid, true)?;
+                Ok(Revision::Layout { eid, layout })
+            }
+            Revision::SectionPositions { eid, positions } => {
+                let mut moved_positions: Vec<(i64, i64)> = Vec::new();
+                for (sid, position) in positions {
+                    if let Some(section) = self.get_section_mut(&eid, &sid) {
+                        moved_positions.push((sid, section.position));
+                        section.position = position;
+                    }
+                }
+                self.sort_sections_by_position(&eid);
+                self.take_snapshot(&eid);
+                self.set_entry_unsaved(eid, true)?;
+                Ok(Revision::SectionPositions {
+                    eid,
+                    positions: moved_positions,
+                })
+            }
+            Revision::SectionCreated { eid, sid } => {
+                let section: Section = self
+                    .get_section_ref(&eid, &sid)
+                    .ok_or(Report::msg("Section not found"))?
+                    .clone();
+                self.delete_section_db(&sid)?;
+                Ok(Revision::SectionDeleted { eid, sid, section })
+            }
+            Revision::SectionDeleted { eid, sid, section } => {
+                SectionRepository::insert_with_id(&self.connection, &eid, &sid, &section)?;
+                self.get_entry_mut(&eid)
+                    .ok_or(Report::msg("Entry not found"))?
+                    .sections
+                    .push((sid, section));
+                self.sort_sections_by_position(&eid);
+                self.take_snapshot(&eid);
+                Ok(Revision::SectionCreated { eid, sid })
+            }
+            Revision::SectionRenamed { sid, title } => {
+                let (eid, sid, _section) = SectionRepository::read_by_id(&self.connection, &sid)?
+                    .ok_or(Report::msg("Section not found"))?;
+                let current_title: String = self
+                    .get_section_ref(&eid, &sid)
+                    .ok_or(Report::msg("Section not found"))?
+                    .title
+                    .clone();
+                self.update_section_name_db(&sid, title.as_str())?;
+                Ok(Revision::SectionRenamed {
+                    sid,
+                    title: current_title,
+                })
+            }
+            Revision::EntryRenamed { eid, name } => {
+                let current_name: String = self
+                    .get_entry_ref(&eid)
+                    .ok_or(Report::msg("Entry not found"))?
+                    .entry_name
+                    .clone();
+                self.update_entry_name_db(&eid, name.as_str())?;
+                Ok(Revision::EntryRenamed {
+                    eid,
+                    name: current_name,
+                })
+            }
+            Revision::EntryCreated { eid } => {
+                let revision: Revision = self.deleted_entry_revision(&eid)?;
+                self.delete_entry_db(&eid)?;
+                Ok(revision)
+            }
+            Revision::EntryDeleted {
+                eid,
+                saved,
+                unsaved,
+            } => {
+                EntryRepository::insert_with_id(&self.connection, &eid, &saved)?;
+                for (sid, section) in &saved.sections {
+                    SectionRepository::insert_with_id(&self.connection, &eid, sid, section)?;
+                }
+                let (eid, entry) = EntryRepository::read_by_id(&self.connection, &eid)?;
+                self.entries.push((eid, entry));
+                self.reconstruct_entry_order();
+                // The unsaved changes stay unsaved, as before the entry was deleted.
+                if let Some(unsaved) = unsaved {
+                    *self.get_entry_mut(&eid).unwrap() = *unsaved;
+                    self.updated_entries.insert(eid);
+                    self.write_journal()?;
+                }
+                self.take_snapshot(&eid);
+                Ok(Revision::EntryCreated { eid })
+            }
+        }
+    }
+
     /// Fetch local entry to new sorted list of entry_order
     fn reconstruct_entry_order(&mut self) {
         let mut new_ordered_entries: Vec<(i64, String)> = self
@@ -365,3 +895,222 @@ impl LocalEntryState {
         max
     }
 }
+
+/// Glyphs for the tests, shared by the modules opening one.
+#[cfg(test)]
+pub(crate) mod test_glyph {
+    use super::*;
+    use crate::db::GlyphRepository;
+
+    /// A glyph in the temporary directory, deleted when dropped even if the test fails.
+    pub(crate) struct TempGlyph {
+        pub path: PathBuf,
+    }
+    impl TempGlyph {
+        /// A fresh glyph named after the test, unique to the process.
+        pub fn new(name: &str) -> Self {
+            let path: PathBuf =
+                std::env::temp_dir().join(format!("{}_{}.glyph", name, std::process::id()));
+            let _ = fs::remove_file(&path);
+            Self { path }
+        }
+        pub fn connect(&self) -> Connection {
+            GlyphRepository::init_glyph_db(&self.path).unwrap()
+        }
+        /// Open the glyph again, as a new session would.
+        pub fn reopen(&self) -> LocalEntryState {
+            LocalEntryState::new(self.connect())
+        }
+    }
+    impl Drop for TempGlyph {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::test_glyph::TempGlyph;
+    use super::*;
+
+    /// Open a fresh glyph named after the test, along with the guard deleting it.
+    fn temp_state(name: &str) -> (LocalEntryState, TempGlyph) {
+        let glyph: TempGlyph = TempGlyph::new(name);
+        (glyph.reopen(), glyph)
+    }
+
+    #[test]
+    fn test_journal_restore() {
+        let (mut state, glyph) = temp_state("journal");
+        let eid: i64 = state.create_default_entry_db("entry").unwrap();
+        state.active_entry_id = Some(eid);
+        let sid: i64 = state
+            .create_section_to_active_entry_db("section", "saved")
+            .unwrap();
+        state.get_section_mut(&eid, &sid).unwrap().content = String::from("unsaved");
+        state.set_entry_unsaved(eid, true).unwrap();
+        assert!(state.has_journal());
+        drop(state);
+
+        // Reopen as if the session was interrupted.
+        let mut state = glyph.reopen();
+        assert_eq!(state.get_section_ref(&eid, &sid).unwrap().content, "saved");
+        assert_eq!(state.restore_journal().unwrap(), 1);
+        assert_eq!(
+            state.get_section_ref(&eid, &sid).unwrap().content,
+            "unsaved"
+        );
+        assert_eq!(state.get_unsaved_entry_names(), vec![String::from("entry")]);
+
+        assert_eq!(state.save_updated_entries_db().unwrap(), 1);
+        assert!(!state.has_journal());
+        drop(state);
+        let state = glyph.reopen();
+        assert_eq!(
+            state.get_section_ref(&eid, &sid).unwrap().content,
+            "unsaved"
+        );
+    }
+
+    #[test]
+    fn test_layout_templates() {
+        let (mut state, glyph) = temp_state("templates");
+        let builtin_count: usize = LayoutTemplate::builtins().len();
+        let cornell: LayoutTemplate = LayoutTemplate::builtins().pop().unwrap();
+        let eid: i64 = state
+            .create_entry_from_template_db("notes", &cornell)
+            .unwrap();
+        state.active_entry_id = Some(eid);
+        assert_eq!(state.get_num_sections(&eid), 3);
+
+        state.save_layout_template_db("mine").unwrap();
+        state.save_layout_template_db("mine").unwrap();
+        let templates: Vec<LayoutTemplate> = state.read_layout_templates().unwrap();
+        assert_eq!(templates.len(), builtin_count + 1);
+        let saved: &LayoutTemplate = templates.last().unwrap();
+        assert_eq!(saved.name, "mine");
+        assert_eq!(saved.sections().len(), 3);
+
+        state.delete_layout_template_db(&saved.id.unwrap()).unwrap();
+        assert!(state.delete_layout_template_db(&saved.id.unwrap()).is_err());
+        assert_eq!(state.read_layout_templates().unwrap().len(), builtin_count);
+    }
+
+    #[test]
+    fn test_shared_layout() {
+        let (mut state, glyph) = temp_state("shared");
+        let first: i64 = state.create_default_entry_db("first").unwrap();
+        let second: i64 = state.create_default_entry_db("second").unwrap();
+        state.active_entry_id = Some(first);
+        state.save_layout_template_db("meeting").unwrap();
+        let template: LayoutTemplate = state.read_layout_templates().unwrap().pop().unwrap();
+        state.link_shared_layout_db(&first, &template).unwrap();
+        state.link_shared_layout_db(&second, &template).unwrap();
+
+        // Editing the layout of one entry changes the other.
+        state.get_entry_mut(&first).unwrap().layout.label = String::from("Edited");
+        state.set_entry_unsaved(first, true).unwrap();
+        assert_eq!(state.get_entry_ref(&second).unwrap().layout.label, "Edited");
+        state.save_updated_entries_db().unwrap();
+        drop(state);
+
+        let mut state = glyph.reopen();
+        for eid in [first, second] {
+            let entry: &Entry = state.get_entry_ref(&eid).unwrap();
+            assert_eq!(entry.layout.label, "Edited");
+            assert_eq!(
+                entry.shared_layout,
+                Some((template.id.unwrap(), String::from("meeting")))
+            );
+        }
+
+        // A detached entry keeps a copy of its own.
+        state.detach_shared_layout_db(&second).unwrap();
+        state.get_entry_mut(&first).unwrap().layout.label = String::from("Again");
+        state.set_entry_unsaved(first, true).unwrap();
+        assert_eq!(state.get_entry_ref(&second).unwrap().layout.label, "Edited");
+        state.save_updated_entries_db().unwrap();
+
+        // Deleting the template leaves its layout to the entries linked to it.
+        state
+            .delete_layout_template_db(&template.id.unwrap())
+            .unwrap();
+        assert!(state.get_entry_ref(&first).unwrap().shared_layout.is_none());
+        drop(state);
+        let state = glyph.reopen();
+        let entry: &Entry = state.get_entry_ref(&first).unwrap();
+        assert_eq!(entry.layout.label, "Again");
+        assert!(entry.shared_layout.is_none());
+        assert_eq!(state.get_entry_ref(&second).unwrap().layout.label, "Edited");
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let (mut state, glyph) = temp_state("history");
+        let eid: i64 = state.create_default_entry_db("entry").unwrap();
+        state.active_entry_id = Some(eid);
+        let first: i64 = state
+            .create_section_to_active_entry_db("first", "content")
+            .unwrap();
+        let second: i64 = state
+            .create_section_to_active_entry_db("second", "")
+            .unwrap();
+
+        // Layout edits and section moves are told apart on marking the entry unsaved.
+        state.get_entry_mut(&eid).unwrap().layout.label = String::from("Edited");
+        state.set_entry_unsaved(eid, true).unwrap();
+        state.get_section_mut(&eid, &second).unwrap().position = 0;
+        state.sort_sections_by_position(&eid);
+        state.set_entry_unsaved(eid, true).unwrap();
+        state.update_section_name_db(&first, "renamed").unwrap();
+        state.update_entry_name_db(&eid, "renamed").unwrap();
+
+        assert_eq!(state.undo().unwrap().unwrap(), "Rename Entry");
+        assert_eq!(state.get_entry_ref(&eid).unwrap().entry_name, "entry");
+        assert_eq!(state.undo().unwrap().unwrap(), "Rename Section");
+        assert_eq!(state.get_section_ref(&eid, &first).unwrap().title, "first");
+        assert_eq!(state.undo().unwrap().unwrap(), "Reorder Sections");
+        assert_eq!(state.get_sections_sid(&eid), vec![first, second]);
+        assert_eq!(state.undo().unwrap().unwrap(), "Edit Layout");
+        assert_eq!(state.get_entry_ref(&eid).unwrap().layout.label, "Root");
+        assert_eq!(state.redo().unwrap().unwrap(), "Edit Layout");
+        assert_eq!(state.get_entry_ref(&eid).unwrap().layout.label, "Edited");
+        assert_eq!(state.redo().unwrap().unwrap(), "Reorder Sections");
+        assert_eq!(state.get_sections_sid(&eid), vec![second, first]);
+
+        // Deleted sections and entries come back under the same ids.
+        state.delete_section_db(&first).unwrap();
+        assert_eq!(state.undo().unwrap().unwrap(), "Delete Section");
+        assert_eq!(
+            state.get_section_ref(&eid, &first).unwrap().content,
+            "content"
+        );
+        // A deleted entry comes back as saved, its unsaved changes are left unsaved.
+        state.get_section_mut(&eid, &first).unwrap().content = String::from("unsaved");
+        state.set_entry_unsaved(eid, true).unwrap();
+        state.delete_active_entry_db().unwrap();
+        assert!(state.get_entry_ref(&eid).is_none());
+        assert_eq!(state.undo().unwrap().unwrap(), "Delete Entry");
+        assert_eq!(state.get_num_sections(&eid), 2);
+        assert_eq!(
+            state.get_section_ref(&eid, &first).unwrap().content,
+            "unsaved"
+        );
+        assert_eq!(state.get_unsaved_entry_names(), vec![String::from("entry")]);
+        assert_eq!(state.redo().unwrap().unwrap(), "Delete Entry");
+        assert!(state.get_entry_ref(&eid).is_none());
+        assert!(state.redo().unwrap().is_none());
+        assert_eq!(state.undo().unwrap().unwrap(), "Delete Entry");
+        state.discard_journal().unwrap();
+        drop(state);
+
+        let state = glyph.reopen();
+        assert_eq!(state.get_num_sections(&eid), 2);
+        assert_eq!(
+            state.get_section_ref(&eid, &first).unwrap().content,
+            "content"
+        );
+        assert_eq!(state.get_entry_ref(&eid).unwrap().layout.label, "Root");
+    }
+}
@@ -1,72 +1,419 @@
-use ratatui::style::{Color, Modifier, Style};
-pub trait Theme {
+use crate::utils::config_dir;
+use color_eyre::eyre::{Report, Result};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::{Deserialize, Deserializer};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Colors of the application, loaded from `themes/*.toml` (or `*.json`) in the config directory.
+///
+/// Colors are written like `"#1d242a"` or `"dark-gray"`, the missing ones are taken from Iceberg.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Default to the file name.
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_color")]
+    background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    font: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    on_surface: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    surface_low: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    surface_low_highlight: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    surface_high: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    surface_high_highlight: Color,
+    /// Colors the theme has been degraded to.
+    #[serde(skip)]
+    color_support: ColorSupport,
+}
+impl Theme {
+    pub fn iceberg() -> Self {
+        Self {
+            name: String::from("Iceberg"),
+            background: Color::Rgb(24, 27, 30),
+            font: Color::Rgb(199, 221, 246),
+            on_surface: Color::Rgb(189, 211, 236),
+            surface_low: Color::Rgb(29, 36, 42),
+            surface_low_highlight: Color::Rgb(51, 58, 64),
+            surface_high: Color::Rgb(117, 151, 181),
+            surface_high_highlight: Color::Rgb(127, 161, 191),
+            color_support: ColorSupport::TrueColor,
+        }
+    }
+    pub fn light() -> Self {
+        Self {
+            name: String::from("Light"),
+            background: Color::Rgb(250, 250, 250),
+            font: Color::Rgb(36, 41, 47),
+            on_surface: Color::Rgb(52, 58, 64),
+            surface_low: Color::Rgb(240, 241, 243),
+            surface_low_highlight: Color::Rgb(216, 222, 228),
+            surface_high: Color::Rgb(84, 120, 160),
+            surface_high_highlight: Color::Rgb(74, 110, 150),
+            color_support: ColorSupport::TrueColor,
+        }
+    }
+    pub fn high_contrast() -> Self {
+        Self {
+            name: String::from("High Contrast"),
+            background: Color::Rgb(0, 0, 0),
+            font: Color::Rgb(255, 255, 255),
+            on_surface: Color::Rgb(255, 255, 255),
+            surface_low: Color::Rgb(0, 0, 0),
+            surface_low_highlight: Color::Rgb(0, 55, 218),
+            surface_high: Color::Rgb(255, 215, 0),
+            surface_high_highlight: Color::Rgb(255, 235, 90),
+            color_support: ColorSupport::TrueColor,
+        }
+    }
+    /// The themes shipped with the application, Iceberg first.
+    pub fn builtins() -> Vec<Theme> {
+        vec![Theme::iceberg(), Theme::light(), Theme::high_contrast()]
+    }
+    /// Map the colors to the nearest ones the terminal could display.
+    pub fn degraded(mut self, color_support: ColorSupport) -> Theme {
+        for color in [
+            &mut self.background,
+            &mut self.font,
+            &mut self.on_surface,
+            &mut self.surface_low,
+            &mut self.surface_low_highlight,
+            &mut self.surface_high,
+            &mut self.surface_high_highlight,
+        ] {
+            *color = color_support.map(*color);
+        }
+        self.color_support = color_support;
+        self
+    }
+    /// Map a color outside of the theme like the theme colors.
+    pub fn color(&self, color: Color) -> Color {
+        self.color_support.map(color)
+    }
+
+    /// Tint the item with a color outside of the theme, or mark it with the modifier when there
+    /// is no color to tell it apart with.
+    pub fn accent<'a, T: Stylize<'a, T>>(&self, item: T, color: Color, modifier: Modifier) -> T {
+        if self.color_support == ColorSupport::NoColor {
+            item.add_modifier(modifier)
+        } else {
+            item.fg(self.color(color))
+        }
+    }
+
     /// Background of the whole application, only use once.
-    fn background(&self) -> Color;
+    pub fn background(&self) -> Color {
+        self.background
+    }
 
     /// Font Color.
-    fn font(&self) -> Color;
+    pub fn font(&self) -> Color {
+        self.font
+    }
 
     /// This determines the object being rendered on a surface.
-    fn on_surface(&self) -> Color;
+    pub fn on_surface(&self) -> Color {
+        self.on_surface
+    }
 
     /// This determines the background of the surface. Normally used in panel/pages.
-    fn surface_low(&self) -> Color;
+    pub fn surface_low(&self) -> Color {
+        self.surface_low
+    }
 
     /// Used for highlighting on surface_low
-    fn surface_low_highlight(&self) -> Color;
+    pub fn surface_low_highlight(&self) -> Color {
+        self.surface_low_highlight
+    }
 
     /// This determines the background of the surface. Normally used in popups/dialogs.
-    fn surface_high(&self) -> Color;
+    pub fn surface_high(&self) -> Color {
+        self.surface_high
+    }
 
-    fn surface_high_highlight(&self) -> Color;
+    pub fn surface_high_highlight(&self) -> Color {
+        self.surface_high_highlight
+    }
+
+    /// Style of the hovered item, reversed when there is no color to highlight with.
+    pub fn highlight(&self) -> Style {
+        if self.color_support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.surface_low_highlight)
+        }
+    }
 
     /// Style of bolded text.
-    fn bold(&self) -> Style;
+    pub fn bold(&self) -> Style {
+        Style::default().add_modifier(Modifier::BOLD)
+    }
 
     /// Style of italic text.
-    fn italic(&self) -> Style;
+    pub fn italic(&self) -> Style {
+        Style::default().add_modifier(Modifier::ITALIC)
+    }
 
     /// Style of underline text.
-    fn underline(&self) -> Style;
+    pub fn underline(&self) -> Style {
+        Style::default().add_modifier(Modifier::UNDERLINED)
+    }
 
     /// Style of strikethrough text.
-    fn strikethrough(&self) -> Style;
+    pub fn strikethrough(&self) -> Style {
+        Style::default().add_modifier(Modifier::CROSSED_OUT)
+    }
 }
-pub struct Iceberg;
-impl Theme for Iceberg {
-    fn background(&self) -> Color {
-        Color::Rgb(24, 27, 30)
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::iceberg()
     }
-    fn font(&self) -> Color {
-        Color::Rgb(199, 221, 246)
+}
+
+/// Colors the terminal is able to display.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSupport {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// Requested through `NO_COLOR`, only modifiers are used.
+    NoColor,
+}
+impl ColorSupport {
+    /// Detect the color support from `NO_COLOR`, `COLORTERM` and `TERM`.
+    pub fn detect() -> Self {
+        let var = |key: &str| std::env::var(key).ok();
+        ColorSupport::from_env(
+            var("NO_COLOR").as_deref(),
+            var("COLORTERM").as_deref(),
+            var("TERM").as_deref(),
+        )
     }
-    fn on_surface(&self) -> Color {
-        Color::Rgb(189, 211, 236)
+    fn from_env(no_color: Option<&str>, color_term: Option<&str>, term: Option<&str>) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::NoColor;
+        }
+        if let Some("truecolor" | "24bit") = color_term {
+            return ColorSupport::TrueColor;
+        }
+        match term {
+            // Consoles without TERM, like on Windows, display true colors.
+            None => ColorSupport::TrueColor,
+            Some("dumb") => ColorSupport::NoColor,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => {
+                ColorSupport::TrueColor
+            }
+            Some(term) if term.contains("256") => ColorSupport::Ansi256,
+            Some(_) => ColorSupport::Ansi16,
+        }
     }
-    fn surface_low(&self) -> Color {
-        Color::Rgb(29, 36, 42)
+    /// The nearest color that could be displayed, only rgb colors are mapped.
+    pub fn map(&self, color: Color) -> Color {
+        match (self, color) {
+            (ColorSupport::NoColor, _) => Color::Reset,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(ansi256(r, g, b)),
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => ansi16(r, g, b),
+            _ => color,
+        }
     }
+}
+
+/// Levels of the 6x6x6 color cube of the 256 colors palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+/// The 16 colors with their usual xterm values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
 
-    fn surface_low_highlight(&self) -> Color {
-        Color::Rgb(51, 58, 64)
+/// Index of the nearest color among the color cube and the gray ramp of the 256 colors palette.
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|index| (CUBE_LEVELS[*index] as i32 - value as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r_index, g_index, b_index) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube_color = (
+        CUBE_LEVELS[r_index],
+        CUBE_LEVELS[g_index],
+        CUBE_LEVELS[b_index],
+    );
+    // The gray ramp goes from 8 to 238 by 10.
+    let average: u32 = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index: u32 = (average.saturating_sub(3) / 10).min(23);
+    let gray_level: u8 = (8 + gray_index * 10) as u8;
+    if distance((gray_level, gray_level, gray_level), (r, g, b)) < distance(cube_color, (r, g, b)) {
+        232 + gray_index as u8
+    } else {
+        16 + (36 * r_index + 6 * g_index + b_index) as u8
     }
+}
+
+/// The nearest of the 16 colors.
+fn ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_color, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _rgb)| *color)
+        .unwrap_or(Color::Reset)
+}
 
-    fn surface_high(&self) -> Color {
-        Color::Rgb(117, 151, 181)
+/// Load the user themes sorted by name, a file failing to load is reported and skipped.
+pub fn load_user_themes() -> (Vec<Theme>, Vec<Report>) {
+    let mut themes: Vec<Theme> = Vec::new();
+    let mut reports: Vec<Report> = Vec::new();
+    let Some(dir) = config_dir().map(|dir| dir.join("themes")) else {
+        return (themes, reports);
+    };
+    let Ok(dir_entries) = fs::read_dir(&dir) else {
+        return (themes, reports);
+    };
+    for path in dir_entries.flatten().map(|dir_entry| dir_entry.path()) {
+        match load_theme(&path) {
+            Ok(Some(theme)) => themes.push(theme),
+            Ok(None) => {}
+            Err(report) => reports.push(report),
+        }
     }
-    fn surface_high_highlight(&self) -> Color {
-        Color::Rgb(127, 161, 191)
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    (themes, reports)
+}
+
+/// Load a theme file, files other than toml and json are ignored.
+fn load_theme(path: &Path) -> Result<Option<Theme>> {
+    let parse = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => |content: &str| toml::from_str::<Theme>(content).map_err(Report::new),
+        Some("json") => |content: &str| serde_json::from_str::<Theme>(content).map_err(Report::new),
+        _ => return Ok(None),
+    };
+    let mut theme: Theme = parse(&fs::read_to_string(path)?)
+        .map_err(|report| report.wrap_err(format!("Invalid theme {}", path.display())))?;
+    if theme.name.is_empty() || theme.name == Theme::iceberg().name {
+        theme.name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
     }
-    fn bold(&self) -> Style {
-        Style::default().add_modifier(Modifier::BOLD)
+    Ok(Some(theme))
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let value: String = String::deserialize(deserializer)?;
+    Color::from_str(&value)
+        .map_err(|_| serde::de::Error::custom(format!("invalid color: {}", value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::text::Span;
+
+    #[test]
+    fn test_deserialize_theme() {
+        let theme: Theme = toml::from_str(
+            r##"
+                name = "Paper"
+                background = "#ffffff"
+                font = "black"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(theme.name, "Paper");
+        assert_eq!(theme.background(), Color::Rgb(255, 255, 255));
+        assert_eq!(theme.font(), Color::Black);
+        // Missing colors are taken from Iceberg.
+        assert_eq!(theme.surface_low(), Theme::iceberg().surface_low());
+
+        let theme: Theme = serde_json::from_str(r##"{ "on_surface": "#102030" }"##).unwrap();
+        assert_eq!(theme.on_surface(), Color::Rgb(16, 32, 48));
+        assert!(toml::from_str::<Theme>(r#"font = "not a color""#).is_err());
     }
-    fn italic(&self) -> Style {
-        Style::default().add_modifier(Modifier::ITALIC)
+    #[test]
+    fn test_detect_color_support() {
+        let detect = ColorSupport::from_env;
+        assert_eq!(
+            detect(Some("1"), Some("truecolor"), None),
+            ColorSupport::NoColor
+        );
+        assert_eq!(
+            detect(Some(""), Some("truecolor"), None),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            detect(None, Some("24bit"), Some("xterm")),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            detect(None, None, Some("xterm-256color")),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(detect(None, None, Some("xterm")), ColorSupport::Ansi16);
+        assert_eq!(detect(None, None, Some("dumb")), ColorSupport::NoColor);
     }
-    fn underline(&self) -> Style {
-        Style::default().add_modifier(Modifier::UNDERLINED)
+    #[test]
+    fn test_map_color() {
+        // Exact entries of the color cube and of the gray ramp.
+        assert_eq!(ansi256(0, 0, 0), 16);
+        assert_eq!(ansi256(255, 255, 255), 231);
+        assert_eq!(ansi256(95, 135, 175), 16 + 36 + 2 * 6 + 3);
+        assert_eq!(ansi256(128, 128, 128), 244);
+        // Iceberg background is closer to a gray than to the cube.
+        assert_eq!(ansi256(24, 27, 30), 234);
+
+        assert_eq!(ansi16(250, 250, 250), Color::White);
+        assert_eq!(ansi16(200, 10, 10), Color::Red);
+        assert_eq!(ansi16(24, 27, 30), Color::Black);
+
+        assert_eq!(
+            ColorSupport::Ansi16.map(Color::Rgb(0, 250, 250)),
+            Color::LightCyan
+        );
+        // Colors of the palette are kept.
+        assert_eq!(ColorSupport::Ansi256.map(Color::Red), Color::Red);
+        assert_eq!(ColorSupport::NoColor.map(Color::Red), Color::Reset);
     }
-    fn strikethrough(&self) -> Style {
-        Style::default().add_modifier(Modifier::CROSSED_OUT)
+    #[test]
+    fn test_degraded_theme() {
+        let theme: Theme = Theme::iceberg().degraded(ColorSupport::Ansi256);
+        assert_eq!(theme.background(), Color::Indexed(234));
+        assert_eq!(theme.highlight().bg, Some(theme.surface_low_highlight()));
+
+        let span: Span = theme.accent(Span::raw("INSERT"), Color::Yellow, Modifier::REVERSED);
+        assert_eq!(span.style.fg, Some(Color::Yellow));
+        assert!(span.style.add_modifier.is_empty());
+
+        let theme: Theme = Theme::iceberg().degraded(ColorSupport::NoColor);
+        assert_eq!(theme.font(), Color::Reset);
+        assert!(theme.highlight().add_modifier.contains(Modifier::REVERSED));
+        let span: Span = theme.accent(Span::raw("INSERT"), Color::Yellow, Modifier::REVERSED);
+        assert_eq!(span.style.fg, None);
+        assert!(span.style.add_modifier.contains(Modifier::REVERSED));
     }
 }
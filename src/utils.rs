@@ -131,6 +131,72 @@ pub fn number_to_roman(mut num: u16) -> String {
     roman.join("")
 }
 
+/// Score how well the query matches the candidate as a case-insensitive subsequence, higher is better.
+/// Consecutive chars and chars at the start of a word weigh more, `None` if the query does not match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    // Best score of the query so far, with its last char matched at each index of the candidate.
+    let mut scores: Vec<Option<i32>> = vec![None; candidate.len()];
+    for (i, q) in query.iter().enumerate() {
+        let mut next_scores: Vec<Option<i32>> = vec![None; candidate.len()];
+        for (j, c) in candidate.iter().enumerate() {
+            if !c.to_lowercase().eq(q.to_lowercase()) {
+                continue;
+            }
+            let is_word_start: bool = j == 0 || !candidate[j - 1].is_alphanumeric();
+            let base: i32 = if is_word_start { 3 } else { 1 };
+            next_scores[j] = if i == 0 {
+                Some(base)
+            } else {
+                (0..j)
+                    .filter_map(|k| {
+                        let consecutive: i32 = if k + 1 == j { 4 } else { 0 };
+                        let gap: i32 = (j - k - 1).min(4) as i32;
+                        scores[k].map(|score| score + base + consecutive - gap)
+                    })
+                    .max()
+            };
+        }
+        scores = next_scores;
+    }
+    scores.into_iter().flatten().max()
+}
+
+/// The directory holding user configuration, `$XDG_CONFIG_HOME/glyph` or `~/.config/glyph`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base: PathBuf = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("glyph"))
+}
+
+/// The directory holding state such as crash reports, `$XDG_STATE_HOME/glyph` or `~/.local/state/glyph`.
+pub fn state_dir() -> Option<PathBuf> {
+    let base: PathBuf = match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/state"),
+    };
+    Some(base.join("glyph"))
+}
+
+/// Turn a type name like `crate::app::page::GlyphReadView` into a title like "Glyph Read View".
+pub fn type_title(type_name: &str) -> String {
+    let name: &str = type_name.rsplit("::").next().unwrap_or(type_name);
+    let mut title: String = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !title.is_empty() {
+            title.push(' ');
+        }
+        title.push(c);
+    }
+    title
+}
+
 /// Auto-increment a name with suffix format ".00x"
 pub fn auto_increment_name(name: &str, existing_names: &[&str]) -> String {
     let mut new_name = name.to_string();
@@ -167,4 +233,18 @@ mod test {
         );
         assert_eq!(auto_increment_name("name", &[]), "name");
     }
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Create Entry"), Some(0));
+        assert!(fuzzy_score("cre", "Create Entry").is_some());
+        assert!(fuzzy_score("CE", "Create Entry").is_some());
+        assert!(fuzzy_score("ec", "Create Entry").is_none());
+        assert!(fuzzy_score("dup", "Duplicate Entry") > fuzzy_score("dup", "Update Position"));
+        assert!(fuzzy_score("ce", "Create Entry") > fuzzy_score("ce", "Delete Section"));
+    }
+    #[test]
+    fn test_type_title() {
+        assert_eq!(type_title("glyph::app::page::GlyphReadView"), "Glyph Read View");
+        assert_eq!(type_title("TextField"), "Text Field");
+    }
 }
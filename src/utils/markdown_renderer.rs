@@ -314,11 +314,11 @@ pub struct MarkdownRenderer<'a> {
     /// Containing table data in Row Major Alignment
     table: MarkdownTable<'a>,
     area: Rect,
-    theme: &'a dyn Theme,
+    theme: &'a Theme,
 }
 
 impl<'a> MarkdownRenderer<'a> {
-    pub fn create(area: Rect, theme: &'a dyn Theme) -> Self {
+    pub fn create(area: Rect, theme: &'a Theme) -> Self {
         Self {
             spans_buffer: Vec::new(),
             rows_area: area.rows().collect(),
@@ -335,6 +335,8 @@ impl<'a> MarkdownRenderer<'a> {
         }
     }
     /// Render a line to the buffer and increment a row number by 1.
+    ///
+    /// The rows past the area are still counted, so that the height of the content is known.
     fn render_buffer(&mut self, buffer: &mut Buffer) {
         if self.quote_state.level != 0 {
             self.spans_buffer
@@ -343,97 +345,42 @@ impl<'a> MarkdownRenderer<'a> {
 
         if let Some(line_area) = self.rows_area.get(self.render_row_index) {
             Line::from(self.spans_buffer.clone()).render(*line_area, buffer);
-            self.spans_buffer = Vec::new();
-            self.render_row_index += 1;
         }
+        self.spans_buffer = Vec::new();
+        self.render_row_index += 1;
     }
     fn render_header(&mut self, buffer: &mut Buffer, level: HeadingLevel) {
+        let (pixel_size, height): (PixelSize, u16) = match level {
+            HeadingLevel::H1 => (PixelSize::Full, 8),
+            HeadingLevel::H2 => (PixelSize::HalfWidth, 8),
+            HeadingLevel::H3 => (PixelSize::HalfHeight, 4),
+            HeadingLevel::H4 => (PixelSize::Quadrant, 4),
+            HeadingLevel::H5 => (PixelSize::Sextant, 3),
+            HeadingLevel::H6 => (PixelSize::Octant, 2),
+        };
         if let Some(line_area) = self.rows_area.get(self.render_row_index) {
-            match level {
-                HeadingLevel::H1 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::Full)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 8))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 8;
-                }
-                HeadingLevel::H2 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::HalfWidth)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 8))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 8;
-                }
-                HeadingLevel::H3 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::HalfHeight)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 4))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 4;
-                }
-                HeadingLevel::H4 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::Quadrant)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 4))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 4;
-                }
-                HeadingLevel::H5 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::Sextant)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 3))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 3;
-                }
-                HeadingLevel::H6 => {
-                    let text = BigText::builder()
-                        .lines([Line::from(self.spans_buffer.clone())])
-                        .pixel_size(PixelSize::Octant)
-                        .build();
-                    text.render(
-                        line_area
-                            .resize(Size::new(line_area.width, 2))
-                            .intersection(self.area),
-                        buffer,
-                    );
-                    self.render_row_index += 2;
-                }
-            }
+            let text = BigText::builder()
+                .lines([Line::from(self.spans_buffer.clone())])
+                .pixel_size(pixel_size)
+                .build();
+            text.render(
+                line_area
+                    .resize(Size::new(line_area.width, height))
+                    .intersection(self.area),
+                buffer,
+            );
         }
+        self.render_row_index += height as usize;
         self.spans_buffer = Vec::new();
     }
-    /// Render a markdown page in area, consume self.
-    pub fn render(mut self, str: &'a str, buffer: &mut Buffer) {
+    /// Number of rows taken by a markdown page rendered in the width.
+    pub fn measure(width: u16, str: &'a str, theme: &'a Theme) -> u16 {
+        let area: Rect = Rect::new(0, 0, width, 0);
+        MarkdownRenderer::create(area, theme).render(str, &mut Buffer::empty(area))
+    }
+    /// Render a markdown page in area, consume self. Return the number of rows of the page, which
+    /// may exceed the area.
+    pub fn render(mut self, str: &'a str, buffer: &mut Buffer) -> u16 {
         let mut options = Options::empty();
         options.insert(
             Options::ENABLE_TABLES
@@ -527,7 +474,7 @@ impl<'a> MarkdownRenderer<'a> {
                 }
                 Event::Code(text) => {
                     self.spans_buffer
-                        .push(Span::raw(text).bg(self.theme.surface_low_highlight()));
+                        .push(Span::raw(text).patch_style(self.theme.highlight()));
                 }
                 Event::Text(text) => {
                     if self.is_in_code_block {
@@ -589,7 +536,7 @@ impl<'a> MarkdownRenderer<'a> {
                         TagEnd::CodeBlock => {
                             self.is_in_code_block = false;
                             let text: Text =
-                                Text::from(self.code_lines).bg(self.theme.surface_low_highlight());
+                                Text::from(self.code_lines).patch_style(self.theme.highlight());
                             let text_height: usize = text.height();
                             let text_width: usize = text.width();
                             if let Some(line_area) = self.rows_area.get(self.render_row_index) {
@@ -603,8 +550,8 @@ impl<'a> MarkdownRenderer<'a> {
                                 let code_inner_area: Rect = code_block_frame.inner(code_block_area);
                                 text.render(code_inner_area, buffer);
                                 code_block_frame.render(code_block_area, buffer);
-                                self.render_row_index += text_height + 2;
                             }
+                            self.render_row_index += text_height + 2;
                             self.code_lines = Vec::new();
                         }
                         TagEnd::Table => {
@@ -631,6 +578,7 @@ impl<'a> MarkdownRenderer<'a> {
                 _ => {}
             }
         }
+        self.render_row_index.min(u16::MAX as usize) as u16
     }
 }
 bitflags! {
@@ -656,7 +604,7 @@ impl TextStyleBuilder {
     pub fn remove_flag(&mut self, flag: TextStyleFlag) {
         self.flags.remove(flag);
     }
-    pub fn build(&self, theme: &dyn Theme) -> Style {
+    pub fn build(&self, theme: &Theme) -> Style {
         let mut style: Style = Style::default().fg(theme.font());
         if self.flags.contains(TextStyleFlag::STRONG) {
             style = style.patch(theme.bold());
@@ -673,7 +621,10 @@ impl TextStyleBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::utils::markdown_renderer::MarkdownTable;
+    use crate::theme::Theme;
+    use crate::utils::markdown_renderer::{MarkdownRenderer, MarkdownTable};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
 
     #[test]
     fn test_table() {
@@ -685,4 +636,16 @@ mod test {
         table.set_width(1);
         assert_eq!(table.size(), (245, 1));
     }
+
+    #[test]
+    fn test_measure() {
+        let theme = Theme::default();
+        assert_eq!(MarkdownRenderer::measure(40, "# Title\n\nText", &theme), 9);
+        assert_eq!(MarkdownRenderer::measure(40, "```\na\nb\n```", &theme), 4);
+        // Lines are counted past the area rendered in.
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 1));
+        let height =
+            MarkdownRenderer::create(buffer.area, &theme).render("a\n\nb\n\nc", &mut buffer);
+        assert_eq!(height, 3);
+    }
 }